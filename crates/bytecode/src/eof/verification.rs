@@ -20,7 +20,20 @@ pub fn validate_raw_eof_inner(
     raw: Bytes,
     first_code_type: Option<CodeType>,
 ) -> Result<Eof, EofError> {
-    if raw.len() > MAX_INITCODE_SIZE {
+    validate_raw_eof_with_max_size(raw, first_code_type, MAX_INITCODE_SIZE)
+}
+
+/// Decodes `raw` into an [`Eof`] container and validates it.
+///
+/// Fails with [`EofDecodeError::InvalidEOFSize`] if `raw` is larger than `max_size`, e.g. the
+/// configured max initcode size.
+#[inline]
+pub fn validate_raw_eof_with_max_size(
+    raw: Bytes,
+    first_code_type: Option<CodeType>,
+    max_size: usize,
+) -> Result<Eof, EofError> {
+    if raw.len() > max_size {
         return Err(EofError::Decode(EofDecodeError::InvalidEOFSize));
     }
     let eof = Eof::decode(raw)?;
@@ -56,12 +69,7 @@ pub fn validate_eof_inner(eof: &Eof, first_code_type: Option<CodeType>) -> Resul
         // Validate the current container.
         let tracker_containers = validate_eof_codes(&eof, code_type)?;
         // Decode subcontainers and push them to the stack.
-        for (container, code_type) in eof
-            .body
-            .container_section
-            .iter()
-            .zip(tracker_containers.into_iter())
-        {
+        for (container, code_type) in eof.body.container_section.iter().zip(tracker_containers) {
             stack.push((Cow::Owned(Eof::decode(container.clone())?), Some(code_type)));
         }
     }
@@ -631,14 +639,13 @@ pub fn validate_eof_code(
                 }
                 tracker.set_subcontainer_type(index, CodeType::ReturnOrStop)?;
             }
-            opcode::RETURN | opcode::STOP => {
+            opcode::RETURN | opcode::STOP
                 if *tracker
                     .this_container_code_type
                     .get_or_insert(CodeType::ReturnOrStop)
-                    != CodeType::ReturnOrStop
-                {
-                    return Err(EofValidationError::SubContainerCalledInTwoModes);
-                }
+                    != CodeType::ReturnOrStop =>
+            {
+                return Err(EofValidationError::SubContainerCalledInTwoModes);
             }
             opcode::DATALOADN => {
                 let index = unsafe { read_u16(code.as_ptr().add(i + 1)) } as isize;
@@ -797,6 +804,34 @@ mod test {
         );
     }
 
+    #[test]
+    fn raw_eof_max_size() {
+        // Stop with a data section of 50000 bytes, over the default max initcode size.
+        let mut raw = hex!("ef0001010004020001000104c350000080000000").to_vec();
+        raw.resize(raw.len() + 0xc350, 0);
+        let raw = Bytes::from(raw);
+        assert!(raw.len() > MAX_INITCODE_SIZE);
+
+        assert_eq!(
+            validate_raw_eof(raw.clone()),
+            Err(EofError::Decode(EofDecodeError::InvalidEOFSize))
+        );
+        assert!(validate_raw_eof_with_max_size(
+            raw.clone(),
+            Some(CodeType::ReturnOrStop),
+            raw.len()
+        )
+        .is_ok());
+        assert_eq!(
+            validate_raw_eof_with_max_size(
+                raw.clone(),
+                Some(CodeType::ReturnOrStop),
+                raw.len() - 1
+            ),
+            Err(EofError::Decode(EofDecodeError::InvalidEOFSize))
+        );
+    }
+
     #[test]
     fn test5() {
         let err = validate_raw_eof(hex!("ef000101000402000100030400000000800000e5ffff").into());
@@ -19,7 +19,7 @@ pub use decode_errors::BytecodeDecodeError;
 pub use eof::{
     verification::{
         validate_eof, validate_eof_code, validate_eof_codes, validate_eof_inner, validate_raw_eof,
-        validate_raw_eof_inner, CodeType, EofValidationError,
+        validate_raw_eof_inner, validate_raw_eof_with_max_size, CodeType, EofValidationError,
     },
     Eof, EOF_MAGIC, EOF_MAGIC_BYTES, EOF_MAGIC_HASH,
 };
@@ -0,0 +1,90 @@
+use criterion::{
+    criterion_group, criterion_main, measurement::WallTime, BenchmarkGroup, Criterion,
+};
+use database::BenchmarkDB;
+use revm::{
+    bytecode::{opcode, Bytecode},
+    primitives::{address, Address, Bytes, TxKind},
+    wiring::EthereumWiring,
+    Evm, EvmWiring,
+};
+use revm_inspector::{inspector_handle_register, inspectors::NoOpInspector, Inspector};
+use std::{fmt::Debug, time::Duration};
+
+/// Inspector with the default callbacks, active unlike [`NoOpInspector`].
+#[derive(Debug, Default)]
+struct DefaultCallbacksInspector;
+
+impl<EvmWiringT: EvmWiring> Inspector<EvmWiringT> for DefaultCallbacksInspector {}
+
+/// Counts down from 10000 in a loop.
+fn counter() -> Bytecode {
+    Bytecode::new_raw(Bytes::from_static(&[
+        opcode::PUSH2,
+        0x27,
+        0x10,
+        opcode::JUMPDEST,
+        opcode::PUSH1,
+        0x01,
+        opcode::SWAP1,
+        opcode::SUB,
+        opcode::DUP1,
+        opcode::PUSH1,
+        0x03,
+        opcode::JUMPI,
+        opcode::STOP,
+    ]))
+}
+
+/// Benchmarks the transaction calling [`counter`] on `evm`.
+fn bench<EXT: Debug>(
+    g: &mut BenchmarkGroup<'_, WallTime>,
+    name: &str,
+    mut evm: Evm<'_, EthereumWiring<BenchmarkDB, EXT>>,
+) {
+    let tx = evm.tx_mut();
+    tx.caller = address!("1000000000000000000000000000000000000000");
+    tx.transact_to = TxKind::Call(Address::ZERO);
+    tx.gas_limit = 1_000_000;
+    g.bench_function(name, |b| b.iter(|| evm.transact().unwrap()));
+}
+
+/// Compares a transaction without inspector, with the bypassed [`NoOpInspector`] and with an
+/// active inspector that wraps every instruction.
+fn inspector_overhead(c: &mut Criterion) {
+    let mut g = c.benchmark_group("inspector_overhead");
+    g.noise_threshold(0.03)
+        .warm_up_time(Duration::from_secs(1))
+        .measurement_time(Duration::from_secs(5));
+
+    bench(
+        &mut g,
+        "plain",
+        Evm::<EthereumWiring<BenchmarkDB, ()>>::builder()
+            .with_db(BenchmarkDB::new_bytecode(counter()))
+            .with_default_ext_ctx()
+            .build(),
+    );
+    bench(
+        &mut g,
+        "noop",
+        Evm::<EthereumWiring<BenchmarkDB, NoOpInspector>>::builder()
+            .with_db(BenchmarkDB::new_bytecode(counter()))
+            .with_default_ext_ctx()
+            .append_handler_register(inspector_handle_register)
+            .build(),
+    );
+    bench(
+        &mut g,
+        "active",
+        Evm::<EthereumWiring<BenchmarkDB, DefaultCallbacksInspector>>::builder()
+            .with_db(BenchmarkDB::new_bytecode(counter()))
+            .with_default_ext_ctx()
+            .append_handler_register(inspector_handle_register)
+            .build(),
+    );
+    g.finish();
+}
+
+criterion_group!(benches, inspector_overhead);
+criterion_main!(benches);
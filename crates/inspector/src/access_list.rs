@@ -0,0 +1,260 @@
+//! Access list inspector, generates the access list of a transaction.
+
+use crate::{inspector_handle_register, Inspector};
+use revm::{
+    bytecode::opcode,
+    interpreter::Interpreter,
+    primitives::{Address, HashSet, B256},
+    specification::eip2930::{AccessList, AccessListItem},
+    transaction::AccessListTrait,
+    wiring::{default::EnvWiring, result::EVMResultGeneric, Transaction},
+    EvmBuilder, EvmContext, EvmWiring,
+};
+use std::{
+    boxed::Box,
+    collections::{BTreeMap, BTreeSet},
+};
+
+/// [Inspector] that collects the accounts and storage slots accessed by a transaction.
+///
+/// Excluded addresses and precompiles are not added unless one of their storage slots is
+/// accessed, as they are warm regardless of the access list.
+#[derive(Clone, Debug, Default)]
+pub struct AccessListInspector {
+    access_list: BTreeMap<Address, BTreeSet<B256>>,
+    excluded: HashSet<Address>,
+}
+
+impl AccessListInspector {
+    /// Creates a new inspector starting from `access_list` that does not add the `excluded`
+    /// addresses, usually the transaction's sender, recipient or created address.
+    pub fn new(
+        access_list: &impl AccessListTrait,
+        excluded: impl IntoIterator<Item = Address>,
+    ) -> Self {
+        Self {
+            access_list: access_list
+                .iter()
+                .map(|(address, slots)| (address, slots.collect()))
+                .collect(),
+            excluded: excluded.into_iter().collect(),
+        }
+    }
+
+    /// Returns the collected access list.
+    pub fn access_list(&self) -> AccessList {
+        AccessList(
+            self.access_list
+                .iter()
+                .map(|(address, slots)| AccessListItem {
+                    address: *address,
+                    storage_keys: slots.iter().copied().collect(),
+                })
+                .collect(),
+        )
+    }
+
+    fn add_address<EvmWiringT: EvmWiring>(
+        &mut self,
+        address: Address,
+        context: &EvmContext<EvmWiringT>,
+    ) {
+        if !self.excluded.contains(&address) && !context.precompiles.contains(&address) {
+            self.access_list.entry(address).or_default();
+        }
+    }
+}
+
+impl<EvmWiringT: EvmWiring> Inspector<EvmWiringT> for AccessListInspector {
+    fn step(&mut self, interp: &mut Interpreter, context: &mut EvmContext<EvmWiringT>) {
+        match interp.current_opcode() {
+            opcode::SLOAD | opcode::SSTORE => {
+                if let Ok(slot) = interp.stack.peek(0) {
+                    self.access_list
+                        .entry(interp.contract.target_address)
+                        .or_default()
+                        .insert(B256::from(slot));
+                }
+            }
+            opcode::EXTCODECOPY
+            | opcode::EXTCODEHASH
+            | opcode::EXTCODESIZE
+            | opcode::BALANCE
+            | opcode::SELFDESTRUCT
+            | opcode::EXTCALL
+            | opcode::EXTDELEGATECALL
+            | opcode::EXTSTATICCALL => {
+                if let Ok(address) = interp.stack.peek(0) {
+                    self.add_address(Address::from_word(B256::from(address)), context);
+                }
+            }
+            opcode::CALL | opcode::CALLCODE | opcode::DELEGATECALL | opcode::STATICCALL => {
+                if let Ok(address) = interp.stack.peek(1) {
+                    self.add_address(Address::from_word(B256::from(address)), context);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Result of [`simulate_access_list`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AccessListSimulation {
+    /// Access list generated for the transaction.
+    pub access_list: AccessList,
+    /// Gas used by the transaction as given.
+    pub gas_used: u64,
+    /// Gas used by the transaction with the generated access list applied.
+    pub gas_used_with_access_list: u64,
+}
+
+/// Generates the access list of the transaction in `env`, like `eth_createAccessList`.
+///
+/// The transaction is run with [`AccessListInspector`], excluding its sender and its recipient or
+/// created address, and run again with the generated access list, set by `set_access_list`, to
+/// measure its gas. Nothing is committed to `db`.
+///
+/// The access list is only applied if the transaction type supports one.
+pub fn simulate_access_list<EvmWiringT>(
+    db: EvmWiringT::Database,
+    spec_id: EvmWiringT::Hardfork,
+    env: Box<EnvWiring<EvmWiringT>>,
+    set_access_list: impl FnOnce(&mut EvmWiringT::Transaction, AccessList),
+) -> EVMResultGeneric<AccessListSimulation, EvmWiringT>
+where
+    EvmWiringT: EvmWiring<ExternalContext = AccessListInspector>,
+{
+    let tx = &env.tx;
+    let caller = tx.common_fields().caller();
+    let target = tx.kind().to().copied().unwrap_or_else(|| {
+        env.cfg
+            .hasher()
+            .create_address(caller, tx.common_fields().nonce())
+    });
+    let inspector = match tx.access_list() {
+        Some(access_list) => AccessListInspector::new(access_list, [caller, target]),
+        None => AccessListInspector::new(&AccessList::default(), [caller, target]),
+    };
+
+    let mut evm = EvmBuilder::new_with(db, inspector, env, EvmWiringT::handler::<'_>(spec_id))
+        .append_handler_register(inspector_handle_register)
+        .build();
+
+    let gas_used = evm.transact()?.result.gas_used();
+    let access_list = evm.context.external.access_list();
+
+    set_access_list(evm.tx_mut(), access_list.clone());
+    let gas_used_with_access_list = evm.transact()?.result.gas_used();
+
+    Ok(AccessListSimulation {
+        access_list,
+        gas_used,
+        gas_used_with_access_list,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use database::InMemoryDB;
+    use revm::{
+        bytecode::Bytecode,
+        primitives::{address, Bytes, TxKind, U256},
+        specification::hardfork::SpecId,
+        state::AccountInfo,
+        transaction::TransactionType,
+        wiring::{
+            default::{block::BlockEnv, Env, TxEnv},
+            EthereumWiring,
+        },
+    };
+    use std::vec;
+
+    type Wiring = EthereumWiring<InMemoryDB, AccessListInspector>;
+
+    fn simulate(db: InMemoryDB, env: Box<Env<BlockEnv, TxEnv>>) -> AccessListSimulation {
+        simulate_access_list::<Wiring>(db, SpecId::CANCUN, env, |tx, access_list| {
+            tx.access_list = access_list
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_simulate_access_list() {
+        let caller = address!("1000000000000000000000000000000000000000");
+        let contract = address!("00000000000000000000000000000000000000a0");
+        let storage = address!("00000000000000000000000000000000000000a1");
+
+        // STATICCALL the storage contract, which reads slot 1, and the identity precompile.
+        let mut code = vec![];
+        for target in [storage, Address::with_last_byte(4)] {
+            code.extend_from_slice(&[
+                opcode::PUSH0,
+                opcode::PUSH0,
+                opcode::PUSH0,
+                opcode::PUSH0,
+                opcode::PUSH20,
+            ]);
+            code.extend_from_slice(target.as_slice());
+            code.extend_from_slice(&[opcode::GAS, opcode::STATICCALL, opcode::POP]);
+        }
+        code.push(opcode::STOP);
+
+        let mut db = InMemoryDB::default();
+        db.insert_account_info(
+            contract,
+            AccountInfo::from_bytecode(Bytecode::new_raw(code.into())),
+        );
+        db.insert_account_info(
+            storage,
+            AccountInfo::from_bytecode(Bytecode::new_raw(Bytes::from_static(&[
+                opcode::PUSH1,
+                0x01,
+                opcode::SLOAD,
+                opcode::STOP,
+            ]))),
+        );
+
+        let mut env = Box::<Env<BlockEnv, TxEnv>>::default();
+        env.tx.tx_type = TransactionType::Eip2930;
+        env.tx.caller = caller;
+        env.tx.transact_to = TxKind::Call(contract);
+        env.tx.gas_limit = 100_000;
+
+        let simulation = simulate(db, env);
+        assert_eq!(
+            simulation.access_list,
+            AccessList(vec![AccessListItem {
+                address: storage,
+                storage_keys: vec![B256::from(U256::from(1))],
+            }])
+        );
+        assert!(simulation.gas_used_with_access_list < simulation.gas_used);
+    }
+
+    #[test]
+    fn test_simulate_access_list_create() {
+        let caller = address!("1000000000000000000000000000000000000000");
+
+        // Reads the balance of the caller and of the created contract itself.
+        let initcode = Bytes::from_static(&[
+            opcode::CALLER,
+            opcode::BALANCE,
+            opcode::ADDRESS,
+            opcode::BALANCE,
+            opcode::STOP,
+        ]);
+
+        let mut env = Box::<Env<BlockEnv, TxEnv>>::default();
+        env.tx.tx_type = TransactionType::Eip2930;
+        env.tx.caller = caller;
+        env.tx.transact_to = TxKind::Create;
+        env.tx.data = initcode;
+        env.tx.gas_limit = 100_000;
+
+        let simulation = simulate(InMemoryDB::default(), env);
+        assert_eq!(simulation.access_list, AccessList::default());
+        assert_eq!(simulation.gas_used_with_access_list, simulation.gas_used);
+    }
+}
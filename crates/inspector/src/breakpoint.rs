@@ -0,0 +1,174 @@
+//! Breakpoint inspector, pauses execution at program counters of contracts.
+
+use crate::Inspector;
+use core::fmt;
+use revm::{
+    interpreter::Interpreter,
+    primitives::{Address, HashSet},
+    EvmContext, EvmWiring,
+};
+use std::vec::Vec;
+
+/// A breakpoint hit recorded by [`BreakpointInspector`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BreakpointHit {
+    /// Address of the code the breakpoint is in.
+    pub address: Address,
+    /// Program counter of the instruction about to execute.
+    pub pc: usize,
+    /// Depth of the frame.
+    pub depth: u64,
+}
+
+/// Handler called at each breakpoint hit, used when none is set.
+type DefaultHandler = fn(&BreakpointHit, &mut Interpreter);
+
+/// [Inspector] that pauses execution before the instructions at `(address, pc)` breakpoints.
+///
+/// The address is the one the code was loaded from, so a breakpoint in a library also fires
+/// when it is executed through `DELEGATECALL`. Init code is matched by the address of the created
+/// contract.
+///
+/// At a hit, the hit is recorded and the handler is called with the paused interpreter. The
+/// handler can inspect or modify the frame and execution resumes when it returns. A stepping
+/// debugger blocks in the handler until the user continues.
+pub struct BreakpointInspector<F = DefaultHandler> {
+    breakpoints: HashSet<(Address, usize)>,
+    hits: Vec<BreakpointHit>,
+    handler: F,
+}
+
+impl BreakpointInspector {
+    /// Creates an inspector without breakpoints that only records the hits.
+    pub fn new() -> Self {
+        Self::with_handler(|_, _| {})
+    }
+}
+
+impl Default for BreakpointInspector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<F: FnMut(&BreakpointHit, &mut Interpreter)> BreakpointInspector<F> {
+    /// Creates an inspector without breakpoints that calls `handler` at each hit.
+    pub fn with_handler(handler: F) -> Self {
+        Self {
+            breakpoints: HashSet::default(),
+            hits: Vec::new(),
+            handler,
+        }
+    }
+
+    /// Adds a breakpoint before the instruction at `pc` in the code of `address`.
+    ///
+    /// Returns `false` if the breakpoint was already set.
+    pub fn add_breakpoint(&mut self, address: Address, pc: usize) -> bool {
+        self.breakpoints.insert((address, pc))
+    }
+
+    /// Removes a breakpoint, returns `false` if it was not set.
+    pub fn remove_breakpoint(&mut self, address: Address, pc: usize) -> bool {
+        self.breakpoints.remove(&(address, pc))
+    }
+
+    /// Returns the recorded hits, in execution order.
+    pub fn hits(&self) -> &[BreakpointHit] {
+        &self.hits
+    }
+}
+
+impl<F> fmt::Debug for BreakpointInspector<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BreakpointInspector")
+            .field("breakpoints", &self.breakpoints)
+            .field("hits", &self.hits)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<EvmWiringT: EvmWiring, F: FnMut(&BreakpointHit, &mut Interpreter)> Inspector<EvmWiringT>
+    for BreakpointInspector<F>
+{
+    fn step(&mut self, interp: &mut Interpreter, context: &mut EvmContext<EvmWiringT>) {
+        if self.breakpoints.is_empty() {
+            return;
+        }
+        let address = interp
+            .contract
+            .bytecode_address
+            .unwrap_or(interp.contract.target_address);
+        let pc = interp.program_counter();
+        if !self.breakpoints.contains(&(address, pc)) {
+            return;
+        }
+        let hit = BreakpointHit {
+            address,
+            pc,
+            depth: context.journaled_state.depth(),
+        };
+        (self.handler)(&hit, interp);
+        self.hits.push(hit);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::inspector_handle_register;
+    use database::BenchmarkDB;
+    use revm::{
+        bytecode::{opcode, Bytecode},
+        primitives::{address, Bytes, U256},
+        test_utils::call_evm_builder,
+    };
+
+    #[test]
+    fn test_breakpoint_inspector() {
+        // Loops three times, counting down from 3.
+        let code = Bytecode::new_raw(Bytes::from_static(&[
+            opcode::PUSH1,
+            0x03,
+            opcode::JUMPDEST,
+            opcode::PUSH1,
+            0x01,
+            opcode::SWAP1,
+            opcode::SUB,
+            opcode::DUP1,
+            opcode::PUSH1,
+            0x02,
+            opcode::JUMPI,
+            opcode::STOP,
+        ]));
+
+        // Records the counter on the stack at each hit.
+        let mut counters = Vec::new();
+        let mut inspector = BreakpointInspector::with_handler(|hit, interp: &mut Interpreter| {
+            assert_eq!(hit.pc, interp.program_counter());
+            counters.push(interp.stack.peek(0).unwrap());
+        });
+        assert!(inspector.add_breakpoint(Address::ZERO, 5));
+        assert!(inspector.add_breakpoint(Address::ZERO, 11));
+        assert!(!inspector.add_breakpoint(Address::ZERO, 11));
+        assert!(inspector.add_breakpoint(address!("00000000000000000000000000000000000000a0"), 5));
+
+        let mut evm = call_evm_builder(
+            BenchmarkDB::new_bytecode(code),
+            &mut inspector,
+            Address::ZERO,
+            100_000,
+        )
+        .append_handler_register(inspector_handle_register)
+        .build();
+        assert!(evm.transact().unwrap().result.is_success());
+        drop(evm);
+
+        // The `SWAP1` fires once per pass, the final `STOP` once.
+        let pcs: Vec<_> = inspector.hits().iter().map(|hit| hit.pc).collect();
+        assert_eq!(pcs, [5, 5, 5, 11]);
+        assert!(inspector.hits().iter().all(|hit| hit.depth == 1));
+        drop(inspector);
+        assert_eq!(counters, [1, 1, 1, 0].map(U256::from));
+    }
+}
@@ -0,0 +1,254 @@
+//! Call trace inspector, records the call tree with the gas used by each frame.
+
+use crate::Inspector;
+use core::fmt::Write;
+use revm::{
+    interpreter::{CallInputs, CallOutcome, CreateInputs, CreateOutcome, EOFCreateInputs, Gas},
+    primitives::Address,
+    EvmContext, EvmWiring,
+};
+use std::{string::String, vec, vec::Vec};
+
+/// A call or create frame recorded by [`CallTraceInspector`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CallTraceNode {
+    /// Index of the calling frame.
+    pub parent: Option<usize>,
+    /// Called address, or the created address once the creation has finished.
+    pub address: Option<Address>,
+    /// `true` for contract creations.
+    pub is_create: bool,
+    /// Gas available to the frame when it started.
+    pub gas_limit: u64,
+    /// Gas left when the frame finished, `None` while it is running.
+    pub gas_remaining: Option<u64>,
+}
+
+impl CallTraceNode {
+    /// Returns the gas used by the frame, including the gas used by its children.
+    pub fn gas_used(&self) -> u64 {
+        self.gas_limit - self.gas_remaining.unwrap_or(self.gas_limit)
+    }
+}
+
+/// [Inspector] that records the tree of calls and creations with their entry and exit gas.
+///
+/// The self gas of a frame, the gas used by its own instructions, is its gas used minus the gas
+/// used by its children, see [`Self::self_gas`]. Calls to precompiles are recorded too.
+#[derive(Clone, Debug, Default)]
+pub struct CallTraceInspector {
+    nodes: Vec<CallTraceNode>,
+    /// Indices of the frames that have not finished yet.
+    open: Vec<usize>,
+}
+
+impl CallTraceInspector {
+    /// Returns the recorded frames, in the order they started.
+    pub fn nodes(&self) -> &[CallTraceNode] {
+        &self.nodes
+    }
+
+    /// Returns the gas used by the instructions of the frame at `index`, excluding its children.
+    pub fn self_gas(&self, index: usize) -> u64 {
+        let children_gas: u64 = self
+            .nodes
+            .iter()
+            .filter(|node| node.parent == Some(index))
+            .map(CallTraceNode::gas_used)
+            .sum();
+        self.nodes[index].gas_used().saturating_sub(children_gas)
+    }
+
+    /// Returns the call tree in the folded stack format of Brendan Gregg's `flamegraph.pl`.
+    ///
+    /// Each frame is a line of the semicolon separated addresses from the transaction's frame
+    /// to it, followed by its self gas. Creations that failed before an address was assigned are
+    /// named `create`.
+    pub fn to_folded_stacks(&self) -> String {
+        let mut children_gas = vec![0u64; self.nodes.len()];
+        for node in &self.nodes {
+            if let Some(parent) = node.parent {
+                children_gas[parent] += node.gas_used();
+            }
+        }
+
+        let mut folded = String::new();
+        for (index, node) in self.nodes.iter().enumerate() {
+            let mut stack = Vec::new();
+            let mut next = Some(index);
+            while let Some(index) = next {
+                stack.push(index);
+                next = self.nodes[index].parent;
+            }
+            for (i, &index) in stack.iter().rev().enumerate() {
+                if i > 0 {
+                    folded.push(';');
+                }
+                match self.nodes[index].address {
+                    Some(address) => write!(folded, "{address}").unwrap(),
+                    None => folded.push_str("create"),
+                }
+            }
+            let self_gas = node.gas_used().saturating_sub(children_gas[index]);
+            writeln!(folded, " {self_gas}").unwrap();
+        }
+        folded
+    }
+
+    fn node_start(&mut self, address: Option<Address>, is_create: bool, gas_limit: u64) {
+        self.nodes.push(CallTraceNode {
+            parent: self.open.last().copied(),
+            address,
+            is_create,
+            gas_limit,
+            gas_remaining: None,
+        });
+        self.open.push(self.nodes.len() - 1);
+    }
+
+    fn node_end(&mut self, gas: &Gas, is_error: bool, address: Option<Address>) {
+        let Some(node) = self.open.pop().map(|index| &mut self.nodes[index]) else {
+            return;
+        };
+        // Halted frames spend all their gas.
+        node.gas_remaining = Some(if is_error { 0 } else { gas.remaining() });
+        if node.is_create {
+            node.address = address;
+        }
+    }
+}
+
+impl<EvmWiringT: EvmWiring> Inspector<EvmWiringT> for CallTraceInspector {
+    fn call(
+        &mut self,
+        _context: &mut EvmContext<EvmWiringT>,
+        inputs: &mut CallInputs,
+    ) -> Option<CallOutcome> {
+        self.node_start(Some(inputs.target_address), false, inputs.gas_limit);
+        None
+    }
+
+    fn call_end(
+        &mut self,
+        _context: &mut EvmContext<EvmWiringT>,
+        _inputs: &CallInputs,
+        outcome: CallOutcome,
+    ) -> CallOutcome {
+        self.node_end(&outcome.result.gas, outcome.result.result.is_error(), None);
+        outcome
+    }
+
+    fn create(
+        &mut self,
+        _context: &mut EvmContext<EvmWiringT>,
+        inputs: &mut CreateInputs,
+    ) -> Option<CreateOutcome> {
+        self.node_start(None, true, inputs.gas_limit);
+        None
+    }
+
+    fn create_end(
+        &mut self,
+        _context: &mut EvmContext<EvmWiringT>,
+        _inputs: &CreateInputs,
+        outcome: CreateOutcome,
+    ) -> CreateOutcome {
+        self.node_end(
+            &outcome.result.gas,
+            outcome.result.result.is_error(),
+            outcome.address,
+        );
+        outcome
+    }
+
+    fn eofcreate(
+        &mut self,
+        _context: &mut EvmContext<EvmWiringT>,
+        inputs: &mut EOFCreateInputs,
+    ) -> Option<CreateOutcome> {
+        self.node_start(None, true, inputs.gas_limit);
+        None
+    }
+
+    fn eofcreate_end(
+        &mut self,
+        _context: &mut EvmContext<EvmWiringT>,
+        _inputs: &EOFCreateInputs,
+        outcome: CreateOutcome,
+    ) -> CreateOutcome {
+        self.node_end(
+            &outcome.result.gas,
+            outcome.result.result.is_error(),
+            outcome.address,
+        );
+        outcome
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::inspector_handle_register;
+    use database::InMemoryDB;
+    use revm::{
+        bytecode::{opcode, Bytecode},
+        primitives::address,
+        state::AccountInfo,
+        test_utils::call_evm_builder,
+    };
+    use std::format;
+
+    #[test]
+    fn test_call_trace_inspector() {
+        let contract = address!("00000000000000000000000000000000000000a0");
+        let callee = address!("00000000000000000000000000000000000000a1");
+
+        // Calls the callee with all gas.
+        let mut code = vec![
+            opcode::PUSH0,
+            opcode::PUSH0,
+            opcode::PUSH0,
+            opcode::PUSH0,
+            opcode::PUSH0,
+            opcode::PUSH20,
+        ];
+        code.extend_from_slice(callee.as_slice());
+        code.extend_from_slice(&[opcode::GAS, opcode::CALL, opcode::STOP]);
+
+        let mut db = InMemoryDB::default();
+        db.insert_account_info(
+            contract,
+            AccountInfo::from_bytecode(Bytecode::new_raw(code.into())),
+        );
+        db.insert_account_info(
+            callee,
+            AccountInfo::from_bytecode(Bytecode::new_raw(
+                vec![opcode::PUSH0, opcode::POP, opcode::STOP].into(),
+            )),
+        );
+
+        let mut evm = call_evm_builder(db, CallTraceInspector::default(), contract, 100_000)
+            .append_handler_register(inspector_handle_register)
+            .build();
+
+        let result = evm.transact().unwrap().result;
+        assert!(result.is_success());
+        let inspector = evm.into_context().external;
+
+        let nodes = inspector.nodes();
+        assert_eq!(nodes.len(), 2);
+        assert_eq!(nodes[0].gas_limit, 100_000 - 21_000);
+        assert_eq!(nodes[0].gas_used(), result.gas_used() - 21_000);
+        assert_eq!(nodes[1].parent, Some(0));
+        // PUSH0 and POP.
+        assert_eq!(nodes[1].gas_used(), 4);
+
+        // Five PUSH0, PUSH20, GAS and the cold CALL.
+        let caller_self_gas = 5 * 2 + 3 + 2 + 2_600;
+        assert_eq!(inspector.self_gas(0), caller_self_gas);
+        assert_eq!(
+            inspector.to_folded_stacks(),
+            format!("{contract} {caller_self_gas}\n{contract};{callee} 4\n")
+        );
+    }
+}
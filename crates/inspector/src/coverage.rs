@@ -0,0 +1,130 @@
+//! Coverage inspector, records the executed instructions of each contract.
+
+use crate::Inspector;
+use revm::{
+    bytecode::{bitvec::vec::BitVec, opcode, Bytecode},
+    interpreter::Interpreter,
+    primitives::{Address, HashMap},
+    EvmContext, EvmWiring,
+};
+use std::vec::Vec;
+
+/// Code of a contract and the program counters executed in it.
+#[derive(Clone, Debug)]
+struct ContractCoverage {
+    bytecode: Bytecode,
+    executed: BitVec<u8>,
+}
+
+/// [Inspector] that records which instructions of the called contracts were executed.
+///
+/// Coverage is keyed by the address the code was loaded from, so code executed through
+/// `DELEGATECALL` counts for the library. Only legacy runtime code is covered, init code and EOF
+/// code are skipped.
+#[derive(Clone, Debug, Default)]
+pub struct CoverageInspector {
+    contracts: HashMap<Address, ContractCoverage>,
+}
+
+impl CoverageInspector {
+    /// Returns `true` if the instruction at `pc` in the code of `address` was executed.
+    pub fn is_executed(&self, address: Address, pc: usize) -> bool {
+        self.contracts
+            .get(&address)
+            .and_then(|coverage| coverage.executed.get(pc).map(|bit| *bit))
+            .unwrap_or_default()
+    }
+
+    /// Returns the program counters of the instructions of `address` that were never executed.
+    ///
+    /// Instructions are found like in the jumpdest analysis, push data is skipped. Empty if the
+    /// code of `address` was not executed at all.
+    pub fn unreached_pcs(&self, address: Address) -> Vec<usize> {
+        let Some(coverage) = self.contracts.get(&address) else {
+            return Vec::new();
+        };
+        let code = coverage.bytecode.original_byte_slice();
+        let mut unreached = Vec::new();
+        let mut pc = 0;
+        while pc < code.len() {
+            if !coverage.executed[pc] {
+                unreached.push(pc);
+            }
+            let push_offset = code[pc].wrapping_sub(opcode::PUSH1);
+            pc += if push_offset < 32 {
+                push_offset as usize + 2
+            } else {
+                1
+            };
+        }
+        unreached
+    }
+}
+
+impl<EvmWiringT: EvmWiring> Inspector<EvmWiringT> for CoverageInspector {
+    fn step(&mut self, interp: &mut Interpreter, _context: &mut EvmContext<EvmWiringT>) {
+        let Some(address) = interp.contract.bytecode_address else {
+            return;
+        };
+        if interp.contract.bytecode.is_eof() {
+            return;
+        }
+        let coverage = self
+            .contracts
+            .entry(address)
+            .or_insert_with(|| ContractCoverage {
+                executed: BitVec::repeat(false, interp.contract.bytecode.len()),
+                bytecode: interp.contract.bytecode.clone(),
+            });
+        // The padding STOP of the analyzed code is past the original code.
+        if let Some(mut bit) = coverage.executed.get_mut(interp.program_counter()) {
+            *bit = true;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::inspector_handle_register;
+    use database::BenchmarkDB;
+    use revm::{
+        primitives::{address, Bytes},
+        test_utils::call_evm_builder,
+    };
+
+    #[test]
+    fn test_coverage_inspector() {
+        // Skips the push and pop if the call value is zero.
+        let code = Bytecode::new_raw(Bytes::from_static(&[
+            opcode::CALLVALUE,
+            opcode::ISZERO,
+            opcode::PUSH1,
+            0x08,
+            opcode::JUMPI,
+            opcode::PUSH1,
+            0x01,
+            opcode::POP,
+            opcode::JUMPDEST,
+            opcode::STOP,
+        ]));
+
+        let mut evm = call_evm_builder(
+            BenchmarkDB::new_bytecode(code),
+            CoverageInspector::default(),
+            Address::ZERO,
+            100_000,
+        )
+        .append_handler_register(inspector_handle_register)
+        .build();
+
+        assert!(evm.transact().unwrap().result.is_success());
+        let inspector = evm.into_context().external;
+        assert!(inspector.is_executed(Address::ZERO, 4));
+        assert!(!inspector.is_executed(Address::ZERO, 5));
+        assert_eq!(inspector.unreached_pcs(Address::ZERO), [5, 7]);
+        assert!(inspector
+            .unreached_pcs(address!("00000000000000000000000000000000000000a0"))
+            .is_empty());
+    }
+}
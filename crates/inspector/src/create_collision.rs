@@ -0,0 +1,170 @@
+//! Create collision inspector, finds creations targeting occupied addresses.
+
+use crate::Inspector;
+use revm::{
+    interpreter::{CreateInputs, CreateOutcome, EOFCreateInputs, EOFCreateKind},
+    primitives::{Address, B256, KECCAK_EMPTY},
+    wiring::Transaction,
+    Database, EvmContext, EvmWiring,
+};
+use std::vec::Vec;
+
+/// A creation recorded by [`CreateCollisionInspector`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CreateCollision {
+    /// Creator of the contract.
+    pub caller: Address,
+    /// Address the contract would have been created at.
+    pub address: Address,
+    /// Nonce of the account at `address`.
+    pub nonce: u64,
+    /// Code hash of the account at `address`.
+    pub code_hash: B256,
+}
+
+/// [Inspector] that records creations whose address already has a nonce or code.
+///
+/// Such creations fail with `CreateCollision` as specified by EIP-684. The address is computed
+/// when the creation starts, from the caller's nonce or, for `CREATE2`, from the salt and the
+/// init code hash.
+#[derive(Clone, Debug, Default)]
+pub struct CreateCollisionInspector {
+    collisions: Vec<CreateCollision>,
+}
+
+impl CreateCollisionInspector {
+    /// Returns the recorded collisions.
+    pub fn collisions(&self) -> &[CreateCollision] {
+        &self.collisions
+    }
+
+    fn check<EvmWiringT: EvmWiring>(
+        &mut self,
+        context: &mut EvmContext<EvmWiringT>,
+        caller: Address,
+        address: Address,
+    ) {
+        // Read the account without loading it into the journal, which would warm it.
+        let info = match context.journaled_state.state.get(&address) {
+            Some(account) => Some(account.info.clone()),
+            None => match context.db.basic(address) {
+                Ok(info) => info,
+                Err(e) => {
+                    context.error = Err(e);
+                    return;
+                }
+            },
+        };
+        let Some(info) = info else {
+            return;
+        };
+        if info.nonce != 0 || info.code_hash != KECCAK_EMPTY {
+            self.collisions.push(CreateCollision {
+                caller,
+                address,
+                nonce: info.nonce,
+                code_hash: info.code_hash,
+            });
+        }
+    }
+}
+
+impl<EvmWiringT: EvmWiring> Inspector<EvmWiringT> for CreateCollisionInspector {
+    fn create(
+        &mut self,
+        context: &mut EvmContext<EvmWiringT>,
+        inputs: &mut CreateInputs,
+    ) -> Option<CreateOutcome> {
+        // The caller is loaded as it is either executing or the transaction's sender.
+        let nonce = context
+            .journaled_state
+            .state
+            .get(&inputs.caller)
+            .map(|account| account.info.nonce)
+            .unwrap_or_default();
+        let hasher = context.env.cfg.hasher();
+        let address = inputs.created_address_with_hasher(nonce, &hasher);
+        self.check(context, inputs.caller, address);
+        None
+    }
+
+    fn eofcreate(
+        &mut self,
+        context: &mut EvmContext<EvmWiringT>,
+        inputs: &mut EOFCreateInputs,
+    ) -> Option<CreateOutcome> {
+        let address = match &inputs.kind {
+            EOFCreateKind::Opcode {
+                created_address, ..
+            } => *created_address,
+            EOFCreateKind::Tx { .. } => context
+                .env
+                .cfg
+                .hasher()
+                .create_address(inputs.caller, context.env.tx.common_fields().nonce()),
+        };
+        self.check(context, inputs.caller, address);
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::inspector_handle_register;
+    use database::InMemoryDB;
+    use revm::{
+        bytecode::{opcode, Bytecode},
+        primitives::{address, keccak256, Bytes},
+        state::AccountInfo,
+        test_utils::call_evm_builder,
+    };
+
+    #[test]
+    fn test_create_collision_inspector() {
+        let factory = address!("00000000000000000000000000000000000000a0");
+
+        // CREATE2 with empty init code and zero salt.
+        let code = Bytecode::new_raw(Bytes::from_static(&[
+            opcode::PUSH0,
+            opcode::PUSH0,
+            opcode::PUSH0,
+            opcode::PUSH0,
+            opcode::CREATE2,
+            opcode::STOP,
+        ]));
+        let created = factory.create2(B256::ZERO, keccak256([]));
+
+        let deploy = |occupied: bool| {
+            let mut db = InMemoryDB::default();
+            db.insert_account_info(factory, AccountInfo::from_bytecode(code.clone()));
+            if occupied {
+                db.insert_account_info(
+                    created,
+                    AccountInfo {
+                        nonce: 1,
+                        ..Default::default()
+                    },
+                );
+            }
+
+            let mut evm =
+                call_evm_builder(db, CreateCollisionInspector::default(), factory, 100_000)
+                    .append_handler_register(inspector_handle_register)
+                    .build();
+            assert!(evm.transact().unwrap().result.is_success());
+            evm.into_context().external.collisions
+        };
+
+        assert_eq!(deploy(false), vec![]);
+        assert_eq!(
+            deploy(true),
+            vec![CreateCollision {
+                caller: factory,
+                address: created,
+                nonce: 1,
+                code_hash: KECCAK_EMPTY,
+            }]
+        );
+    }
+}
@@ -0,0 +1,244 @@
+//! Create tree inspector, reconstructs the chain of contract creations.
+
+use crate::Inspector;
+use revm::{
+    bytecode::opcode,
+    interpreter::{
+        CreateInputs, CreateOutcome, EOFCreateInputs, EOFCreateKind, InstructionResult, Interpreter,
+    },
+    primitives::{Address, Bytes, U256},
+    wiring::default::CreateScheme,
+    EvmContext, EvmWiring,
+};
+use std::vec::Vec;
+
+/// Kind of a [`CreateNode`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CreateNodeKind {
+    /// Legacy `CREATE` or `CREATE2`, from an opcode or a transaction.
+    Legacy(CreateScheme),
+    /// `EOFCREATE` of the subcontainer at `initcontainer_index`.
+    EofCreate {
+        /// Index of the initcode container in the creator's container section.
+        initcontainer_index: u8,
+    },
+    /// EOF creation transaction.
+    EofCreateTx,
+}
+
+/// A contract creation recorded by [`CreateTreeInspector`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CreateNode {
+    /// Kind of the creation.
+    pub kind: CreateNodeKind,
+    /// Index of the enclosing creation, if this one was made by initcode.
+    pub parent: Option<usize>,
+    /// Creator of the contract.
+    pub caller: Address,
+    /// Endowment of the contract.
+    pub value: U256,
+    /// Created address, set once the creation has finished.
+    pub address: Option<Address>,
+    /// Result of the creation, set once the creation has finished.
+    pub result: Option<InstructionResult>,
+    /// Index of the runtime container chosen by `RETURNCONTRACT` for EOF creations.
+    pub returncontract_index: Option<u8>,
+    /// Deployed runtime code if the creation succeeded.
+    pub deployed_code: Option<Bytes>,
+}
+
+/// [Inspector] that records every contract creation of a transaction as a tree.
+///
+/// Nodes are stored in the order the creations start, each pointing to the creation whose
+/// initcode made it. For EOF creations the initcode and runtime container indices are recorded.
+#[derive(Clone, Debug, Default)]
+pub struct CreateTreeInspector {
+    nodes: Vec<CreateNode>,
+    /// Indices of the creations that have not finished yet.
+    open: Vec<usize>,
+    /// Immediate of the last `EOFCREATE` step, consumed by the `eofcreate` hook.
+    initcontainer_index: Option<u8>,
+}
+
+impl CreateTreeInspector {
+    /// Returns the recorded creations.
+    pub fn nodes(&self) -> &[CreateNode] {
+        &self.nodes
+    }
+
+    fn node_start(&mut self, kind: CreateNodeKind, caller: Address, value: U256) {
+        self.open.push(self.nodes.len());
+        self.nodes.push(CreateNode {
+            kind,
+            parent: self.open.iter().rev().nth(1).copied(),
+            caller,
+            value,
+            address: None,
+            result: None,
+            returncontract_index: None,
+            deployed_code: None,
+        });
+    }
+
+    fn node_end(&mut self, outcome: &CreateOutcome) {
+        let Some(node) = self.open.pop().map(|index| &mut self.nodes[index]) else {
+            return;
+        };
+        node.address = outcome.address;
+        node.result = Some(outcome.result.result);
+        if outcome.result.is_ok() {
+            node.deployed_code = Some(outcome.result.output.clone());
+        }
+    }
+}
+
+impl<EvmWiringT: EvmWiring> Inspector<EvmWiringT> for CreateTreeInspector {
+    fn step(&mut self, interp: &mut Interpreter, _context: &mut EvmContext<EvmWiringT>) {
+        let immediate = || interp.bytecode.get(interp.program_counter() + 1).copied();
+        match interp.current_opcode() {
+            opcode::EOFCREATE => self.initcontainer_index = immediate(),
+            opcode::RETURNCONTRACT => {
+                if let Some(&index) = self.open.last() {
+                    self.nodes[index].returncontract_index = immediate();
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn create(
+        &mut self,
+        _context: &mut EvmContext<EvmWiringT>,
+        inputs: &mut CreateInputs,
+    ) -> Option<CreateOutcome> {
+        self.node_start(
+            CreateNodeKind::Legacy(inputs.scheme),
+            inputs.caller,
+            inputs.value,
+        );
+        None
+    }
+
+    fn create_end(
+        &mut self,
+        _context: &mut EvmContext<EvmWiringT>,
+        _inputs: &CreateInputs,
+        outcome: CreateOutcome,
+    ) -> CreateOutcome {
+        self.node_end(&outcome);
+        outcome
+    }
+
+    fn eofcreate(
+        &mut self,
+        _context: &mut EvmContext<EvmWiringT>,
+        inputs: &mut EOFCreateInputs,
+    ) -> Option<CreateOutcome> {
+        let kind = match inputs.kind {
+            EOFCreateKind::Opcode { .. } => CreateNodeKind::EofCreate {
+                initcontainer_index: self.initcontainer_index.take().unwrap_or_default(),
+            },
+            EOFCreateKind::Tx { .. } => CreateNodeKind::EofCreateTx,
+        };
+        self.node_start(kind, inputs.caller, inputs.value);
+        None
+    }
+
+    fn eofcreate_end(
+        &mut self,
+        _context: &mut EvmContext<EvmWiringT>,
+        _inputs: &EOFCreateInputs,
+        outcome: CreateOutcome,
+    ) -> CreateOutcome {
+        self.node_end(&outcome);
+        outcome
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::inspector_handle_register;
+    use database::InMemoryDB;
+    use revm::{
+        bytecode::{
+            eof::{EofBody, TypesSection},
+            Bytecode, Eof,
+        },
+        primitives::{address, keccak256},
+        specification::hardfork::SpecId,
+        state::AccountInfo,
+        test_utils::call_evm_builder,
+    };
+    use std::{sync::Arc, vec};
+
+    fn eof(code: Vec<u8>, max_stack_size: u16, container_section: Vec<Bytes>) -> Eof {
+        EofBody {
+            types_section: vec![TypesSection::new(0, 0x80, max_stack_size)],
+            code_section: vec![code.into()],
+            container_section,
+            data_section: Bytes::new(),
+            is_data_filled: true,
+        }
+        .into_eof()
+    }
+
+    #[test]
+    fn test_create_tree_eofcreate() {
+        let contract = address!("00000000000000000000000000000000000000a0");
+
+        let runtime = eof(vec![opcode::STOP], 0, vec![]);
+        let initcode = eof(
+            vec![opcode::PUSH0, opcode::PUSH0, opcode::RETURNCONTRACT, 0],
+            2,
+            vec![runtime.raw.clone()],
+        );
+        let creator = eof(
+            vec![
+                opcode::PUSH0,
+                opcode::PUSH0,
+                opcode::PUSH0,
+                opcode::PUSH0,
+                opcode::EOFCREATE,
+                0,
+                opcode::POP,
+                opcode::STOP,
+            ],
+            4,
+            vec![initcode.raw.clone()],
+        );
+
+        let mut db = InMemoryDB::default();
+        db.insert_account_info(
+            contract,
+            AccountInfo::from_bytecode(Bytecode::Eof(Arc::new(creator))),
+        );
+
+        let mut evm = call_evm_builder(db, CreateTreeInspector::default(), contract, 1_000_000)
+            .with_spec_id(SpecId::PRAGUE_EOF)
+            .append_handler_register(inspector_handle_register)
+            .build();
+
+        let result = evm.transact().unwrap().result;
+        assert!(result.is_success(), "{result:?}");
+
+        let nodes = evm.into_context().external.nodes;
+        assert_eq!(nodes.len(), 1);
+        let node = &nodes[0];
+        assert_eq!(
+            node.kind,
+            CreateNodeKind::EofCreate {
+                initcontainer_index: 0
+            }
+        );
+        assert_eq!(node.parent, None);
+        assert_eq!(node.caller, contract);
+        assert_eq!(
+            node.address,
+            Some(contract.create2(U256::ZERO.to_be_bytes(), keccak256(&initcode.raw)))
+        );
+        assert_eq!(node.result, Some(InstructionResult::ReturnContract));
+        assert_eq!(node.returncontract_index, Some(0));
+        assert_eq!(node.deployed_code, Some(runtime.raw));
+    }
+}
@@ -44,8 +44,8 @@ impl<EvmWiringT: EvmWiring> Inspector<EvmWiringT> for CustomPrintTracer {
             gas_remaining,
             name,
             opcode,
-            interp.gas.refunded(),
-            interp.gas.refunded(),
+            interp.refunded(),
+            interp.refunded(),
             interp.stack.data(),
             memory_size,
         );
@@ -0,0 +1,218 @@
+//! Deepest revert inspector, finds the frame that started a revert cascade.
+
+use crate::Inspector;
+use revm::{
+    interpreter::{
+        CallInputs, CallOutcome, CreateInputs, CreateOutcome, EOFCreateInputs, InstructionResult,
+        InterpreterResult,
+    },
+    primitives::{Address, Bytes},
+    EvmContext, EvmWiring,
+};
+use std::vec::Vec;
+
+/// A failed frame recorded by [`DeepestRevertInspector`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RevertFrame {
+    /// Depth of the frame, the transaction's frame being at depth 1.
+    pub depth: usize,
+    /// Address the frame executed at, zero for creations that failed before getting one.
+    pub address: Address,
+    /// Result of the frame, a revert or a halt.
+    pub result: InstructionResult,
+    /// Output of the frame, the revert data for reverts.
+    pub output: Bytes,
+}
+
+/// [Inspector] that finds the innermost frame of the last chain of failed frames.
+///
+/// When a frame fails right after one of its subcalls failed, the subcall is assumed to be the
+/// origin of the failure, e.g. a bubbled up revert. A frame that starts another subcall or
+/// succeeds after a failed subcall handled the failure, so it no longer counts.
+#[derive(Clone, Debug, Default)]
+pub struct DeepestRevertInspector {
+    /// Addresses of the running frames, zero for creations.
+    frames: Vec<Address>,
+    deepest: Option<RevertFrame>,
+}
+
+impl DeepestRevertInspector {
+    /// Returns the frame that started the last revert cascade, if a frame failed.
+    pub fn deepest_revert(&self) -> Option<&RevertFrame> {
+        self.deepest.as_ref()
+    }
+
+    fn frame_start(&mut self, address: Address) {
+        self.frames.push(address);
+        // A new frame at the depth of the failure means its parent moved on.
+        if matches!(&self.deepest, Some(deepest) if deepest.depth >= self.frames.len()) {
+            self.deepest = None;
+        }
+    }
+
+    fn frame_end(&mut self, result: &InterpreterResult, address: Option<Address>) {
+        let depth = self.frames.len();
+        let Some(frame_address) = self.frames.pop() else {
+            return;
+        };
+        let caused_by_subcall = matches!(&self.deepest, Some(deepest) if deepest.depth > depth);
+        if result.is_ok() {
+            if caused_by_subcall {
+                self.deepest = None;
+            }
+        } else if !caused_by_subcall {
+            self.deepest = Some(RevertFrame {
+                depth,
+                address: address.unwrap_or(frame_address),
+                result: result.result,
+                output: result.output.clone(),
+            });
+        }
+    }
+}
+
+impl<EvmWiringT: EvmWiring> Inspector<EvmWiringT> for DeepestRevertInspector {
+    fn call(
+        &mut self,
+        _context: &mut EvmContext<EvmWiringT>,
+        inputs: &mut CallInputs,
+    ) -> Option<CallOutcome> {
+        self.frame_start(inputs.target_address);
+        None
+    }
+
+    fn call_end(
+        &mut self,
+        _context: &mut EvmContext<EvmWiringT>,
+        _inputs: &CallInputs,
+        outcome: CallOutcome,
+    ) -> CallOutcome {
+        self.frame_end(&outcome.result, None);
+        outcome
+    }
+
+    fn create(
+        &mut self,
+        _context: &mut EvmContext<EvmWiringT>,
+        _inputs: &mut CreateInputs,
+    ) -> Option<CreateOutcome> {
+        self.frame_start(Address::ZERO);
+        None
+    }
+
+    fn create_end(
+        &mut self,
+        _context: &mut EvmContext<EvmWiringT>,
+        _inputs: &CreateInputs,
+        outcome: CreateOutcome,
+    ) -> CreateOutcome {
+        self.frame_end(&outcome.result, outcome.address);
+        outcome
+    }
+
+    fn eofcreate(
+        &mut self,
+        _context: &mut EvmContext<EvmWiringT>,
+        _inputs: &mut EOFCreateInputs,
+    ) -> Option<CreateOutcome> {
+        self.frame_start(Address::ZERO);
+        None
+    }
+
+    fn eofcreate_end(
+        &mut self,
+        _context: &mut EvmContext<EvmWiringT>,
+        _inputs: &EOFCreateInputs,
+        outcome: CreateOutcome,
+    ) -> CreateOutcome {
+        self.frame_end(&outcome.result, outcome.address);
+        outcome
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::inspector_handle_register;
+    use database::InMemoryDB;
+    use revm::{
+        bytecode::{opcode, Bytecode},
+        primitives::address,
+        state::AccountInfo,
+        test_utils::call_evm_builder,
+        wiring::result::ExecutionResult,
+    };
+    use std::vec;
+
+    /// Code calling `target` and reverting without data if the call failed.
+    fn bubble_up(target: Address) -> Bytecode {
+        let mut code = vec![
+            opcode::PUSH0,
+            opcode::PUSH0,
+            opcode::PUSH0,
+            opcode::PUSH0,
+            opcode::PUSH0,
+            opcode::PUSH20,
+        ];
+        code.extend_from_slice(target.as_slice());
+        code.extend_from_slice(&[
+            opcode::GAS,
+            opcode::CALL,
+            // Jump over the revert if the call succeeded.
+            opcode::PUSH1,
+            0,
+            opcode::JUMPI,
+            opcode::PUSH0,
+            opcode::PUSH0,
+            opcode::REVERT,
+            opcode::JUMPDEST,
+            opcode::STOP,
+        ]);
+        let jumpdest = code.len() - 2;
+        code[jumpdest - 5] = jumpdest as u8;
+        Bytecode::new_raw(code.into())
+    }
+
+    #[test]
+    fn test_deepest_revert() {
+        let first = address!("00000000000000000000000000000000000000a1");
+        let second = address!("00000000000000000000000000000000000000a2");
+        let third = address!("00000000000000000000000000000000000000a3");
+
+        let mut db = InMemoryDB::default();
+        db.insert_account_info(first, AccountInfo::from_bytecode(bubble_up(second)));
+        db.insert_account_info(second, AccountInfo::from_bytecode(bubble_up(third)));
+        // Revert with the 32 byte word 0xaa.
+        db.insert_account_info(
+            third,
+            AccountInfo::from_bytecode(Bytecode::new_raw(Bytes::from_static(&[
+                opcode::PUSH1,
+                0xaa,
+                opcode::PUSH0,
+                opcode::MSTORE,
+                opcode::PUSH1,
+                0x20,
+                opcode::PUSH0,
+                opcode::REVERT,
+            ]))),
+        );
+
+        let mut evm = call_evm_builder(db, DeepestRevertInspector::default(), first, 1_000_000)
+            .append_handler_register(inspector_handle_register)
+            .build();
+
+        assert!(matches!(
+            evm.transact().unwrap().result,
+            ExecutionResult::Revert { .. }
+        ));
+
+        let inspector = evm.into_context().external;
+        let deepest = inspector.deepest_revert().unwrap();
+        assert_eq!(deepest.depth, 3);
+        assert_eq!(deepest.address, third);
+        assert_eq!(deepest.result, InstructionResult::Revert);
+        let mut data = [0; 32];
+        data[31] = 0xaa;
+        assert_eq!(deepest.output, Bytes::copy_from_slice(&data));
+    }
+}
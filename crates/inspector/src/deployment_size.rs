@@ -0,0 +1,192 @@
+//! Deployment size inspector, records the code sizes of created contracts.
+
+use crate::Inspector;
+use revm::{
+    interpreter::{CreateInputs, CreateOutcome, EOFCreateInputs, EOFCreateKind, InstructionResult},
+    primitives::Address,
+    specification::constants::{MAX_CODE_SIZE, MAX_INITCODE_SIZE},
+    EvmContext, EvmWiring,
+};
+use std::vec::Vec;
+
+/// A deployment recorded by [`DeploymentSizeInspector`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Deployment {
+    /// Address of the created contract.
+    pub address: Address,
+    /// Length of the init code, including the init data of EOF creation transactions.
+    pub init_code_size: usize,
+    /// Length of the runtime code returned by the init code.
+    pub code_size: usize,
+}
+
+impl Deployment {
+    /// Returns `true` if the runtime code exceeds the EIP-170 limit or the init code exceeds the
+    /// EIP-3860 limit.
+    pub fn is_oversized(&self) -> bool {
+        self.code_size > MAX_CODE_SIZE || self.init_code_size > MAX_INITCODE_SIZE
+    }
+}
+
+/// [Inspector] that records the init code and runtime code sizes of deployments.
+///
+/// Deployments are recorded when the init code returned runtime code, including deployments
+/// that then failed with `CreateContractSizeLimit`. The sizes are compared with the mainnet
+/// limits, not with the limits configured in [`CfgEnv`](revm::wiring::default::CfgEnv).
+#[derive(Clone, Debug, Default)]
+pub struct DeploymentSizeInspector {
+    /// Init code sizes of the running creations.
+    init_code_sizes: Vec<usize>,
+    deployments: Vec<Deployment>,
+}
+
+impl DeploymentSizeInspector {
+    /// Returns the recorded deployments, in the order their init code returned.
+    pub fn deployments(&self) -> &[Deployment] {
+        &self.deployments
+    }
+
+    /// Returns the addresses of the deployments exceeding a code size limit.
+    pub fn oversized(&self) -> impl Iterator<Item = Address> + '_ {
+        self.deployments
+            .iter()
+            .filter(|deployment| deployment.is_oversized())
+            .map(|deployment| deployment.address)
+    }
+
+    fn create_end(&mut self, outcome: &CreateOutcome) {
+        let Some(init_code_size) = self.init_code_sizes.pop() else {
+            return;
+        };
+        let Some(address) = outcome.address else {
+            return;
+        };
+        if !matches!(
+            outcome.result.result,
+            InstructionResult::Return
+                | InstructionResult::ReturnContract
+                | InstructionResult::CreateContractSizeLimit
+        ) {
+            return;
+        }
+        self.deployments.push(Deployment {
+            address,
+            init_code_size,
+            code_size: outcome.result.output.len(),
+        });
+    }
+}
+
+impl<EvmWiringT: EvmWiring> Inspector<EvmWiringT> for DeploymentSizeInspector {
+    fn create(
+        &mut self,
+        _context: &mut EvmContext<EvmWiringT>,
+        inputs: &mut CreateInputs,
+    ) -> Option<CreateOutcome> {
+        self.init_code_sizes.push(inputs.init_code.len());
+        None
+    }
+
+    fn create_end(
+        &mut self,
+        _context: &mut EvmContext<EvmWiringT>,
+        _inputs: &CreateInputs,
+        outcome: CreateOutcome,
+    ) -> CreateOutcome {
+        self.create_end(&outcome);
+        outcome
+    }
+
+    fn eofcreate(
+        &mut self,
+        _context: &mut EvmContext<EvmWiringT>,
+        inputs: &mut EOFCreateInputs,
+    ) -> Option<CreateOutcome> {
+        self.init_code_sizes.push(match &inputs.kind {
+            EOFCreateKind::Tx { initdata } => initdata.len(),
+            EOFCreateKind::Opcode { initcode, .. } => initcode.raw.len(),
+        });
+        None
+    }
+
+    fn eofcreate_end(
+        &mut self,
+        _context: &mut EvmContext<EvmWiringT>,
+        _inputs: &EOFCreateInputs,
+        outcome: CreateOutcome,
+    ) -> CreateOutcome {
+        self.create_end(&outcome);
+        outcome
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::inspector_handle_register;
+    use database::InMemoryDB;
+    use revm::{
+        bytecode::opcode,
+        primitives::{address, Bytes, TxKind},
+        wiring::{
+            result::{ExecutionResult, HaltReason},
+            EthereumWiring,
+        },
+        Evm,
+    };
+
+    #[test]
+    fn test_deployment_size_inspector() {
+        let caller = address!("1000000000000000000000000000000000000000");
+
+        // Returns `size` zero bytes.
+        let deploy = |size: usize| {
+            let [high, low] = (size as u16).to_be_bytes();
+            let mut evm = Evm::<EthereumWiring<InMemoryDB, DeploymentSizeInspector>>::builder()
+                .with_db(InMemoryDB::default())
+                .with_default_ext_ctx()
+                .modify_tx_env(|tx| {
+                    tx.caller = caller;
+                    tx.transact_to = TxKind::Create;
+                    tx.data = Bytes::from(vec![
+                        opcode::PUSH2,
+                        high,
+                        low,
+                        opcode::PUSH0,
+                        opcode::RETURN,
+                    ]);
+                    tx.gas_limit = 10_000_000;
+                })
+                .append_handler_register(inspector_handle_register)
+                .build();
+            let result = evm.transact().unwrap().result;
+            (result, evm.into_context().external)
+        };
+
+        let (result, inspector) = deploy(MAX_CODE_SIZE);
+        assert!(result.is_success());
+        assert_eq!(
+            inspector.deployments(),
+            &[Deployment {
+                address: caller.create(0),
+                init_code_size: 5,
+                code_size: MAX_CODE_SIZE,
+            }]
+        );
+        assert_eq!(inspector.oversized().count(), 0);
+
+        let (result, inspector) = deploy(MAX_CODE_SIZE + 1);
+        assert!(matches!(
+            result,
+            ExecutionResult::Halt {
+                reason: HaltReason::CreateContractSizeLimit,
+                ..
+            }
+        ));
+        assert_eq!(inspector.deployments()[0].code_size, MAX_CODE_SIZE + 1);
+        assert_eq!(
+            inspector.oversized().collect::<Vec<_>>(),
+            [caller.create(0)]
+        );
+    }
+}
@@ -0,0 +1,124 @@
+//! 4byte tracer, counts function selectors of executed calls.
+
+use crate::Inspector;
+use revm::{
+    interpreter::{CallInputs, CallOutcome},
+    primitives::{FixedBytes, HashMap},
+    EvmContext, EvmWiring,
+};
+
+/// [Inspector] that counts calls per function selector, like geth's `4byteTracer`.
+///
+/// Every call with at least 4 bytes of input is counted by its selector and the size of the
+/// calldata following the selector. Calls to precompiles are skipped.
+#[derive(Clone, Debug, Default)]
+pub struct FourByteInspector {
+    counts: HashMap<(FixedBytes<4>, usize), u64>,
+}
+
+impl FourByteInspector {
+    /// Returns the call counts keyed by selector and calldata size.
+    pub fn into_map(self) -> HashMap<(FixedBytes<4>, usize), u64> {
+        self.counts
+    }
+}
+
+impl<EvmWiringT: EvmWiring> Inspector<EvmWiringT> for FourByteInspector {
+    fn call(
+        &mut self,
+        context: &mut EvmContext<EvmWiringT>,
+        inputs: &mut CallInputs,
+    ) -> Option<CallOutcome> {
+        if inputs.input.len() >= 4 && !context.precompiles.contains(&inputs.bytecode_address) {
+            let selector = FixedBytes::from_slice(&inputs.input[..4]);
+            *self
+                .counts
+                .entry((selector, inputs.input.len() - 4))
+                .or_default() += 1;
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::inspector_handle_register;
+    use database::InMemoryDB;
+    use revm::{
+        bytecode::{opcode, Bytecode},
+        primitives::{address, fixed_bytes, Address, Bytes, TxKind},
+        state::AccountInfo,
+        test_utils::call_evm_builder,
+    };
+
+    #[test]
+    fn test_four_byte_inspector() {
+        let contract = address!("00000000000000000000000000000000000000a0");
+        let callee = address!("00000000000000000000000000000000000000a1");
+
+        // Store selector 0x11223344 at memory 0, then STATICCALL the callee with 4 bytes of
+        // input and DELEGATECALL it with 8 bytes of input.
+        let mut code = vec![
+            opcode::PUSH4,
+            0x11,
+            0x22,
+            0x33,
+            0x44,
+            opcode::PUSH1,
+            0xe0,
+            opcode::SHL,
+            opcode::PUSH1,
+            0x00,
+            opcode::MSTORE,
+        ];
+        for (call, input_len) in [(opcode::STATICCALL, 4), (opcode::DELEGATECALL, 8)] {
+            code.extend_from_slice(&[
+                opcode::PUSH1,
+                0x00,
+                opcode::PUSH1,
+                0x00,
+                opcode::PUSH1,
+                input_len,
+                opcode::PUSH1,
+                0x00,
+                opcode::PUSH20,
+            ]);
+            code.extend_from_slice(callee.as_slice());
+            code.extend_from_slice(&[opcode::GAS, call, opcode::POP]);
+        }
+        code.push(opcode::STOP);
+
+        let mut db = InMemoryDB::default();
+        db.insert_account_info(
+            contract,
+            AccountInfo::from_bytecode(Bytecode::new_raw(code.into())),
+        );
+        db.insert_account_info(
+            callee,
+            AccountInfo::from_bytecode(Bytecode::new_raw(Bytes::from_static(&[opcode::STOP]))),
+        );
+
+        let mut evm = call_evm_builder(db, FourByteInspector::default(), contract, 100_000)
+            .modify_tx_env(|tx| {
+                tx.data = Bytes::from_static(&[0xaa, 0xbb, 0xcc, 0xdd, 0, 0, 0, 1]);
+            })
+            .append_handler_register(inspector_handle_register)
+            .build();
+
+        // Changes are not committed, so the same transaction can be run again.
+        for _ in 0..2 {
+            assert!(evm.transact().unwrap().result.is_success());
+        }
+
+        // Calls to precompiles are not counted.
+        evm.tx_mut().transact_to = TxKind::Call(Address::with_last_byte(4));
+        assert!(evm.transact().unwrap().result.is_success());
+
+        let counts = evm.into_context().external.into_map();
+        assert_eq!(counts.len(), 3);
+        assert_eq!(counts[&(fixed_bytes!("aabbccdd"), 4)], 2);
+        assert_eq!(counts[&(fixed_bytes!("11223344"), 0)], 2);
+        assert_eq!(counts[&(fixed_bytes!("11223344"), 4)], 2);
+    }
+}
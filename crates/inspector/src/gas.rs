@@ -12,6 +12,7 @@ use revm::{
 pub struct GasInspector {
     gas_remaining: u64,
     last_gas_cost: u64,
+    current_refund: i64,
 }
 
 impl GasInspector {
@@ -22,6 +23,13 @@ impl GasInspector {
     pub fn last_gas_cost(&self) -> u64 {
         self.last_gas_cost
     }
+
+    /// Returns the refund counter of the current frame after the last executed instruction.
+    ///
+    /// Refunds accrue on `SSTORE` clears and, before London, on `SELFDESTRUCT`.
+    pub fn current_refund(&self) -> i64 {
+        self.current_refund
+    }
 }
 
 impl<EvmWiringT: EvmWiring> Inspector<EvmWiringT> for GasInspector {
@@ -31,6 +39,7 @@ impl<EvmWiringT: EvmWiring> Inspector<EvmWiringT> for GasInspector {
         _context: &mut EvmContext<EvmWiringT>,
     ) {
         self.gas_remaining = interp.gas.limit();
+        self.current_refund = interp.refunded();
     }
 
     fn step(&mut self, interp: &mut Interpreter, _context: &mut EvmContext<EvmWiringT>) {
@@ -41,6 +50,7 @@ impl<EvmWiringT: EvmWiring> Inspector<EvmWiringT> for GasInspector {
         let remaining = interp.gas.remaining();
         self.last_gas_cost = self.gas_remaining.saturating_sub(remaining);
         self.gas_remaining = remaining;
+        self.current_refund = interp.refunded();
     }
 
     fn call_end(
@@ -74,11 +84,14 @@ impl<EvmWiringT: EvmWiring> Inspector<EvmWiringT> for GasInspector {
 mod tests {
     use super::*;
     use crate::inspector_handle_register;
-    use database::BenchmarkDB;
+    use database::{BenchmarkDB, InMemoryDB};
     use revm::{
         bytecode::{opcode, Bytecode},
         interpreter::Interpreter,
-        primitives::{address, Bytes, Log, TxKind},
+        primitives::{address, Bytes, Log, TxKind, U256},
+        specification::hardfork::SpecId,
+        state::AccountInfo,
+        test_utils::call_evm_builder,
         wiring::EvmWiring as PrimitiveEvmWiring,
         wiring::{DefaultEthereumWiring, EthereumWiring},
         Evm, EvmWiring,
@@ -91,6 +104,7 @@ mod tests {
         pc: usize,
         gas_inspector: GasInspector,
         gas_remaining_steps: Vec<(usize, u64)>,
+        refund_steps: Vec<(usize, i64)>,
     }
 
     impl<EvmWiringT: EvmWiring> Inspector<EvmWiringT> for StackInspector {
@@ -120,6 +134,8 @@ mod tests {
             self.gas_inspector.step_end(interp, context);
             self.gas_remaining_steps
                 .push((self.pc, self.gas_inspector.gas_remaining()));
+            self.refund_steps
+                .push((self.pc, self.gas_inspector.current_refund()));
         }
 
         fn call(
@@ -211,4 +227,104 @@ mod tests {
 
         assert_eq!(inspector.gas_remaining_steps, steps);
     }
+
+    #[test]
+    fn test_gas_inspector_refund() {
+        // Clear two storage slots that were set before the transaction.
+        let contract_data: Bytes = Bytes::from(vec![
+            opcode::PUSH1,
+            0x0,
+            opcode::PUSH1,
+            0x0,
+            opcode::SSTORE,
+            opcode::PUSH1,
+            0x0,
+            opcode::PUSH1,
+            0x1,
+            opcode::SSTORE,
+            opcode::STOP,
+        ]);
+        let contract = address!("0000000000000000000000000000000000000000");
+
+        let mut db = InMemoryDB::default();
+        db.insert_account_info(
+            contract,
+            AccountInfo::from_bytecode(Bytecode::new_raw(contract_data)),
+        );
+        for slot in [U256::ZERO, U256::from(1)] {
+            db.insert_account_storage(contract, slot, U256::from(1))
+                .unwrap();
+        }
+
+        let mut evm = Evm::<EthereumWiring<InMemoryDB, StackInspector>>::builder()
+            .with_db(db)
+            .with_default_ext_ctx()
+            .modify_tx_env(|tx| {
+                *tx = <TestEvmWiring as PrimitiveEvmWiring>::Transaction::default();
+
+                tx.caller = address!("1000000000000000000000000000000000000000");
+                tx.transact_to = TxKind::Call(contract);
+                tx.gas_limit = 100_000;
+            })
+            .append_handler_register(inspector_handle_register)
+            .build();
+
+        // run evm.
+        evm.transact().unwrap();
+
+        let inspector = evm.into_context().external;
+
+        let sstore_refunds: Vec<_> = inspector
+            .refund_steps
+            .iter()
+            .filter(|(pc, _)| *pc == 4 || *pc == 9)
+            .map(|(_, refund)| *refund)
+            .collect();
+        assert_eq!(sstore_refunds.len(), 2);
+        assert!(sstore_refunds[0] > 0);
+        assert!(sstore_refunds[1] > sstore_refunds[0]);
+        assert_eq!(inspector.refund_steps.last().unwrap().1, sstore_refunds[1]);
+    }
+
+    #[test]
+    fn test_gas_inspector_refund_eip3529() {
+        // Clear a storage slot that was set before the transaction.
+        let contract_data: Bytes = Bytes::from(vec![
+            opcode::PUSH1,
+            0x0,
+            opcode::PUSH1,
+            0x0,
+            opcode::SSTORE,
+            opcode::STOP,
+        ]);
+        let contract = address!("0000000000000000000000000000000000000000");
+
+        // EIP-3529 lowers the clearing refund from 15000 to 4800.
+        for (spec_id, refund) in [(SpecId::BERLIN, 15_000), (SpecId::LONDON, 4_800)] {
+            let mut db = InMemoryDB::default();
+            db.insert_account_info(
+                contract,
+                AccountInfo::from_bytecode(Bytecode::new_raw(contract_data.clone())),
+            );
+            db.insert_account_storage(contract, U256::ZERO, U256::from(1))
+                .unwrap();
+
+            let mut evm = call_evm_builder(db, StackInspector::default(), contract, 100_000)
+                .with_spec_id(spec_id)
+                .append_handler_register(inspector_handle_register)
+                .build();
+
+            assert!(evm.transact().unwrap().result.is_success());
+
+            let inspector = evm.into_context().external;
+            let before = inspector.refund_steps.iter().find(|(pc, _)| *pc == 2);
+            let after = inspector.refund_steps.iter().find(|(pc, _)| *pc == 4);
+            assert_eq!(before.map(|(_, refund)| *refund), Some(0), "{spec_id:?}");
+            assert_eq!(
+                after.map(|(_, refund)| *refund),
+                Some(refund),
+                "{spec_id:?}"
+            );
+        }
+    }
 }
@@ -1,13 +1,18 @@
-use crate::Inspector;
+use crate::{CodeReadKind, Inspector};
 use core::cell::RefCell;
 use revm::{
     bytecode::opcode,
     handler::register::EvmHandler,
-    interpreter::{table::DynInstruction, InstructionResult, Interpreter},
-    wiring::result::EVMResultGeneric,
+    interpreter::{
+        table::{DynInstruction, InstructionTables},
+        InstructionResult, Interpreter, Stack,
+    },
+    primitives::{Address, B256},
+    transaction::Transaction,
+    wiring::result::{EVMError, EVMResultGeneric},
     Context, EvmWiring, FrameOrResult, FrameResult, JournalEntry,
 };
-use std::{rc::Rc, sync::Arc, vec::Vec};
+use std::{format, rc::Rc, sync::Arc, vec::Vec};
 
 /// Provides access to an `Inspector` instance.
 pub trait GetInspector<EvmWiringT: EvmWiring> {
@@ -32,13 +37,42 @@ impl<EvmWiringT: EvmWiring, INSP: Inspector<EvmWiringT>> GetInspector<EvmWiringT
 /// to use this register with any other register.
 ///
 /// A few instructions handlers are wrapped twice once for `step` and `step_end`
-/// and in case of Logs and Selfdestruct wrapper is wrapped again for the
-/// `log` and `selfdestruct` calls.
+/// and in case of Logs, Selfdestruct, EOF CALLF/RETF, memory accessing, transient storage, jump
+/// and external code reading instructions wrapper is wrapped again for the `log`, `selfdestruct`, `eof_call`,
+/// `eof_return`, `memory_read`, `memory_write`, `tload`, `tstore`, `jump` and `read_code` calls.
+///
+/// Each instruction that did not halt or start a frame is passed to `override_gas_cost` after
+/// it executed, before `step_end`, for inspectors that
+/// [opt in](Inspector::is_gas_cost_override_active).
+///
+/// Journal entries appended by instructions and by the frame start and create return handles
+/// are reported with the `journal_entry` call to inspectors that
+/// [opt in](Inspector::is_journal_entry_active). Entries reverted by the frame return handles are
+/// retracted with the `journal_revert` call.
+///
+/// Frames of inspectors that are not [active](Inspector::is_active), like
+/// [`NoOpInspector`](crate::inspectors::NoOpInspector), run on the instruction table as it was
+/// before this register wrapped it, if it was still plain.
 pub fn inspector_handle_register<
+    'a,
     EvmWiringT: EvmWiring<ExternalContext: GetInspector<EvmWiringT>>,
 >(
-    handler: &mut EvmHandler<'_, EvmWiringT>,
+    handler: &mut EvmHandler<'a, EvmWiringT>,
 ) {
+    // Runs the frames of inactive inspectors on the unwrapped table.
+    if let InstructionTables::Plain(table) = &handler.instruction_table {
+        let unwrapped = InstructionTables::<'a, _>::Plain(*table);
+        let prev_handle = handler.execution.execute_frame.clone();
+        handler.execution.execute_frame = Arc::new(move |frame, shared_memory, tables, ctx| {
+            let tables = if ctx.external.get_inspector().is_active() {
+                tables
+            } else {
+                &unwrapped
+            };
+            prev_handle(frame, shared_memory, tables, ctx)
+        });
+    }
+
     let table = &mut handler.instruction_table;
 
     // Update all instructions to call inspector step and step_end.
@@ -91,6 +125,140 @@ pub fn inspector_handle_register<
         }
     });
 
+    // Register memory reading and writing instructions.
+    for opcode in [
+        opcode::MLOAD,
+        opcode::MSTORE,
+        opcode::MSTORE8,
+        opcode::MCOPY,
+        opcode::CALLDATACOPY,
+        opcode::CODECOPY,
+        opcode::EXTCODECOPY,
+        opcode::RETURNDATACOPY,
+        opcode::DATACOPY,
+    ] {
+        table.update_boxed(opcode, move |prev, interpreter, host| {
+            // ranges are taken from the inputs, they are popped by the instruction.
+            let (read, write) = memory_access(opcode, &interpreter.stack);
+            prev(interpreter, host);
+            // check if the instruction succeeded.
+            if interpreter.instruction_result != InstructionResult::Continue {
+                return;
+            }
+            let inspector = host.external.get_inspector();
+            if let Some((offset, len)) = read {
+                inspector.memory_read(offset, len);
+            }
+            if let Some((offset, len)) = write {
+                inspector.memory_write(offset, len);
+            }
+        });
+    }
+
+    // Register transient storage instructions.
+    table.update_boxed(opcode::TLOAD, |prev, interpreter, host| {
+        let key = interpreter.stack.peek(0);
+        prev(interpreter, host);
+        // check if the instruction succeeded.
+        if interpreter.instruction_result != InstructionResult::Continue {
+            return;
+        }
+        if let (Ok(key), Ok(value)) = (key, interpreter.stack.peek(0)) {
+            let address = interpreter.contract.target_address;
+            host.external.get_inspector().tload(address, key, value);
+        }
+    });
+    table.update_boxed(opcode::TSTORE, |prev, interpreter, host| {
+        let (key, value) = (interpreter.stack.peek(0), interpreter.stack.peek(1));
+        prev(interpreter, host);
+        // check if the instruction succeeded.
+        if interpreter.instruction_result != InstructionResult::Continue {
+            return;
+        }
+        if let (Ok(key), Ok(value)) = (key, value) {
+            let address = interpreter.contract.target_address;
+            host.external.get_inspector().tstore(address, key, value);
+        }
+    });
+
+    // Register jump instructions.
+    for opcode in [opcode::JUMP, opcode::JUMPI] {
+        table.update_boxed(opcode, move |prev, interpreter, host| {
+            // the PC was already incremented past the jump.
+            let from_pc = interpreter.program_counter() - 1;
+            let condition = interpreter.stack.peek(1);
+            prev(interpreter, host);
+            // check if the instruction succeeded.
+            if interpreter.instruction_result != InstructionResult::Continue {
+                return;
+            }
+            let conditional = opcode == opcode::JUMPI;
+            let taken = !conditional || condition.is_ok_and(|condition| !condition.is_zero());
+            host.external.get_inspector().jump(
+                from_pc,
+                interpreter.program_counter(),
+                conditional,
+                taken,
+            );
+        });
+    }
+
+    // Register external code reading instructions.
+    for (opcode, kind) in [
+        (opcode::EXTCODESIZE, CodeReadKind::Size),
+        (opcode::EXTCODEHASH, CodeReadKind::Hash),
+        (opcode::EXTCODECOPY, CodeReadKind::Copy),
+    ] {
+        table.update_boxed(opcode, move |prev, interpreter, host| {
+            let address = interpreter.stack.peek(0);
+            prev(interpreter, host);
+            // check if the instruction succeeded.
+            if interpreter.instruction_result != InstructionResult::Continue {
+                return;
+            }
+            if let Ok(address) = address {
+                let address = Address::from_word(B256::from(address));
+                host.external.get_inspector().read_code(address, kind);
+            }
+        });
+    }
+
+    // Register EOF function call and return instructions.
+    table.update_boxed(opcode::CALLF, |prev, interpreter, host| {
+        let prev_len = interpreter.function_stack.return_stack_len();
+        prev(interpreter, host);
+        // check if the function frame was pushed.
+        if interpreter.function_stack.return_stack_len() == prev_len + 1 {
+            let target_section = interpreter.function_stack.current_code_idx as u16;
+            host.external.get_inspector().eof_call(target_section);
+        }
+    });
+    table.update_boxed(opcode::RETF, |prev, interpreter, host| {
+        let prev_len = interpreter.function_stack.return_stack_len();
+        prev(interpreter, host);
+        // check if the function frame was popped.
+        if interpreter.function_stack.return_stack_len() + 1 == prev_len {
+            host.external.get_inspector().eof_return();
+        }
+    });
+
+    // Calls inspector `transaction_start` before the pre-execution.
+    let prev_handle = handler.pre_execution.load_accounts.clone();
+    handler.pre_execution.load_accounts = Arc::new(move |ctx| {
+        ctx.external.get_inspector().transaction_start(&mut ctx.evm);
+        prev_handle(ctx)
+    });
+
+    // Calls inspector `authorization` for each EIP-7702 authorization before the list is applied.
+    let prev_handle = handler.pre_execution.apply_eip7702_auth_list.clone();
+    handler.pre_execution.apply_eip7702_auth_list = Arc::new(move |ctx| {
+        let inspector = ctx.external.get_inspector();
+        for authorization in ctx.evm.env.tx.authorization_list() {
+            inspector.authorization(&authorization);
+        }
+        prev_handle(ctx)
+    });
+
     // call and create input stack shared between handlers. They are used to share
     // inputs in *_end Inspector calls.
     let call_input_stack = Rc::<RefCell<Vec<_>>>::default();
@@ -110,7 +278,9 @@ pub fn inspector_handle_register<
             }
             create_input_stack_inner.borrow_mut().push(inputs.clone());
 
+            let position = journal_position(ctx);
             let mut frame_or_result = prev_handle(ctx, inputs);
+            notify_journal_entries(ctx, position);
             if let Ok(FrameOrResult::Frame(frame)) = &mut frame_or_result {
                 ctx.external
                     .get_inspector()
@@ -131,7 +301,9 @@ pub fn inspector_handle_register<
             return Ok(FrameOrResult::Result(FrameResult::Call(outcome)));
         }
 
+        let position = journal_position(ctx);
         let mut frame_or_result = prev_handle(ctx, inputs);
+        notify_journal_entries(ctx, position);
         if let Ok(FrameOrResult::Frame(frame)) = &mut frame_or_result {
             ctx.external
                 .get_inspector()
@@ -158,7 +330,9 @@ pub fn inspector_handle_register<
             return Ok(FrameOrResult::Result(FrameResult::EOFCreate(outcome)));
         }
 
+        let position = journal_position(ctx);
         let mut frame_or_result = prev_handle(ctx, inputs);
+        notify_journal_entries(ctx, position);
         if let Ok(FrameOrResult::Frame(frame)) = &mut frame_or_result {
             ctx.external
                 .get_inspector()
@@ -167,12 +341,37 @@ pub fn inspector_handle_register<
         frame_or_result
     });
 
+    // Reports the journal entries of the created contract's code being set, or the entries
+    // reverted by the frame.
+    let prev_handle = handler.execution.call_return.clone();
+    handler.execution.call_return = Arc::new(move |ctx, frame, result| {
+        let position = journal_return_position(ctx);
+        let outcome = prev_handle(ctx, frame, result);
+        notify_journal_return(ctx, position);
+        outcome
+    });
+    let prev_handle = handler.execution.create_return.clone();
+    handler.execution.create_return = Arc::new(move |ctx, frame, result| {
+        let position = journal_return_position(ctx);
+        let outcome = prev_handle(ctx, frame, result);
+        notify_journal_return(ctx, position);
+        outcome
+    });
+    let prev_handle = handler.execution.eofcreate_return.clone();
+    handler.execution.eofcreate_return = Arc::new(move |ctx, frame, result| {
+        let position = journal_return_position(ctx);
+        let outcome = prev_handle(ctx, frame, result);
+        notify_journal_return(ctx, position);
+        outcome
+    });
+
     // Pops eofcreate input from the stack and calls inspector `eofcreate_end` function.
     // preserve the old handler and calls it with the outcome.
     let eofcreate_input_stack_inner = eofcreate_input_stack.clone();
     let prev_handle = handler.execution.insert_eofcreate_outcome.clone();
     handler.execution.insert_eofcreate_outcome = Arc::new(move |ctx, frame, mut outcome| {
-        let create_inputs = eofcreate_input_stack_inner.borrow_mut().pop().unwrap();
+        let create_inputs =
+            pop_frame_inputs::<_, EvmWiringT>(&eofcreate_input_stack_inner, "eofcreate")?;
         outcome = ctx
             .external
             .get_inspector()
@@ -185,7 +384,7 @@ pub fn inspector_handle_register<
     let prev_handle = handler.execution.insert_call_outcome.clone();
     handler.execution.insert_call_outcome =
         Arc::new(move |ctx, frame, shared_memory, mut outcome| {
-            let call_inputs = call_input_stack_inner.borrow_mut().pop().unwrap();
+            let call_inputs = pop_frame_inputs::<_, EvmWiringT>(&call_input_stack_inner, "call")?;
             outcome = ctx
                 .external
                 .get_inspector()
@@ -197,7 +396,7 @@ pub fn inspector_handle_register<
     let create_input_stack_inner = create_input_stack.clone();
     let prev_handle = handler.execution.insert_create_outcome.clone();
     handler.execution.insert_create_outcome = Arc::new(move |ctx, frame, mut outcome| {
-        let create_inputs = create_input_stack_inner.borrow_mut().pop().unwrap();
+        let create_inputs = pop_frame_inputs::<_, EvmWiringT>(&create_input_stack_inner, "create")?;
         outcome = ctx
             .external
             .get_inspector()
@@ -211,15 +410,17 @@ pub fn inspector_handle_register<
         let inspector = ctx.external.get_inspector();
         match frame_result {
             FrameResult::Call(outcome) => {
-                let call_inputs = call_input_stack.borrow_mut().pop().unwrap();
+                let call_inputs = pop_frame_inputs::<_, EvmWiringT>(&call_input_stack, "call")?;
                 *outcome = inspector.call_end(&mut ctx.evm, &call_inputs, outcome.clone());
             }
             FrameResult::Create(outcome) => {
-                let create_inputs = create_input_stack.borrow_mut().pop().unwrap();
+                let create_inputs =
+                    pop_frame_inputs::<_, EvmWiringT>(&create_input_stack, "create")?;
                 *outcome = inspector.create_end(&mut ctx.evm, &create_inputs, outcome.clone());
             }
             FrameResult::EOFCreate(outcome) => {
-                let eofcreate_inputs = eofcreate_input_stack.borrow_mut().pop().unwrap();
+                let eofcreate_inputs =
+                    pop_frame_inputs::<_, EvmWiringT>(&eofcreate_input_stack, "eofcreate")?;
                 *outcome =
                     inspector.eofcreate_end(&mut ctx.evm, &eofcreate_inputs, outcome.clone());
             }
@@ -228,6 +429,124 @@ pub fn inspector_handle_register<
     });
 }
 
+/// Memory range as `(offset, len)`.
+type MemoryRange = (usize, usize);
+
+/// Returns the non-empty memory ranges read and written by the instruction, from its stack
+/// inputs.
+fn memory_access(opcode: u8, stack: &Stack) -> (Option<MemoryRange>, Option<MemoryRange>) {
+    let arg = |n: usize| {
+        stack
+            .peek(n)
+            .ok()
+            .map(|value| value.saturating_to::<usize>())
+    };
+    let range =
+        |offset: Option<usize>, len: Option<usize>| offset.zip(len).filter(|&(_, len)| len != 0);
+    match opcode {
+        opcode::MLOAD => (range(arg(0), Some(32)), None),
+        opcode::MSTORE => (None, range(arg(0), Some(32))),
+        opcode::MSTORE8 => (None, range(arg(0), Some(1))),
+        opcode::MCOPY => (range(arg(1), arg(2)), range(arg(0), arg(2))),
+        opcode::EXTCODECOPY => (None, range(arg(1), arg(3))),
+        _ => (None, range(arg(0), arg(2))),
+    }
+}
+
+/// Position in the journal as the number of journals and the number of entries in the last one.
+type JournalPosition = (usize, usize);
+
+/// Returns the current end of the journal, or `None` if the inspector does not track journal
+/// entries.
+fn journal_position<EvmWiringT>(ctx: &mut Context<EvmWiringT>) -> Option<JournalPosition>
+where
+    EvmWiringT: EvmWiring,
+    EvmWiringT::ExternalContext: GetInspector<EvmWiringT>,
+{
+    if !ctx.external.get_inspector().is_journal_entry_active() {
+        return None;
+    }
+    let journal = &ctx.evm.journaled_state.journal;
+    Some((journal.len(), journal.last().map_or(0, Vec::len)))
+}
+
+/// Calls inspector `journal_entry` for the entries appended since `position`.
+///
+/// Entries reverted in the meantime are truncated from the journal and are not reported.
+fn notify_journal_entries<EvmWiringT>(
+    ctx: &mut Context<EvmWiringT>,
+    position: Option<JournalPosition>,
+) where
+    EvmWiringT: EvmWiring,
+    EvmWiringT::ExternalContext: GetInspector<EvmWiringT>,
+{
+    let Some((journals, entries)) = position else {
+        return;
+    };
+    let inspector = ctx.external.get_inspector();
+    let journal = &ctx.evm.journaled_state.journal;
+    for (i, journal_entries) in journal.iter().enumerate().skip(journals.saturating_sub(1)) {
+        let start = if i + 1 == journals { entries } else { 0 };
+        for entry in journal_entries.get(start..).unwrap_or_default() {
+            inspector.journal_entry(entry);
+        }
+    }
+}
+
+/// Returns the current end of the journal and its number of entries, or `None` if the inspector
+/// does not track journal entries.
+fn journal_return_position<EvmWiringT>(
+    ctx: &mut Context<EvmWiringT>,
+) -> Option<(JournalPosition, usize)>
+where
+    EvmWiringT: EvmWiring,
+    EvmWiringT::ExternalContext: GetInspector<EvmWiringT>,
+{
+    let position = journal_position(ctx)?;
+    let len = ctx.evm.journaled_state.journal.iter().map(Vec::len).sum();
+    Some((position, len))
+}
+
+/// Calls inspector `journal_revert` if the frame return reverted the journal since `position`,
+/// else `journal_entry` for the entries appended since.
+fn notify_journal_return<EvmWiringT>(
+    ctx: &mut Context<EvmWiringT>,
+    position: Option<(JournalPosition, usize)>,
+) where
+    EvmWiringT: EvmWiring,
+    EvmWiringT::ExternalContext: GetInspector<EvmWiringT>,
+{
+    let Some((position, len)) = position else {
+        return;
+    };
+    // A checkpoint revert truncates the journals created since the checkpoint.
+    let journal = &ctx.evm.journaled_state.journal;
+    if journal.len() < position.0 {
+        let reverted = len - journal.iter().map(Vec::len).sum::<usize>();
+        if reverted > 0 {
+            ctx.external.get_inspector().journal_revert(reverted);
+        }
+    } else {
+        notify_journal_entries(ctx, Some(position));
+    }
+}
+
+/// Pops the inputs of the frame that has ended.
+///
+/// Inputs are pushed when a frame starts, so an empty stack means that frame starts and ends got
+/// out of sync, for example because a handle was replaced after the inspector was registered.
+/// This is returned as an error instead of panicking.
+fn pop_frame_inputs<T, EvmWiringT: EvmWiring>(
+    stack: &RefCell<Vec<T>>,
+    kind: &str,
+) -> EVMResultGeneric<T, EvmWiringT> {
+    stack.borrow_mut().pop().ok_or_else(|| {
+        EVMError::Custom(format!(
+            "inspector {kind} input stack is empty, frame start and end are out of sync"
+        ))
+    })
+}
+
 fn inspector_instruction<EvmWiringT>(
     prev: &DynInstruction<'_, Context<EvmWiringT>>,
     interpreter: &mut Interpreter,
@@ -248,11 +567,35 @@ fn inspector_instruction<EvmWiringT>(
         return;
     }
 
+    let opcode = interpreter.current_opcode();
+    let gas_before = interpreter.gas.remaining();
+
     // Reset PC to previous value.
     interpreter.instruction_pointer = unsafe { interpreter.instruction_pointer.add(1) };
 
-    // Execute instruction.
+    // Execute instruction and report the journal entries it appended.
+    let position = journal_position(host);
     prev(interpreter, host);
+    notify_journal_entries(host, position);
+
+    // Replace the gas charged by the instruction if the inspector overrides it. Instructions
+    // starting a frame are skipped, their charge includes the gas forwarded to the frame.
+    if interpreter.instruction_result == InstructionResult::Continue
+        && interpreter.next_action.is_none()
+        && host.external.get_inspector().is_gas_cost_override_active()
+    {
+        let default_cost = gas_before - interpreter.gas.remaining();
+        if let Some(cost) = host
+            .external
+            .get_inspector()
+            .override_gas_cost(opcode, default_cost)
+        {
+            interpreter.gas.erase_cost(default_cost);
+            if !interpreter.gas.record_cost(cost) {
+                interpreter.instruction_result = InstructionResult::OutOfGas;
+            }
+        }
+    }
 
     // Call step_end.
     host.external
@@ -264,13 +607,26 @@ fn inspector_instruction<EvmWiringT>(
 mod tests {
     use super::*;
     use crate::{inspector_handle_register, inspectors::NoOpInspector};
-    use database::BenchmarkDB;
+    use core::fmt::Debug;
+    use database::{BenchmarkDB, InMemoryDB};
     use revm::{
-        bytecode::{opcode, Bytecode},
+        bytecode::{opcode, Bytecode, Eof},
         database_interface::EmptyDB,
-        interpreter::{CallInputs, CallOutcome, CreateInputs, CreateOutcome},
-        primitives::{address, Bytes, TxKind},
-        wiring::{DefaultEthereumWiring, EthereumWiring, EvmWiring as PrimitiveEvmWiring},
+        interpreter::{
+            CallInputs, CallOutcome, CallValue, CreateInputs, CreateOutcome, Gas, InterpreterResult,
+        },
+        primitives::{address, bytes, Address, Bytes, TxKind, U256},
+        specification::{
+            eip7702::{Authorization as Eip7702Authorization, RecoveredAuthorization, Signature},
+            hardfork::SpecId,
+        },
+        state::AccountInfo,
+        test_utils::{call_evm_builder, TEST_CALLER},
+        transaction::{eip7702::Authorization, TransactionType},
+        wiring::{
+            result::{ExecutionResult, HaltReason, OutOfGasError, ResultAndState},
+            DefaultEthereumWiring, EthereumWiring, EvmWiring as PrimitiveEvmWiring,
+        },
         Evm, EvmContext, EvmWiring,
     };
 
@@ -405,4 +761,721 @@ mod tests {
             .append_handler_register(inspector_handle_register)
             .build();
     }
+
+    /// Inspector with the default callbacks, active unlike [`NoOpInspector`].
+    #[derive(Default, Debug)]
+    struct DefaultCallbacksInspector;
+
+    impl<EvmWiringT: EvmWiring> Inspector<EvmWiringT> for DefaultCallbacksInspector {}
+
+    /// Counts its steps, but reports itself inactive unless `active` is set.
+    #[derive(Default, Debug)]
+    struct StepCountInspector {
+        active: bool,
+        steps: usize,
+    }
+
+    impl<EvmWiringT: EvmWiring> Inspector<EvmWiringT> for StepCountInspector {
+        fn is_active(&self) -> bool {
+            self.active
+        }
+
+        fn step(&mut self, _interp: &mut Interpreter, _context: &mut EvmContext<EvmWiringT>) {
+            self.steps += 1;
+        }
+    }
+
+    #[test]
+    fn test_inactive_inspector_is_bypassed() {
+        let transact = |active| {
+            // Stores the call value at slot 0, then reverts in a call to itself.
+            let code = Bytecode::new_raw(Bytes::from_static(&[
+                opcode::CALLVALUE,
+                opcode::PUSH0,
+                opcode::SSTORE,
+                opcode::PUSH0,
+                opcode::PUSH0,
+                opcode::PUSH0,
+                opcode::PUSH0,
+                opcode::PUSH0,
+                opcode::ADDRESS,
+                opcode::PUSH2,
+                0x10,
+                0x00,
+                opcode::CALL,
+                opcode::STOP,
+            ]));
+            let mut evm = call_evm_builder(
+                BenchmarkDB::new_bytecode(code),
+                StepCountInspector { active, steps: 0 },
+                Address::ZERO,
+                100_000,
+            )
+            .append_handler_register(inspector_handle_register)
+            .build();
+            let result = evm.transact().unwrap();
+            (result, evm.into_context().external.steps)
+        };
+
+        let (bypassed, steps) = transact(false);
+        assert_eq!(steps, 0);
+        let (wrapped, steps) = transact(true);
+        assert!(steps > 0);
+        assert!(bypassed.result.is_success());
+        assert_eq!(bypassed, wrapped);
+    }
+
+    #[test]
+    fn test_dyn_inspector() {
+        let mut noop = NoOpInspector;
+        let inspector: &mut dyn Inspector<TestEvmWiring> = &mut noop;
+        assert!(!inspector.is_active());
+        let inspector: Box<dyn Inspector<TestEvmWiring>> = Box::new(DefaultCallbacksInspector);
+        assert!(inspector.is_active());
+    }
+
+    #[test]
+    fn test_frame_end_without_start_is_error() {
+        let mut evm = Evm::<EthereumWiring<BenchmarkDB, DefaultCallbacksInspector>>::builder()
+            .with_db(BenchmarkDB::new_bytecode(Bytecode::new()))
+            .with_default_ext_ctx()
+            .append_handler_register(inspector_handle_register)
+            .build();
+
+        // No call was started, so there are no inputs to pair the outcome with.
+        let mut result = FrameResult::Call(CallOutcome::new(
+            InterpreterResult::new(InstructionResult::Stop, Bytes::new(), Gas::new(0)),
+            0..0,
+        ));
+        let err = evm
+            .handler
+            .execution()
+            .last_frame_return(&mut evm.context, &mut result)
+            .unwrap_err();
+        assert!(matches!(err, EVMError::Custom(_)));
+    }
+
+    #[derive(Default, Debug)]
+    struct EofFunctionInspector {
+        /// `Some(section)` for `eof_call`, `None` for `eof_return`.
+        events: Vec<Option<u16>>,
+    }
+
+    impl<EvmWiringT: EvmWiring> Inspector<EvmWiringT> for EofFunctionInspector {
+        fn eof_call(&mut self, target_section: u16) {
+            self.events.push(Some(target_section));
+        }
+
+        fn eof_return(&mut self) {
+            self.events.push(None);
+        }
+    }
+
+    #[test]
+    fn test_inspector_eof_call_return() {
+        // Code section 0: `CALLF 1; STOP`, code section 1: `RETF`.
+        let eof = Eof::decode(bytes!(
+            "ef000101000802000200040001040000000080000000000000e3000100e4"
+        ))
+        .unwrap();
+
+        let mut evm = call_evm_builder(
+            BenchmarkDB::new_bytecode(Bytecode::Eof(Arc::new(eof))),
+            EofFunctionInspector::default(),
+            address!("0000000000000000000000000000000000000000"),
+            100_000,
+        )
+        .with_spec_id(SpecId::PRAGUE_EOF)
+        .append_handler_register(inspector_handle_register)
+        .build();
+
+        assert!(evm.transact().unwrap().result.is_success());
+        assert_eq!(evm.into_context().external.events, vec![Some(1), None]);
+    }
+
+    #[derive(Default, Debug)]
+    struct AuthorizationInspector {
+        authorizations: Vec<(Option<Address>, Address)>,
+    }
+
+    impl<EvmWiringT: EvmWiring> Inspector<EvmWiringT> for AuthorizationInspector {
+        fn authorization(&mut self, authorization: &dyn Authorization) {
+            self.authorizations
+                .push((authorization.authority(), authorization.address()));
+        }
+    }
+
+    #[test]
+    fn test_inspector_authorization() {
+        let authorities = [Address::with_last_byte(0xa1), Address::with_last_byte(0xa2)];
+        let delegates = [Address::with_last_byte(0xd1), Address::with_last_byte(0xd2)];
+
+        let mut evm = call_evm_builder(
+            BenchmarkDB::new_bytecode(Bytecode::new_raw(Bytes::from_static(&[opcode::STOP]))),
+            AuthorizationInspector::default(),
+            address!("0000000000000000000000000000000000000000"),
+            100_000,
+        )
+        .with_spec_id(SpecId::PRAGUE)
+        .modify_tx_env(|tx| {
+            tx.tx_type = TransactionType::Eip7702;
+            tx.authorization_list = authorities
+                .iter()
+                .zip(delegates)
+                .map(|(&authority, address)| {
+                    RecoveredAuthorization::new_unchecked(
+                        Eip7702Authorization {
+                            chain_id: U256::ZERO,
+                            address,
+                            nonce: 0,
+                        }
+                        .into_signed(Signature::test_signature()),
+                        Some(authority),
+                    )
+                })
+                .collect::<Vec<_>>()
+                .into();
+        })
+        .append_handler_register(inspector_handle_register)
+        .build();
+
+        assert!(evm.transact().unwrap().result.is_success());
+        assert_eq!(
+            evm.into_context().external.authorizations,
+            vec![
+                (Some(authorities[0]), delegates[0]),
+                (Some(authorities[1]), delegates[1]),
+            ]
+        );
+    }
+
+    #[derive(Default, Debug)]
+    struct MemoryInspector {
+        /// `(is_write, offset, len)` of each access.
+        accesses: Vec<(bool, usize, usize)>,
+    }
+
+    impl<EvmWiringT: EvmWiring> Inspector<EvmWiringT> for MemoryInspector {
+        fn memory_read(&mut self, offset: usize, len: usize) {
+            self.accesses.push((false, offset, len));
+        }
+
+        fn memory_write(&mut self, offset: usize, len: usize) {
+            self.accesses.push((true, offset, len));
+        }
+    }
+
+    #[test]
+    fn test_inspector_memory_access() {
+        let code = Bytecode::new_raw(Bytes::from_static(&[
+            opcode::PUSH1,
+            0xaa,
+            opcode::PUSH1,
+            0x20,
+            opcode::MSTORE,
+            opcode::PUSH1,
+            0x20,
+            opcode::MLOAD,
+            opcode::STOP,
+        ]));
+
+        let mut evm = call_evm_builder(
+            BenchmarkDB::new_bytecode(code),
+            MemoryInspector::default(),
+            address!("0000000000000000000000000000000000000000"),
+            100_000,
+        )
+        .append_handler_register(inspector_handle_register)
+        .build();
+
+        assert!(evm.transact().unwrap().result.is_success());
+        assert_eq!(
+            evm.into_context().external.accesses,
+            vec![(true, 0x20, 32), (false, 0x20, 32)]
+        );
+    }
+
+    #[derive(Default, Debug)]
+    struct TransientStorageInspector {
+        /// `(is_write, address, key, value)` of each access.
+        accesses: Vec<(bool, Address, U256, U256)>,
+    }
+
+    impl<EvmWiringT: EvmWiring> Inspector<EvmWiringT> for TransientStorageInspector {
+        fn tload(&mut self, address: Address, key: U256, value: U256) {
+            self.accesses.push((false, address, key, value));
+        }
+
+        fn tstore(&mut self, address: Address, key: U256, value: U256) {
+            self.accesses.push((true, address, key, value));
+        }
+    }
+
+    #[test]
+    fn test_inspector_transient_storage() {
+        let code = Bytecode::new_raw(Bytes::from_static(&[
+            opcode::PUSH1,
+            0xaa,
+            opcode::PUSH1,
+            0x01,
+            opcode::TSTORE,
+            opcode::PUSH1,
+            0x01,
+            opcode::TLOAD,
+            opcode::POP,
+            opcode::PUSH1,
+            0x02,
+            opcode::TLOAD,
+            opcode::STOP,
+        ]));
+        let contract = address!("0000000000000000000000000000000000000000");
+
+        let mut evm = call_evm_builder(
+            BenchmarkDB::new_bytecode(code),
+            TransientStorageInspector::default(),
+            contract,
+            100_000,
+        )
+        .append_handler_register(inspector_handle_register)
+        .build();
+
+        assert!(evm.transact().unwrap().result.is_success());
+        assert_eq!(
+            evm.into_context().external.accesses,
+            vec![
+                (true, contract, U256::from(1), U256::from(0xaa)),
+                (false, contract, U256::from(1), U256::from(0xaa)),
+                (false, contract, U256::from(2), U256::ZERO),
+            ]
+        );
+    }
+
+    /// Rewrites the calldata and value of every call.
+    #[derive(Debug)]
+    struct RewriteCallInspector;
+
+    impl<EvmWiringT: EvmWiring> Inspector<EvmWiringT> for RewriteCallInspector {
+        fn call(
+            &mut self,
+            _context: &mut EvmContext<EvmWiringT>,
+            inputs: &mut CallInputs,
+        ) -> Option<CallOutcome> {
+            inputs.input = U256::from(0x2a).to_be_bytes_vec().into();
+            inputs.value = CallValue::Transfer(U256::from(5));
+            None
+        }
+    }
+
+    #[test]
+    fn test_inspector_rewrite_call_inputs() {
+        let caller = TEST_CALLER;
+        let contract = address!("00000000000000000000000000000000000000a0");
+
+        // Stores the first calldata word at slot 0 and the call value at slot 1.
+        let mut db = InMemoryDB::default();
+        db.insert_account_info(caller, AccountInfo::from_balance(U256::from(100)));
+        db.insert_account_info(
+            contract,
+            AccountInfo::from_bytecode(Bytecode::new_raw(Bytes::from_static(&[
+                opcode::PUSH0,
+                opcode::CALLDATALOAD,
+                opcode::PUSH0,
+                opcode::SSTORE,
+                opcode::CALLVALUE,
+                opcode::PUSH1,
+                0x01,
+                opcode::SSTORE,
+                opcode::STOP,
+            ]))),
+        );
+
+        let mut evm = call_evm_builder(db, RewriteCallInspector, contract, 100_000)
+            .append_handler_register(inspector_handle_register)
+            .build();
+
+        let ResultAndState { result, state, .. } = evm.transact().unwrap();
+        assert!(result.is_success());
+        let account = &state[&contract];
+        assert_eq!(account.storage[&U256::ZERO].present_value, U256::from(0x2a));
+        assert_eq!(account.storage[&U256::from(1)].present_value, U256::from(5));
+        assert_eq!(account.info.balance, U256::from(5));
+    }
+
+    #[derive(Default, Debug)]
+    struct JumpInspector {
+        /// `(from_pc, to_pc, conditional, taken)` of each jump.
+        jumps: Vec<(usize, usize, bool, bool)>,
+    }
+
+    impl<EvmWiringT: EvmWiring> Inspector<EvmWiringT> for JumpInspector {
+        fn jump(&mut self, from_pc: usize, to_pc: usize, conditional: bool, taken: bool) {
+            self.jumps.push((from_pc, to_pc, conditional, taken));
+        }
+    }
+
+    #[test]
+    fn test_inspector_jump() {
+        let code = Bytecode::new_raw(Bytes::from_static(&[
+            // Not taken, the condition is zero.
+            opcode::PUSH0,
+            opcode::PUSH1,
+            0x0a,
+            opcode::JUMPI,
+            // Taken.
+            opcode::PUSH1,
+            0x01,
+            opcode::PUSH1,
+            0x0a,
+            opcode::JUMPI,
+            opcode::INVALID,
+            opcode::JUMPDEST,
+            opcode::PUSH1,
+            0x0f,
+            opcode::JUMP,
+            opcode::INVALID,
+            opcode::JUMPDEST,
+            opcode::STOP,
+        ]));
+
+        let mut evm = call_evm_builder(
+            BenchmarkDB::new_bytecode(code),
+            JumpInspector::default(),
+            address!("0000000000000000000000000000000000000000"),
+            100_000,
+        )
+        .append_handler_register(inspector_handle_register)
+        .build();
+
+        assert!(evm.transact().unwrap().result.is_success());
+        assert_eq!(
+            evm.into_context().external.jumps,
+            vec![
+                (3, 4, true, false),
+                (8, 10, true, true),
+                (13, 15, false, true)
+            ]
+        );
+    }
+
+    #[derive(Default, Debug)]
+    struct ReadCodeInspector {
+        reads: Vec<(Address, CodeReadKind)>,
+    }
+
+    impl<EvmWiringT: EvmWiring> Inspector<EvmWiringT> for ReadCodeInspector {
+        fn read_code(&mut self, address: Address, kind: CodeReadKind) {
+            self.reads.push((address, kind));
+        }
+    }
+
+    #[test]
+    fn test_inspector_read_code() {
+        let first = address!("00000000000000000000000000000000000000a1");
+        let second = address!("00000000000000000000000000000000000000a2");
+        let mut code = vec![opcode::PUSH20];
+        code.extend_from_slice(first.as_slice());
+        code.extend_from_slice(&[opcode::EXTCODESIZE, opcode::PUSH20]);
+        code.extend_from_slice(second.as_slice());
+        code.extend_from_slice(&[opcode::EXTCODESIZE, opcode::STOP]);
+
+        let mut evm = call_evm_builder(
+            BenchmarkDB::new_bytecode(Bytecode::new_raw(code.into())),
+            ReadCodeInspector::default(),
+            Address::ZERO,
+            100_000,
+        )
+        .append_handler_register(inspector_handle_register)
+        .build();
+
+        assert!(evm.transact().unwrap().result.is_success());
+        assert_eq!(
+            evm.into_context().external.reads,
+            vec![(first, CodeReadKind::Size), (second, CodeReadKind::Size)]
+        );
+    }
+
+    #[derive(Debug)]
+    struct CreateGasInspector {
+        returned: u64,
+    }
+
+    impl<EvmWiringT: EvmWiring> Inspector<EvmWiringT> for CreateGasInspector {
+        fn create_end(
+            &mut self,
+            _context: &mut EvmContext<EvmWiringT>,
+            _inputs: &CreateInputs,
+            mut outcome: CreateOutcome,
+        ) -> CreateOutcome {
+            outcome.result.gas.erase_cost(self.returned);
+            outcome
+        }
+    }
+
+    #[test]
+    fn test_inspector_create_end_gas() {
+        // Creates a contract with the reverting init code `PUSH0 PUSH0 REVERT`.
+        let code = Bytecode::new_raw(Bytes::from_static(&[
+            opcode::PUSH3,
+            opcode::PUSH0,
+            opcode::PUSH0,
+            opcode::REVERT,
+            opcode::PUSH0,
+            opcode::MSTORE,
+            opcode::PUSH1,
+            0x03,
+            opcode::PUSH1,
+            0x1d,
+            opcode::PUSH0,
+            opcode::CREATE,
+            opcode::STOP,
+        ]));
+
+        let gas_used = |returned| {
+            let mut evm = call_evm_builder(
+                BenchmarkDB::new_bytecode(code.clone()),
+                CreateGasInspector { returned },
+                Address::ZERO,
+                100_000,
+            )
+            .append_handler_register(inspector_handle_register)
+            .build();
+            let result = evm.transact().unwrap().result;
+            assert!(result.is_success());
+            result.gas_used()
+        };
+
+        assert_eq!(gas_used(0) - gas_used(1_000), 1_000);
+    }
+
+    #[derive(Debug)]
+    struct GasCostInspector {
+        opcode: u8,
+        factor: u64,
+    }
+
+    impl<EvmWiringT: EvmWiring> Inspector<EvmWiringT> for GasCostInspector {
+        fn is_gas_cost_override_active(&self) -> bool {
+            true
+        }
+
+        fn override_gas_cost(&mut self, opcode: u8, default_cost: u64) -> Option<u64> {
+            (opcode == self.opcode).then_some(default_cost * self.factor)
+        }
+    }
+
+    #[test]
+    fn test_inspector_override_gas_cost() {
+        // Loads the cold slot 0.
+        let code = Bytecode::new_raw(Bytes::from_static(&[
+            opcode::PUSH0,
+            opcode::SLOAD,
+            opcode::STOP,
+        ]));
+
+        let gas_used = |factor| {
+            let mut evm = call_evm_builder(
+                BenchmarkDB::new_bytecode(code.clone()),
+                GasCostInspector {
+                    opcode: opcode::SLOAD,
+                    factor,
+                },
+                Address::ZERO,
+                100_000,
+            )
+            .append_handler_register(inspector_handle_register)
+            .build();
+            let result = evm.transact().unwrap().result;
+            assert!(result.is_success());
+            result.gas_used()
+        };
+
+        assert_eq!(gas_used(1), 21_000 + 2 + 2_100);
+        assert_eq!(gas_used(2), 21_000 + 2 + 4_200);
+    }
+
+    #[test]
+    fn test_inspector_override_gas_cost_out_of_gas() {
+        // Stores 1 at the cold slot 0, which costs 22100 gas.
+        let code = Bytecode::new_raw(Bytes::from_static(&[
+            opcode::PUSH1,
+            0x01,
+            opcode::PUSH0,
+            opcode::SSTORE,
+            opcode::STOP,
+        ]));
+
+        let inspector = GasCostInspector {
+            opcode: opcode::SSTORE,
+            factor: 2,
+        };
+        // Enough for the default cost but not for the doubled one.
+        let gas_limit = 21_000 + 5 + 30_000;
+        let mut evm = call_evm_builder(
+            BenchmarkDB::new_bytecode(code),
+            inspector,
+            Address::ZERO,
+            gas_limit,
+        )
+        .append_handler_register(inspector_handle_register)
+        .build();
+
+        let ResultAndState { result, state, .. } = evm.transact().unwrap();
+        assert_eq!(
+            result,
+            ExecutionResult::Halt {
+                reason: HaltReason::OutOfGas(OutOfGasError::Basic),
+                gas_used: gas_limit,
+            }
+        );
+        // The store already happened when the cost was overridden, it is reverted with the frame.
+        assert!(state[&Address::ZERO]
+            .storage
+            .values()
+            .all(|slot| !slot.is_changed()));
+    }
+
+    #[derive(Default, Debug)]
+    struct JournalEntryInspector {
+        active: bool,
+        entries: Vec<JournalEntry>,
+        reverted: Vec<usize>,
+    }
+
+    impl<EvmWiringT: EvmWiring> Inspector<EvmWiringT> for JournalEntryInspector {
+        fn is_journal_entry_active(&self) -> bool {
+            self.active
+        }
+
+        fn journal_entry(&mut self, entry: &JournalEntry) {
+            self.entries.push(entry.clone());
+        }
+
+        fn journal_revert(&mut self, count: usize) {
+            self.entries.truncate(self.entries.len() - count);
+            self.reverted.push(count);
+        }
+    }
+
+    #[test]
+    fn test_inspector_journal_entry() {
+        let code = Bytecode::new_raw(Bytes::from_static(&[
+            opcode::PUSH1,
+            0xaa,
+            opcode::PUSH1,
+            0x01,
+            opcode::SSTORE,
+            opcode::STOP,
+        ]));
+        let contract = address!("0000000000000000000000000000000000000000");
+
+        let entries = |active| {
+            let mut evm = call_evm_builder(
+                BenchmarkDB::new_bytecode(code.clone()),
+                JournalEntryInspector {
+                    active,
+                    ..Default::default()
+                },
+                contract,
+                100_000,
+            )
+            .append_handler_register(inspector_handle_register)
+            .build();
+            assert!(evm.transact().unwrap().result.is_success());
+            evm.into_context().external.entries
+        };
+
+        // Inspectors that do not opt in are not reported any entry.
+        assert!(entries(false).is_empty());
+        // The frame start touches the contract, then SSTORE warms and changes the slot.
+        assert_eq!(
+            entries(true),
+            vec![
+                JournalEntry::AccountTouched { address: contract },
+                JournalEntry::StorageWarmed {
+                    address: contract,
+                    key: U256::from(1),
+                },
+                JournalEntry::StorageChanged {
+                    address: contract,
+                    key: U256::from(1),
+                    had_value: U256::ZERO,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_inspector_journal_revert() {
+        let contract = address!("00000000000000000000000000000000000000a0");
+        let callee = address!("00000000000000000000000000000000000000a1");
+
+        // Calls the callee, then stores 0xbb at slot 2.
+        let mut code = vec![
+            opcode::PUSH0,
+            opcode::PUSH0,
+            opcode::PUSH0,
+            opcode::PUSH0,
+            opcode::PUSH0,
+            opcode::PUSH20,
+        ];
+        code.extend_from_slice(callee.as_slice());
+        code.extend_from_slice(&[
+            opcode::GAS,
+            opcode::CALL,
+            opcode::POP,
+            opcode::PUSH1,
+            0xbb,
+            opcode::PUSH1,
+            0x02,
+            opcode::SSTORE,
+            opcode::STOP,
+        ]);
+        // Stores 0xaa at slot 1, then reverts.
+        let callee_code = Bytes::from_static(&[
+            opcode::PUSH1,
+            0xaa,
+            opcode::PUSH1,
+            0x01,
+            opcode::SSTORE,
+            opcode::PUSH0,
+            opcode::PUSH0,
+            opcode::REVERT,
+        ]);
+
+        let mut db = InMemoryDB::default();
+        db.insert_account_info(
+            contract,
+            AccountInfo::from_bytecode(Bytecode::new_raw(code.into())),
+        );
+        db.insert_account_info(
+            callee,
+            AccountInfo::from_bytecode(Bytecode::new_raw(callee_code)),
+        );
+
+        let mut evm = call_evm_builder(
+            db,
+            JournalEntryInspector {
+                active: true,
+                ..Default::default()
+            },
+            contract,
+            100_000,
+        )
+        .append_handler_register(inspector_handle_register)
+        .build();
+        assert!(evm.transact().unwrap().result.is_success());
+        let inspector = evm.into_context().external;
+
+        // The callee's touch, slot warming and change are retracted.
+        assert_eq!(inspector.reverted, vec![3]);
+        assert!(!inspector.entries.iter().any(|entry| matches!(
+            entry,
+            JournalEntry::StorageChanged { address, .. } if *address == callee
+        )));
+        assert!(inspector.entries.contains(&JournalEntry::StorageChanged {
+            address: contract,
+            key: U256::from(2),
+            had_value: U256::ZERO,
+        }));
+    }
 }
@@ -4,12 +4,53 @@ use revm::{
         CallInputs, CallOutcome, CreateInputs, CreateOutcome, EOFCreateInputs, Interpreter,
     },
     primitives::{Address, Log, U256},
-    EvmContext, EvmWiring,
+    transaction::eip7702::Authorization,
+    EvmContext, EvmWiring, JournalEntry,
 };
 
+/// How an instruction read the code of an account, see [`Inspector::read_code`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum CodeReadKind {
+    /// `EXTCODESIZE`.
+    Size,
+    /// `EXTCODEHASH`.
+    Hash,
+    /// `EXTCODECOPY`.
+    Copy,
+}
+
 /// EVM [Interpreter] callbacks.
 #[auto_impl(&mut, Box)]
 pub trait Inspector<EvmWiringT: EvmWiring> {
+    /// Returns `false` if all callbacks of the inspector are no-ops.
+    ///
+    /// Frames of inactive inspectors run on the instruction table that
+    /// [`inspector_handle_register`](crate::inspector_handle_register) wrapped, without the
+    /// per-instruction inspector calls.
+    #[inline]
+    fn is_active(&self) -> bool {
+        true
+    }
+
+    /// Returns `true` if the inspector implements [`Inspector::journal_entry`] and
+    /// [`Inspector::journal_revert`].
+    ///
+    /// Journal entries are only tracked and reported to inspectors that opt in, as it is done
+    /// around every instruction.
+    #[inline]
+    fn is_journal_entry_active(&self) -> bool {
+        false
+    }
+
+    /// Returns `true` if the inspector implements [`Inspector::override_gas_cost`].
+    ///
+    /// Gas costs are only reported to inspectors that opt in, as it is done after every
+    /// instruction.
+    #[inline]
+    fn is_gas_cost_override_active(&self) -> bool {
+        false
+    }
+
     /// Called before the interpreter is initialized.
     ///
     /// If `interp.instruction_result` is set to anything other than [revm::interpreter::InstructionResult::Continue] then the execution of the interpreter
@@ -40,6 +81,9 @@ pub trait Inspector<EvmWiringT: EvmWiring> {
 
     /// Called after `step` when the instruction has been executed.
     ///
+    /// The running gas refund counter of the current frame is available through
+    /// `interp.gas.refunded()`.
+    ///
     /// Setting `interp.instruction_result` to anything other than [revm::interpreter::InstructionResult::Continue] alters the execution
     /// of the interpreter.
     #[inline]
@@ -59,6 +103,11 @@ pub trait Inspector<EvmWiringT: EvmWiring> {
     /// Called whenever a call to a contract is about to start.
     ///
     /// InstructionResulting anything other than [revm::interpreter::InstructionResult::Continue] overrides the result of the call.
+    ///
+    /// If this returns `None`, the call proceeds with `inputs` as modified here, e.g. with a
+    /// rewritten `input`, `value` or `gas_limit`. `call_end` receives the modified inputs. The
+    /// caller was already charged the original gas limit and gets back the gas the callee did
+    /// not spend, so raising `gas_limit` gives the callee gas nobody paid for.
     #[inline]
     fn call(
         &mut self,
@@ -107,6 +156,11 @@ pub trait Inspector<EvmWiringT: EvmWiring> {
     ///
     /// InstructionResulting anything other than the values passed to this function (`(ret, remaining_gas,
     /// address, out)`) will alter the result of the create.
+    ///
+    /// The gas of the returned outcome is what the calling frame gets back: its remaining gas
+    /// is returned to the caller if the creation succeeded or reverted, and its refund is
+    /// recorded if it succeeded. A halted creation consumes all of its gas whatever the outcome
+    /// gas is, change the result to a revert to give gas back.
     #[inline]
     fn create_end(
         &mut self,
@@ -151,4 +205,131 @@ pub trait Inspector<EvmWiringT: EvmWiring> {
         let _ = target;
         let _ = value;
     }
+
+    /// Called after an EOF `CALLF` entered the code section `target_section`.
+    ///
+    /// EOF function calls run inside the current frame, so they are not reported by `call`.
+    #[inline]
+    fn eof_call(&mut self, target_section: u16) {
+        let _ = target_section;
+    }
+
+    /// Called after an EOF `RETF` returned to the calling code section.
+    #[inline]
+    fn eof_return(&mut self) {}
+
+    /// Called after an instruction read `len` bytes of memory starting at `offset`.
+    ///
+    /// Reported for `MLOAD` and the source of `MCOPY`. Empty reads are not reported.
+    #[inline]
+    fn memory_read(&mut self, offset: usize, len: usize) {
+        let _ = offset;
+        let _ = len;
+    }
+
+    /// Called after an instruction wrote `len` bytes of memory starting at `offset`.
+    ///
+    /// Reported for `MSTORE`, `MSTORE8`, `MCOPY` and the `*COPY` opcodes copying into memory.
+    /// Empty writes are not reported.
+    #[inline]
+    fn memory_write(&mut self, offset: usize, len: usize) {
+        let _ = offset;
+        let _ = len;
+    }
+
+    /// Called after `TLOAD` read `value` from transient storage `key` of `address`.
+    #[inline]
+    fn tload(&mut self, address: Address, key: U256, value: U256) {
+        let _ = address;
+        let _ = key;
+        let _ = value;
+    }
+
+    /// Called after `TSTORE` wrote `value` to transient storage `key` of `address`.
+    #[inline]
+    fn tstore(&mut self, address: Address, key: U256, value: U256) {
+        let _ = address;
+        let _ = key;
+        let _ = value;
+    }
+
+    /// Called after a `JUMP` or `JUMPI` at `from_pc` executed.
+    ///
+    /// `to_pc` is the program counter execution continues at, the instruction following the
+    /// `JUMPI` if it was not `taken`. `conditional` is `true` for `JUMPI`. Jumps to an invalid
+    /// destination are not reported.
+    #[inline]
+    fn jump(&mut self, from_pc: usize, to_pc: usize, conditional: bool, taken: bool) {
+        let _ = from_pc;
+        let _ = to_pc;
+        let _ = conditional;
+        let _ = taken;
+    }
+
+    /// Called after an instruction executed, with the gas `default_cost` it charged, including
+    /// memory expansion.
+    ///
+    /// If this returns `Some`, the returned cost is charged instead. Allows modelling alternative
+    /// gas schedules without replacing instructions. Instructions that halted or started a call
+    /// or create frame are not reported, as their charge includes the gas forwarded to the frame.
+    ///
+    /// The instruction already took effect when it is called. If the returned cost exceeds the
+    /// gas left before the instruction, the frame halts with `OutOfGas` and its state changes,
+    /// including those of the instruction, are reverted like for any out of gas halt.
+    ///
+    /// Only called if [`Inspector::is_gas_cost_override_active`] returns `true`.
+    #[inline]
+    fn override_gas_cost(&mut self, opcode: u8, default_cost: u64) -> Option<u64> {
+        let _ = opcode;
+        let _ = default_cost;
+        None
+    }
+
+    /// Called after `EXTCODESIZE`, `EXTCODEHASH` or `EXTCODECOPY` read the code of `address`.
+    #[inline]
+    fn read_code(&mut self, address: Address, kind: CodeReadKind) {
+        let _ = address;
+        let _ = kind;
+    }
+
+    /// Called once per transaction after it is validated, before the accounts are loaded and the
+    /// caller is charged.
+    ///
+    /// The transaction is available in `context.env.tx`, e.g. for tracers to tag the execution
+    /// of a deposit with its source hash.
+    #[inline]
+    fn transaction_start(&mut self, context: &mut EvmContext<EvmWiringT>) {
+        let _ = context;
+    }
+
+    /// Called for each EIP-7702 authorization of the transaction, in order, before the
+    /// authorization list is applied.
+    ///
+    /// Invalid authorizations are reported as well, they are skipped when applied.
+    #[inline]
+    fn authorization(&mut self, authorization: &dyn Authorization) {
+        let _ = authorization;
+    }
+
+    /// Called for each entry appended to the journal by an instruction, when a frame starts and
+    /// when a created contract's code is set, in the order the entries were appended.
+    ///
+    /// Entries appended before or after the execution of the transaction's frames, for example
+    /// when the caller is charged, are not reported.
+    ///
+    /// Only called if [`Inspector::is_journal_entry_active`] returns `true`.
+    #[inline]
+    fn journal_entry(&mut self, entry: &JournalEntry) {
+        let _ = entry;
+    }
+
+    /// Called when a frame reverts, with the number of entries reported by
+    /// [`Inspector::journal_entry`] that were reverted. These are the last `count` entries
+    /// reported, including those of the frame's committed sub-frames.
+    ///
+    /// Only called if [`Inspector::is_journal_entry_active`] returns `true`.
+    #[inline]
+    fn journal_revert(&mut self, count: usize) {
+        let _ = count;
+    }
 }
@@ -5,24 +5,57 @@
 #[cfg(not(feature = "std"))]
 extern crate alloc as std;
 
+mod access_list;
+mod breakpoint;
+mod call_trace;
+mod coverage;
+mod create_collision;
+mod create_tree;
 #[cfg(feature = "std")]
 mod customprinter;
+mod deepest_revert;
+mod deployment_size;
 #[cfg(all(feature = "std", feature = "serde-json"))]
 mod eip3155;
+mod four_byte;
 mod gas;
 mod handler_register;
 mod inspector;
 mod noop;
+mod opcode_trace;
+mod out_of_gas;
+mod precompile_usage;
+mod read_before_write;
+#[cfg(all(feature = "std", feature = "serde-json"))]
+mod struct_log;
+mod value_transfer;
 
+pub use access_list::{simulate_access_list, AccessListSimulation};
 pub use handler_register::{inspector_handle_register, GetInspector};
-pub use inspector::Inspector;
+pub use inspector::{CodeReadKind, Inspector};
 
 /// [Inspector] implementations.
 pub mod inspectors {
+    pub use super::access_list::AccessListInspector;
+    pub use super::breakpoint::{BreakpointHit, BreakpointInspector};
+    pub use super::call_trace::{CallTraceInspector, CallTraceNode};
+    pub use super::coverage::CoverageInspector;
+    pub use super::create_collision::{CreateCollision, CreateCollisionInspector};
+    pub use super::create_tree::{CreateNode, CreateNodeKind, CreateTreeInspector};
     #[cfg(feature = "std")]
     pub use super::customprinter::CustomPrintTracer;
+    pub use super::deepest_revert::{DeepestRevertInspector, RevertFrame};
+    pub use super::deployment_size::{Deployment, DeploymentSizeInspector};
     #[cfg(all(feature = "std", feature = "serde-json"))]
     pub use super::eip3155::TracerEip3155;
+    pub use super::four_byte::FourByteInspector;
     pub use super::gas::GasInspector;
     pub use super::noop::NoOpInspector;
+    pub use super::opcode_trace::OpcodeTraceInspector;
+    pub use super::out_of_gas::{OutOfGas, OutOfGasInspector};
+    pub use super::precompile_usage::PrecompileUsageInspector;
+    pub use super::read_before_write::ReadBeforeWriteInspector;
+    #[cfg(all(feature = "std", feature = "serde-json"))]
+    pub use super::struct_log::StreamingStructLogTracer;
+    pub use super::value_transfer::ValueTransferInspector;
 }
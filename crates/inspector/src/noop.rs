@@ -5,4 +5,9 @@ use revm::EvmWiring;
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct NoOpInspector;
 
-impl<EvmWiringT: EvmWiring> Inspector<EvmWiringT> for NoOpInspector {}
+impl<EvmWiringT: EvmWiring> Inspector<EvmWiringT> for NoOpInspector {
+    #[inline]
+    fn is_active(&self) -> bool {
+        false
+    }
+}
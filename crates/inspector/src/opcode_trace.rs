@@ -0,0 +1,84 @@
+//! Opcode trace inspector, records the executed opcodes in order.
+
+use crate::Inspector;
+use revm::{interpreter::Interpreter, EvmContext, EvmWiring};
+use std::vec::Vec;
+
+/// [Inspector] that records the `(pc, opcode)` pair of each executed instruction.
+///
+/// A lightweight alternative to the EIP-3155 tracer for control flow analysis, the stack and
+/// memory are not recorded. Instructions of all frames are recorded in execution order, the
+/// program counter is relative to the code of their frame.
+#[derive(Clone, Debug, Default)]
+pub struct OpcodeTraceInspector {
+    trace: Vec<(usize, u8)>,
+}
+
+impl OpcodeTraceInspector {
+    /// Returns the recorded `(pc, opcode)` pairs.
+    pub fn trace(&self) -> &[(usize, u8)] {
+        &self.trace
+    }
+
+    /// Returns the recorded opcodes, without their program counter.
+    pub fn opcodes(&self) -> impl Iterator<Item = u8> + '_ {
+        self.trace.iter().map(|&(_, opcode)| opcode)
+    }
+}
+
+impl<EvmWiringT: EvmWiring> Inspector<EvmWiringT> for OpcodeTraceInspector {
+    fn step(&mut self, interp: &mut Interpreter, _context: &mut EvmContext<EvmWiringT>) {
+        self.trace
+            .push((interp.program_counter(), interp.current_opcode()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::inspector_handle_register;
+    use database::BenchmarkDB;
+    use revm::{
+        bytecode::{opcode, Bytecode},
+        primitives::{Address, Bytes},
+        test_utils::call_evm_builder,
+    };
+
+    #[test]
+    fn test_opcode_trace_inspector() {
+        // Jumps over the INVALID opcode if the call value is zero.
+        let code = Bytecode::new_raw(Bytes::from_static(&[
+            opcode::CALLVALUE,
+            opcode::ISZERO,
+            opcode::PUSH1,
+            0x07,
+            opcode::JUMPI,
+            opcode::INVALID,
+            opcode::INVALID,
+            opcode::JUMPDEST,
+            opcode::STOP,
+        ]));
+
+        let mut evm = call_evm_builder(
+            BenchmarkDB::new_bytecode(code),
+            OpcodeTraceInspector::default(),
+            Address::ZERO,
+            100_000,
+        )
+        .append_handler_register(inspector_handle_register)
+        .build();
+
+        assert!(evm.transact().unwrap().result.is_success());
+        assert_eq!(
+            evm.into_context().external.trace(),
+            &[
+                (0, opcode::CALLVALUE),
+                (1, opcode::ISZERO),
+                (2, opcode::PUSH1),
+                (4, opcode::JUMPI),
+                (7, opcode::JUMPDEST),
+                (8, opcode::STOP),
+            ]
+        );
+    }
+}
@@ -0,0 +1,139 @@
+//! Out of gas inspector, finds the opcode that ran out of gas.
+
+use crate::Inspector;
+use revm::{
+    interpreter::{InstructionResult, Interpreter},
+    primitives::Address,
+    EvmContext, EvmWiring,
+};
+
+/// An opcode that ran out of gas, recorded by [`OutOfGasInspector`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OutOfGas {
+    /// Address of the executing contract.
+    pub address: Address,
+    /// Program counter of the opcode.
+    pub pc: usize,
+    /// The opcode.
+    pub opcode: u8,
+    /// Out of gas result of the opcode, e.g. `MemoryOOG` when memory expansion ran out.
+    pub result: InstructionResult,
+    /// Gas remaining before the opcode executed.
+    pub gas_before: u64,
+    /// Gas remaining when the opcode ran out, the charge it failed to record exceeded it.
+    ///
+    /// Lower than `gas_before` if the opcode charged a part of its cost before running out.
+    pub gas_left: u64,
+    /// The charge the opcode failed to record, `None` if it ran out of gas without one, e.g.
+    /// with `MemoryLimitOOG`.
+    pub gas_requested: Option<u64>,
+}
+
+/// [Inspector] that records the last opcode that halted with an out of gas result.
+///
+/// If the transaction halted out of gas, it is the opcode that exhausted its gas. Out of gas
+/// halts of inner frames are recorded too, even if the caller recovered from them.
+#[derive(Clone, Debug, Default)]
+pub struct OutOfGasInspector {
+    pc: usize,
+    opcode: u8,
+    gas_before: u64,
+    out_of_gas: Option<OutOfGas>,
+}
+
+impl OutOfGasInspector {
+    /// Returns the last opcode that ran out of gas.
+    pub fn out_of_gas(&self) -> Option<&OutOfGas> {
+        self.out_of_gas.as_ref()
+    }
+}
+
+impl<EvmWiringT: EvmWiring> Inspector<EvmWiringT> for OutOfGasInspector {
+    fn step(&mut self, interp: &mut Interpreter, _context: &mut EvmContext<EvmWiringT>) {
+        self.pc = interp.program_counter();
+        self.opcode = interp.current_opcode();
+        self.gas_before = interp.gas.remaining();
+    }
+
+    fn step_end(&mut self, interp: &mut Interpreter, _context: &mut EvmContext<EvmWiringT>) {
+        if matches!(
+            interp.instruction_result,
+            InstructionResult::OutOfGas
+                | InstructionResult::MemoryOOG
+                | InstructionResult::MemoryLimitOOG
+                | InstructionResult::PrecompileOOG
+                | InstructionResult::InvalidOperandOOG
+                | InstructionResult::ReentrancySentryOOG
+        ) {
+            self.out_of_gas = Some(OutOfGas {
+                address: interp.contract.target_address,
+                pc: self.pc,
+                opcode: self.opcode,
+                result: interp.instruction_result,
+                gas_before: self.gas_before,
+                gas_left: interp.gas.remaining(),
+                gas_requested: interp.gas.rejected_cost(),
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::inspector_handle_register;
+    use database::InMemoryDB;
+    use revm::{
+        bytecode::{opcode, Bytecode},
+        primitives::{address, Bytes},
+        state::AccountInfo,
+        test_utils::call_evm_builder,
+        wiring::result::{ExecutionResult, HaltReason, OutOfGasError},
+    };
+
+    #[test]
+    fn test_out_of_gas_inspector() {
+        let contract = address!("00000000000000000000000000000000000000a0");
+
+        // Store 1 at slot 0, which costs 22100 gas.
+        let mut db = InMemoryDB::default();
+        db.insert_account_info(
+            contract,
+            AccountInfo::from_bytecode(Bytecode::new_raw(Bytes::from_static(&[
+                opcode::PUSH1,
+                0x01,
+                opcode::PUSH0,
+                opcode::SSTORE,
+                opcode::STOP,
+            ]))),
+        );
+
+        // Intrinsic gas, both pushes and 5000 gas left for SSTORE.
+        let gas_limit = 21_000 + 5 + 5_000;
+        let mut evm = call_evm_builder(db, OutOfGasInspector::default(), contract, gas_limit)
+            .append_handler_register(inspector_handle_register)
+            .build();
+
+        assert_eq!(
+            evm.transact().unwrap().result,
+            ExecutionResult::Halt {
+                reason: HaltReason::OutOfGas(OutOfGasError::Basic),
+                gas_used: 26_005,
+            }
+        );
+
+        let inspector = evm.into_context().external;
+        assert_eq!(
+            inspector.out_of_gas(),
+            Some(&OutOfGas {
+                address: contract,
+                pc: 3,
+                opcode: opcode::SSTORE,
+                result: InstructionResult::OutOfGas,
+                gas_before: 5_000,
+                gas_left: 5_000,
+                gas_requested: Some(22_100),
+            })
+        );
+    }
+}
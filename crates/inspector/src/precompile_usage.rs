@@ -0,0 +1,89 @@
+//! Precompile usage inspector, counts calls to precompiles.
+
+use crate::Inspector;
+use revm::{
+    interpreter::{CallInputs, CallOutcome},
+    primitives::{Address, HashMap},
+    EvmContext, EvmWiring,
+};
+
+/// [Inspector] that counts calls per precompile address.
+///
+/// Calls are counted when they start, including the ones that fail, and by the address of the
+/// executed code so delegate calls to a precompile are included.
+#[derive(Clone, Debug, Default)]
+pub struct PrecompileUsageInspector {
+    counts: HashMap<Address, u64>,
+}
+
+impl PrecompileUsageInspector {
+    /// Returns the call counts keyed by precompile address.
+    pub fn into_map(self) -> HashMap<Address, u64> {
+        self.counts
+    }
+}
+
+impl<EvmWiringT: EvmWiring> Inspector<EvmWiringT> for PrecompileUsageInspector {
+    fn call(
+        &mut self,
+        context: &mut EvmContext<EvmWiringT>,
+        inputs: &mut CallInputs,
+    ) -> Option<CallOutcome> {
+        if context.precompiles.contains(&inputs.bytecode_address) {
+            *self.counts.entry(inputs.bytecode_address).or_default() += 1;
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::inspector_handle_register;
+    use database::InMemoryDB;
+    use revm::{
+        bytecode::{opcode, Bytecode},
+        primitives::address,
+        state::AccountInfo,
+        test_utils::call_evm_builder,
+    };
+
+    #[test]
+    fn test_precompile_usage_inspector() {
+        let contract = address!("00000000000000000000000000000000000000a0");
+        let ecrecover = Address::with_last_byte(1);
+        let identity = Address::with_last_byte(4);
+
+        // STATICCALL ecrecover and identity without input.
+        let mut code = vec![];
+        for target in [ecrecover, identity] {
+            code.extend_from_slice(&[
+                opcode::PUSH0,
+                opcode::PUSH0,
+                opcode::PUSH0,
+                opcode::PUSH0,
+                opcode::PUSH20,
+            ]);
+            code.extend_from_slice(target.as_slice());
+            code.extend_from_slice(&[opcode::GAS, opcode::STATICCALL, opcode::POP]);
+        }
+        code.push(opcode::STOP);
+
+        let mut db = InMemoryDB::default();
+        db.insert_account_info(
+            contract,
+            AccountInfo::from_bytecode(Bytecode::new_raw(code.into())),
+        );
+
+        let mut evm = call_evm_builder(db, PrecompileUsageInspector::default(), contract, 100_000)
+            .append_handler_register(inspector_handle_register)
+            .build();
+
+        assert!(evm.transact().unwrap().result.is_success());
+
+        let counts = evm.into_context().external.into_map();
+        assert_eq!(counts.len(), 2);
+        assert_eq!(counts[&ecrecover], 1);
+        assert_eq!(counts[&identity], 1);
+    }
+}
@@ -0,0 +1,122 @@
+//! Read before write inspector, finds storage slots loaded before the frame stored them.
+
+use crate::Inspector;
+use revm::{
+    bytecode::opcode,
+    interpreter::Interpreter,
+    primitives::{Address, HashSet, U256},
+    EvmContext, EvmWiring,
+};
+use std::vec::Vec;
+
+/// [Inspector] that records the storage slots a frame `SLOAD`s before it `SSTORE`s them.
+///
+/// Such reads see the value from before the frame, possibly an uninitialized slot. Each frame
+/// tracks its own stores, so a slot stored by the caller or by a finished call is still flagged.
+/// Slots are reported once, in the order of their first flagged read.
+#[derive(Clone, Debug, Default)]
+pub struct ReadBeforeWriteInspector {
+    /// Slots stored by each running frame, indexed by depth.
+    written: Vec<HashSet<U256>>,
+    flagged: HashSet<(Address, U256)>,
+    reads: Vec<(Address, U256)>,
+}
+
+impl ReadBeforeWriteInspector {
+    /// Returns the `(address, slot)` pairs loaded before being stored.
+    pub fn reads(&self) -> &[(Address, U256)] {
+        &self.reads
+    }
+}
+
+impl<EvmWiringT: EvmWiring> Inspector<EvmWiringT> for ReadBeforeWriteInspector {
+    fn initialize_interp(
+        &mut self,
+        _interp: &mut Interpreter,
+        context: &mut EvmContext<EvmWiringT>,
+    ) {
+        // Drops the stores of the previous frame at this depth.
+        let depth = context.journaled_state.depth() as usize;
+        self.written.truncate(depth);
+        self.written.resize_with(depth + 1, HashSet::default);
+    }
+
+    fn step(&mut self, interp: &mut Interpreter, context: &mut EvmContext<EvmWiringT>) {
+        let opcode = interp.current_opcode();
+        if opcode != opcode::SLOAD && opcode != opcode::SSTORE {
+            return;
+        }
+        let Ok(slot) = interp.stack.peek(0) else {
+            return;
+        };
+        let Some(written) = self
+            .written
+            .get_mut(context.journaled_state.depth() as usize)
+        else {
+            return;
+        };
+        if opcode == opcode::SSTORE {
+            written.insert(slot);
+            return;
+        }
+        let key = (interp.contract.target_address, slot);
+        if !written.contains(&slot) && self.flagged.insert(key) {
+            self.reads.push(key);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::inspector_handle_register;
+    use database::BenchmarkDB;
+    use revm::{bytecode::Bytecode, primitives::Bytes, test_utils::call_evm_builder};
+
+    fn reads(code: &'static [u8]) -> Vec<(Address, U256)> {
+        let mut evm = call_evm_builder(
+            BenchmarkDB::new_bytecode(Bytecode::new_raw(Bytes::from_static(code))),
+            ReadBeforeWriteInspector::default(),
+            Address::ZERO,
+            100_000,
+        )
+        .append_handler_register(inspector_handle_register)
+        .build();
+        assert!(evm.transact().unwrap().result.is_success());
+        evm.into_context().external.reads().to_vec()
+    }
+
+    #[test]
+    fn test_read_before_write_inspector() {
+        // Loads slot 1, then stores and loads slot 2.
+        let flagged = reads(&[
+            opcode::PUSH1,
+            0x01,
+            opcode::SLOAD,
+            opcode::PUSH1,
+            0xaa,
+            opcode::PUSH1,
+            0x02,
+            opcode::SSTORE,
+            opcode::PUSH1,
+            0x02,
+            opcode::SLOAD,
+            opcode::STOP,
+        ]);
+        assert_eq!(flagged, [(Address::ZERO, U256::from(1))]);
+
+        // Stores and loads slot 1.
+        let flagged = reads(&[
+            opcode::PUSH1,
+            0xaa,
+            opcode::PUSH1,
+            0x01,
+            opcode::SSTORE,
+            opcode::PUSH1,
+            0x01,
+            opcode::SLOAD,
+            opcode::STOP,
+        ]);
+        assert!(flagged.is_empty());
+    }
+}
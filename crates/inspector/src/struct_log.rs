@@ -0,0 +1,195 @@
+//! Streaming struct log tracer, writes each executed step as a JSON line.
+
+use crate::Inspector;
+use revm::{
+    bytecode::opcode::OpCode,
+    interpreter::Interpreter,
+    primitives::{hex, U256},
+    EvmContext, EvmWiring,
+};
+use serde::Serialize;
+use std::io::{self, Write};
+
+/// A step written by [`StreamingStructLogTracer`], in the `structLogs` format of geth.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct StructLog<'a> {
+    pc: u64,
+    op: &'static str,
+    gas: u64,
+    gas_cost: u64,
+    depth: u64,
+    stack: &'a [U256],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    memory: Option<String>,
+    refund: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+/// [Inspector] that writes a struct log line for each executed instruction to a writer.
+///
+/// Unlike collecting the steps in memory, the trace of a transaction is not bounded by the
+/// available memory. Lines are not flushed, wrap the writer in a [`BufWriter`](std::io::BufWriter)
+/// when writing to a file. Tracing stops at the first write error, see [`Self::error`].
+#[derive(Debug)]
+pub struct StreamingStructLogTracer<W: Write> {
+    writer: W,
+    include_memory: bool,
+    steps: u64,
+    error: Option<io::Error>,
+
+    stack: Vec<U256>,
+    memory: Option<String>,
+    pc: usize,
+    opcode: u8,
+    gas: u64,
+    refund: u64,
+}
+
+impl<W: Write> StreamingStructLogTracer<W> {
+    /// Creates a tracer writing to `writer`.
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer,
+            include_memory: false,
+            steps: 0,
+            error: None,
+            stack: Vec::new(),
+            memory: None,
+            pc: 0,
+            opcode: 0,
+            gas: 0,
+            refund: 0,
+        }
+    }
+
+    /// Include the memory of the frame in each line. This significantly increases the output size.
+    pub fn with_memory(mut self) -> Self {
+        self.include_memory = true;
+        self
+    }
+
+    /// Returns the number of lines written.
+    pub fn steps(&self) -> u64 {
+        self.steps
+    }
+
+    /// Returns the write error that stopped the trace.
+    pub fn error(&self) -> Option<&io::Error> {
+        self.error.as_ref()
+    }
+
+    /// Returns the writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+
+    fn write_step(&mut self, step: &StructLog<'_>) -> io::Result<()> {
+        serde_json::to_writer(&mut self.writer, step)?;
+        self.writer.write_all(b"\n")
+    }
+}
+
+impl<EvmWiringT: EvmWiring, W: Write> Inspector<EvmWiringT> for StreamingStructLogTracer<W> {
+    fn step(&mut self, interp: &mut Interpreter, _context: &mut EvmContext<EvmWiringT>) {
+        if self.error.is_some() {
+            return;
+        }
+        self.stack.clone_from(interp.stack.data());
+        self.memory = self
+            .include_memory
+            .then(|| hex::encode_prefixed(interp.shared_memory.context_memory()));
+        self.pc = interp.program_counter();
+        self.opcode = interp.current_opcode();
+        self.gas = interp.gas.remaining();
+        self.refund = interp.gas.refunded().max(0) as u64;
+    }
+
+    fn step_end(&mut self, interp: &mut Interpreter, context: &mut EvmContext<EvmWiringT>) {
+        if self.error.is_some() {
+            return;
+        }
+        let stack = core::mem::take(&mut self.stack);
+        let step = StructLog {
+            pc: self.pc as u64,
+            op: OpCode::name_by_op(self.opcode),
+            gas: self.gas,
+            gas_cost: self.gas.saturating_sub(interp.gas.remaining()),
+            depth: context.journaled_state.depth(),
+            stack: &stack,
+            memory: self.memory.take(),
+            refund: self.refund,
+            error: (!interp.instruction_result.is_ok())
+                .then(|| format!("{:?}", interp.instruction_result)),
+        };
+        match self.write_step(&step) {
+            Ok(()) => self.steps += 1,
+            Err(error) => self.error = Some(error),
+        }
+        self.stack = stack;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{inspector_handle_register, inspectors::OpcodeTraceInspector};
+    use database::BenchmarkDB;
+    use revm::{
+        bytecode::{opcode, Bytecode},
+        primitives::{Address, Bytes},
+        test_utils::call_evm_builder,
+        wiring::EthereumWiring,
+    };
+
+    #[test]
+    fn test_streaming_struct_log_tracer() {
+        // Loops three times, counting down from 3.
+        let code = Bytecode::new_raw(Bytes::from_static(&[
+            opcode::PUSH1,
+            0x03,
+            opcode::JUMPDEST,
+            opcode::PUSH1,
+            0x01,
+            opcode::SWAP1,
+            opcode::SUB,
+            opcode::DUP1,
+            opcode::PUSH1,
+            0x02,
+            opcode::JUMPI,
+            opcode::STOP,
+        ]));
+
+        fn transact<I: core::fmt::Debug + Inspector<EthereumWiring<BenchmarkDB, I>>>(
+            code: Bytecode,
+            inspector: I,
+        ) -> I {
+            let mut evm = call_evm_builder(
+                BenchmarkDB::new_bytecode(code),
+                inspector,
+                Address::ZERO,
+                100_000,
+            )
+            .append_handler_register(inspector_handle_register)
+            .build();
+            assert!(evm.transact().unwrap().result.is_success());
+            evm.into_context().external
+        }
+
+        let tracer = transact(code.clone(), StreamingStructLogTracer::new(Vec::new()));
+        let trace = transact(code, OpcodeTraceInspector::default());
+
+        assert!(tracer.error().is_none());
+        assert_eq!(tracer.steps(), trace.trace().len() as u64);
+        let output = String::from_utf8(tracer.into_inner()).unwrap();
+        assert_eq!(output.lines().count(), trace.trace().len());
+
+        let first: serde_json::Value =
+            serde_json::from_str(output.lines().next().unwrap()).unwrap();
+        assert_eq!(first["pc"], 0);
+        assert_eq!(first["op"], "PUSH1");
+        assert_eq!(first["gasCost"], 3);
+        assert_eq!(first["stack"], serde_json::json!([]));
+    }
+}
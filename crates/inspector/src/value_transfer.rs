@@ -0,0 +1,215 @@
+//! Value transfer inspector, records ether moved between accounts.
+
+use crate::Inspector;
+use revm::{
+    interpreter::{
+        CallInputs, CallOutcome, CreateInputs, CreateOutcome, EOFCreateInputs, InterpreterResult,
+    },
+    primitives::{Address, U256},
+    EvmContext, EvmWiring,
+};
+use std::vec::Vec;
+
+/// [Inspector] that records every value transfer of a transaction.
+///
+/// Call values, create endowments and self-destructed balances are recorded in execution order.
+/// Transfers of frames that did not succeed, including those of nested frames, are dropped.
+/// Value sent by an account to itself, e.g. with `CALLCODE`, does not move ether and is not
+/// recorded.
+#[derive(Clone, Debug, Default)]
+pub struct ValueTransferInspector {
+    transfers: Vec<(Address, Address, U256)>,
+    /// Length of `transfers` at the start of each open frame and whether the frame itself
+    /// transferred value.
+    checkpoints: Vec<(usize, bool)>,
+}
+
+impl ValueTransferInspector {
+    /// Returns the recorded transfers as `(from, to, value)`.
+    pub fn transfers(&self) -> &[(Address, Address, U256)] {
+        &self.transfers
+    }
+
+    fn frame_start(&mut self, from: Address, to: Address, value: U256) {
+        self.checkpoints
+            .push((self.transfers.len(), !value.is_zero()));
+        if !value.is_zero() {
+            self.transfers.push((from, to, value));
+        }
+    }
+
+    fn frame_end(&mut self, result: &InterpreterResult, created_address: Option<Address>) {
+        let (checkpoint, transferred) = self.checkpoints.pop().unwrap_or_default();
+        if !result.is_ok() {
+            self.transfers.truncate(checkpoint);
+            return;
+        }
+        // Endowment target is only known once the create frame has finished.
+        if let (true, Some(address)) = (transferred, created_address) {
+            self.transfers[checkpoint].1 = address;
+        }
+    }
+}
+
+impl<EvmWiringT: EvmWiring> Inspector<EvmWiringT> for ValueTransferInspector {
+    fn call(
+        &mut self,
+        _context: &mut EvmContext<EvmWiringT>,
+        inputs: &mut CallInputs,
+    ) -> Option<CallOutcome> {
+        let value = if inputs.caller == inputs.target_address {
+            U256::ZERO
+        } else {
+            inputs.transfer_value().unwrap_or_default()
+        };
+        self.frame_start(inputs.caller, inputs.target_address, value);
+        None
+    }
+
+    fn call_end(
+        &mut self,
+        _context: &mut EvmContext<EvmWiringT>,
+        _inputs: &CallInputs,
+        outcome: CallOutcome,
+    ) -> CallOutcome {
+        self.frame_end(&outcome.result, None);
+        outcome
+    }
+
+    fn create(
+        &mut self,
+        _context: &mut EvmContext<EvmWiringT>,
+        inputs: &mut CreateInputs,
+    ) -> Option<CreateOutcome> {
+        self.frame_start(inputs.caller, Address::ZERO, inputs.value);
+        None
+    }
+
+    fn create_end(
+        &mut self,
+        _context: &mut EvmContext<EvmWiringT>,
+        _inputs: &CreateInputs,
+        outcome: CreateOutcome,
+    ) -> CreateOutcome {
+        self.frame_end(&outcome.result, outcome.address);
+        outcome
+    }
+
+    fn eofcreate(
+        &mut self,
+        _context: &mut EvmContext<EvmWiringT>,
+        inputs: &mut EOFCreateInputs,
+    ) -> Option<CreateOutcome> {
+        self.frame_start(inputs.caller, Address::ZERO, inputs.value);
+        None
+    }
+
+    fn eofcreate_end(
+        &mut self,
+        _context: &mut EvmContext<EvmWiringT>,
+        _inputs: &EOFCreateInputs,
+        outcome: CreateOutcome,
+    ) -> CreateOutcome {
+        self.frame_end(&outcome.result, outcome.address);
+        outcome
+    }
+
+    fn selfdestruct(&mut self, contract: Address, target: Address, value: U256) {
+        if !value.is_zero() && contract != target {
+            self.transfers.push((contract, target, value));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::inspector_handle_register;
+    use database::InMemoryDB;
+    use revm::{
+        bytecode::{opcode, Bytecode},
+        primitives::{address, Bytes},
+        state::AccountInfo,
+        test_utils::{call_evm_builder, TEST_CALLER},
+    };
+
+    const CONTRACT: Address = address!("00000000000000000000000000000000000000a0");
+    const CALLEE: Address = address!("00000000000000000000000000000000000000a1");
+
+    /// Calls `CALLEE` with `call` with value 7 and with zero value, then stops or reverts.
+    fn transact(call: u8, revert: bool) -> ValueTransferInspector {
+        let mut code = Vec::new();
+        for value in [7, 0] {
+            code.extend_from_slice(&[
+                opcode::PUSH1,
+                0x00,
+                opcode::PUSH1,
+                0x00,
+                opcode::PUSH1,
+                0x00,
+                opcode::PUSH1,
+                0x00,
+                opcode::PUSH1,
+                value,
+                opcode::PUSH20,
+            ]);
+            code.extend_from_slice(CALLEE.as_slice());
+            code.extend_from_slice(&[opcode::GAS, call, opcode::POP]);
+        }
+        if revert {
+            code.extend_from_slice(&[opcode::PUSH1, 0x00, opcode::PUSH1, 0x00, opcode::REVERT]);
+        } else {
+            code.push(opcode::STOP);
+        }
+
+        let mut db = InMemoryDB::default();
+        db.insert_account_info(TEST_CALLER, AccountInfo::from_balance(U256::from(1_000)));
+        db.insert_account_info(
+            CONTRACT,
+            AccountInfo::from_bytecode(Bytecode::new_raw(code.into())),
+        );
+        db.insert_account_info(
+            CALLEE,
+            AccountInfo::from_bytecode(Bytecode::new_raw(Bytes::from_static(&[opcode::STOP]))),
+        );
+
+        let mut evm = call_evm_builder(db, ValueTransferInspector::default(), CONTRACT, 100_000)
+            .modify_tx_env(|tx| {
+                tx.value = U256::from(100);
+            })
+            .append_handler_register(inspector_handle_register)
+            .build();
+
+        let result = evm.transact().unwrap().result;
+        assert_eq!(result.is_success(), !revert);
+        evm.into_context().external
+    }
+
+    #[test]
+    fn test_value_transfer() {
+        let inspector = transact(opcode::CALL, false);
+        assert_eq!(
+            inspector.transfers(),
+            &[
+                (TEST_CALLER, CONTRACT, U256::from(100)),
+                (CONTRACT, CALLEE, U256::from(7)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_value_transfer_reverted() {
+        let inspector = transact(opcode::CALL, true);
+        assert!(inspector.transfers().is_empty());
+    }
+
+    #[test]
+    fn test_value_transfer_callcode() {
+        // The value stays in the contract, running the code of the callee.
+        let inspector = transact(opcode::CALLCODE, false);
+        assert_eq!(
+            inspector.transfers(),
+            &[(TEST_CALLER, CONTRACT, U256::from(100))]
+        );
+    }
+}
@@ -6,6 +6,8 @@ mod constants;
 pub use calc::*;
 pub use constants::*;
 
+use core::num::NonZeroU64;
+
 /// Represents the state of gas during execution.
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -16,6 +18,9 @@ pub struct Gas {
     remaining: u64,
     /// Refunded gas. This is used only at the end of execution.
     refunded: i64,
+    /// The last cost that exceeded the remaining gas, zero if none did.
+    #[cfg_attr(feature = "serde", serde(default))]
+    rejected_cost: u64,
 }
 
 impl Gas {
@@ -26,6 +31,7 @@ impl Gas {
             limit,
             remaining: limit,
             refunded: 0,
+            rejected_cost: 0,
         }
     }
 
@@ -36,6 +42,7 @@ impl Gas {
             limit,
             remaining: 0,
             refunded: 0,
+            rejected_cost: 0,
         }
     }
 
@@ -72,6 +79,17 @@ impl Gas {
         self.remaining
     }
 
+    /// Returns the last cost that [`Self::record_cost`] failed to record as it exceeded the
+    /// remaining gas.
+    #[inline]
+    pub const fn rejected_cost(&self) -> Option<u64> {
+        if self.rejected_cost == 0 {
+            None
+        } else {
+            Some(self.rejected_cost)
+        }
+    }
+
     /// Return remaining gas after subtracting 63/64 parts.
     pub const fn remaining_63_of_64_parts(&self) -> u64 {
         self.remaining - self.remaining / 64
@@ -106,6 +124,14 @@ impl Gas {
     #[inline]
     pub fn set_final_refund(&mut self, is_london: bool) {
         let max_refund_quotient = if is_london { 5 } else { 2 };
+        self.set_final_refund_with_quotient(NonZeroU64::new(max_refund_quotient).unwrap());
+    }
+
+    /// Set final refund, capped at `gas_spent / max_refund_quotient`.
+    ///
+    /// Used instead of [`Self::set_final_refund`] when the quotient is configured.
+    #[inline]
+    pub fn set_final_refund_with_quotient(&mut self, max_refund_quotient: NonZeroU64) {
         self.refunded = (self.refunded() as u64).min(self.spent() / max_refund_quotient) as i64;
     }
 
@@ -117,7 +143,8 @@ impl Gas {
 
     /// Records an explicit cost.
     ///
-    /// Returns `false` if the gas limit is exceeded.
+    /// Returns `false` if the gas limit is exceeded, the cost is then kept as
+    /// [`Self::rejected_cost`].
     #[inline]
     #[must_use = "prefer using `gas!` instead to return an out-of-gas error on failure"]
     pub fn record_cost(&mut self, cost: u64) -> bool {
@@ -125,6 +152,8 @@ impl Gas {
         let success = !overflow;
         if success {
             self.remaining = remaining;
+        } else {
+            self.rejected_cost = cost;
         }
         success
     }
@@ -15,13 +15,21 @@ macro_rules! tri {
 }
 
 /// `SSTORE` opcode refund calculation.
-#[allow(clippy::collapsible_else_if)]
 #[inline]
 pub fn sstore_refund(spec_id: SpecId, vals: &SStoreResult) -> i64 {
+    custom_sstore_refund(spec_id, vals, COLD_SLOAD_COST, WARM_STORAGE_READ_COST)
+}
+
+/// `SSTORE` opcode refund calculation with the given EIP-2929 cold and warm `SLOAD` costs.
+///
+/// The costs are only used since Berlin.
+#[allow(clippy::collapsible_else_if)]
+#[inline]
+pub fn custom_sstore_refund(spec_id: SpecId, vals: &SStoreResult, cold: u64, warm: u64) -> i64 {
     if spec_id.is_enabled_in(SpecId::ISTANBUL) {
         // EIP-3529: Reduction in refunds
         let sstore_clears_schedule = if spec_id.is_enabled_in(SpecId::LONDON) {
-            (SSTORE_RESET - COLD_SLOAD_COST + ACCESS_LIST_STORAGE_KEY) as i64
+            (SSTORE_RESET.saturating_sub(cold) + access_list_storage_key_cost(cold)) as i64
         } else {
             REFUND_SSTORE_CLEARS
         };
@@ -43,14 +51,14 @@ pub fn sstore_refund(spec_id: SpecId, vals: &SStoreResult) -> i64 {
 
                 if vals.is_original_eq_new() {
                     let (gas_sstore_reset, gas_sload) = if spec_id.is_enabled_in(SpecId::BERLIN) {
-                        (SSTORE_RESET - COLD_SLOAD_COST, WARM_STORAGE_READ_COST)
+                        (SSTORE_RESET.saturating_sub(cold), warm)
                     } else {
                         (SSTORE_RESET, sload_cost(spec_id, false))
                     };
                     if vals.is_original_zero() {
-                        refund += (SSTORE_SET - gas_sload) as i64;
+                        refund += SSTORE_SET as i64 - gas_sload as i64;
                     } else {
-                        refund += (gas_sstore_reset - gas_sload) as i64;
+                        refund += gas_sstore_reset as i64 - gas_sload as i64;
                     }
                 }
 
@@ -147,7 +155,13 @@ pub const fn log_cost(n: u8, len: u64) -> Option<u64> {
 /// `KECCAK256` opcode cost calculation.
 #[inline]
 pub const fn keccak256_cost(len: u64) -> Option<u64> {
-    KECCAK256.checked_add(tri!(cost_per_word(len, KECCAK256WORD)))
+    custom_keccak256_cost(len, KECCAK256, KECCAK256WORD)
+}
+
+/// `KECCAK256` opcode cost calculation with the given base and per word costs.
+#[inline]
+pub const fn custom_keccak256_cost(len: u64, base: u64, per_word: u64) -> Option<u64> {
+    base.checked_add(tri!(cost_per_word(len, per_word)))
 }
 
 /// Calculate the cost of buffer per word.
@@ -172,11 +186,19 @@ pub const fn initcode_cost(len: u64) -> u64 {
 /// `SLOAD` opcode cost calculation.
 #[inline]
 pub const fn sload_cost(spec_id: SpecId, is_cold: bool) -> u64 {
+    custom_sload_cost(spec_id, is_cold, COLD_SLOAD_COST, WARM_STORAGE_READ_COST)
+}
+
+/// `SLOAD` opcode cost calculation with the given EIP-2929 cold and warm costs.
+///
+/// The costs are only used since Berlin, before it `SLOAD` has a single cost.
+#[inline]
+pub const fn custom_sload_cost(spec_id: SpecId, is_cold: bool, cold: u64, warm: u64) -> u64 {
     if spec_id.is_enabled_in(SpecId::BERLIN) {
         if is_cold {
-            COLD_SLOAD_COST
+            cold
         } else {
-            WARM_STORAGE_READ_COST
+            warm
         }
     } else if spec_id.is_enabled_in(SpecId::ISTANBUL) {
         // EIP-1884: Repricing for trie-size-dependent opcodes
@@ -192,17 +214,38 @@ pub const fn sload_cost(spec_id: SpecId, is_cold: bool) -> u64 {
 /// `SSTORE` opcode cost calculation.
 #[inline]
 pub fn sstore_cost(spec_id: SpecId, vals: &SStoreResult, is_cold: bool) -> u64 {
+    custom_sstore_cost(
+        spec_id,
+        vals,
+        is_cold,
+        COLD_SLOAD_COST,
+        WARM_STORAGE_READ_COST,
+    )
+}
+
+/// `SSTORE` opcode cost calculation with the given EIP-2929 cold and warm `SLOAD` costs.
+///
+/// The costs are only used since Berlin: the cold cost is the cold surcharge and is deducted from
+/// the reset cost, the warm cost is charged for no-op and dirty writes.
+#[inline]
+pub fn custom_sstore_cost(
+    spec_id: SpecId,
+    vals: &SStoreResult,
+    is_cold: bool,
+    cold: u64,
+    warm: u64,
+) -> u64 {
     if spec_id.is_enabled_in(SpecId::BERLIN) {
         // Berlin specification logic
-        let mut gas_cost = istanbul_sstore_cost::<WARM_STORAGE_READ_COST, WARM_SSTORE_RESET>(vals);
+        let mut gas_cost = istanbul_sstore_cost(vals, warm, SSTORE_RESET.saturating_sub(cold));
 
         if is_cold {
-            gas_cost += COLD_SLOAD_COST;
+            gas_cost += cold;
         }
         gas_cost
     } else if spec_id.is_enabled_in(SpecId::ISTANBUL) {
         // Istanbul logic
-        istanbul_sstore_cost::<ISTANBUL_SLOAD_GAS, SSTORE_RESET>(vals)
+        istanbul_sstore_cost(vals, ISTANBUL_SLOAD_GAS, SSTORE_RESET)
     } else {
         // Frontier logic
         frontier_sstore_cost(vals)
@@ -211,17 +254,15 @@ pub fn sstore_cost(spec_id: SpecId, vals: &SStoreResult, is_cold: bool) -> u64 {
 
 /// EIP-2200: Structured Definitions for Net Gas Metering
 #[inline]
-fn istanbul_sstore_cost<const SLOAD_GAS: u64, const SSTORE_RESET_GAS: u64>(
-    vals: &SStoreResult,
-) -> u64 {
+fn istanbul_sstore_cost(vals: &SStoreResult, sload_gas: u64, sstore_reset_gas: u64) -> u64 {
     if vals.is_new_eq_present() {
-        SLOAD_GAS
+        sload_gas
     } else if vals.is_original_eq_present() && vals.is_original_zero() {
         SSTORE_SET
     } else if vals.is_original_eq_present() {
-        SSTORE_RESET_GAS
+        sstore_reset_gas
     } else {
-        SLOAD_GAS
+        sload_gas
     }
 }
 
@@ -235,6 +276,20 @@ fn frontier_sstore_cost(vals: &SStoreResult) -> u64 {
     }
 }
 
+/// `PAY` opcode cost of the EIP-5920 draft: the Berlin account access cost, plus the value
+/// transfer and new account costs if value is transferred.
+#[inline]
+pub const fn pay_cost(transfers_value: bool, account_load: AccountLoad) -> u64 {
+    let mut gas = warm_cold_cost(account_load.load.state_load.is_cold);
+    if transfers_value {
+        gas += CALLVALUE;
+        if account_load.is_empty {
+            gas += NEWACCOUNT;
+        }
+    }
+    gas
+}
+
 /// `SELFDESTRUCT` opcode cost calculation.
 #[inline]
 pub const fn selfdestruct_cost(spec_id: SpecId, res: StateLoad<SelfDestructResult>) -> u64 {
@@ -356,6 +411,15 @@ pub const fn memory_gas(num_words: u64) -> u64 {
         .saturating_add(num_words.saturating_mul(num_words) / 512)
 }
 
+/// EIP-2930 intrinsic cost of an access list storage key given the cold `SLOAD` cost.
+///
+/// Warming a slot through the access list stays 200 gas cheaper than a cold `SLOAD`, which gives
+/// [`ACCESS_LIST_STORAGE_KEY`] for the EIP-2929 cold cost.
+#[inline]
+pub const fn access_list_storage_key_cost(cold: u64) -> u64 {
+    cold.saturating_sub(COLD_SLOAD_COST - ACCESS_LIST_STORAGE_KEY)
+}
+
 /// Initial gas that is deducted for transaction to be included.
 /// Initial gas contains initial stipend gas, gas for access list and input data.
 pub fn validate_initial_tx_gas<AccessListT: AccessListTrait>(
@@ -364,6 +428,26 @@ pub fn validate_initial_tx_gas<AccessListT: AccessListTrait>(
     is_create: bool,
     access_list: Option<&AccessListT>,
     authorization_list_num: u64,
+) -> u64 {
+    custom_initial_tx_gas(
+        spec_id,
+        input,
+        is_create,
+        access_list,
+        authorization_list_num,
+        ACCESS_LIST_STORAGE_KEY,
+    )
+}
+
+/// Initial gas that is deducted for transaction to be included, charging
+/// `access_list_storage_key` for each storage key of the access list.
+pub fn custom_initial_tx_gas<AccessListT: AccessListTrait>(
+    spec_id: SpecId,
+    input: &[u8],
+    is_create: bool,
+    access_list: Option<&AccessListT>,
+    authorization_list_num: u64,
+    access_list_storage_key: u64,
 ) -> u64 {
     let mut initial_gas = 0;
     let zero_data_len = input.iter().filter(|v| **v == 0).count() as u64;
@@ -383,7 +467,7 @@ pub fn validate_initial_tx_gas<AccessListT: AccessListTrait>(
     if let Some(access_list) = access_list {
         let (account_num, storage_num) = access_list.num_account_storages();
         initial_gas += account_num as u64 * ACCESS_LIST_ADDRESS;
-        initial_gas += storage_num as u64 * ACCESS_LIST_STORAGE_KEY;
+        initial_gas += storage_num as u64 * access_list_storage_key;
     }
 
     // base stipend
@@ -1,3 +1,4 @@
+use crate::{CallInputs, InstructionResult};
 use core::ops::{Deref, DerefMut};
 use primitives::{Address, Bytes, Log, B256, U256};
 
@@ -19,6 +20,16 @@ pub trait Host {
     /// Load an account code.
     fn load_account_delegated(&mut self, address: Address) -> Option<AccountLoad>;
 
+    /// Load an account without following its EIP-7702 delegation.
+    ///
+    /// The returned load never has a delegate. By default it loads the account with
+    /// [`Host::load_account_delegated`] and drops the delegate load.
+    fn load_account(&mut self, address: Address) -> Option<AccountLoad> {
+        let mut account_load = self.load_account_delegated(address)?;
+        account_load.load.is_delegate_account_cold = None;
+        Some(account_load)
+    }
+
     /// Get the block hash of the given block `number`.
     fn block_hash(&mut self, number: u64) -> Option<B256>;
 
@@ -31,6 +42,15 @@ pub trait Host {
     /// Get code hash of `address` and if the account is cold.
     fn code_hash(&mut self, address: Address) -> Option<Eip7702CodeLoad<B256>>;
 
+    /// Get the address the code of `address` is delegated to with EIP-7702.
+    ///
+    /// Returns `None` for accounts without a delegation designator. Loads the account, a failed
+    /// load also returns `None` and is reported like the other loads of the host.
+    fn delegation_target(&mut self, address: Address) -> Option<Address> {
+        let _ = address;
+        None
+    }
+
     /// Get storage value of `address` at `index` and if the account is cold.
     fn sload(&mut self, address: Address, index: U256) -> Option<StateLoad<U256>>;
 
@@ -59,6 +79,46 @@ pub trait Host {
         address: Address,
         target: Address,
     ) -> Option<StateLoad<SelfDestructResult>>;
+
+    /// Returns `true` if accessing `address` would be warm as defined by EIP-2929, e.g. because
+    /// it is in the access list or was already accessed.
+    ///
+    /// Does not load the account. Hosts that do not track accessed accounts report them cold.
+    fn is_address_warm(&self, address: Address) -> bool {
+        let _ = address;
+        false
+    }
+
+    /// Returns `true` if accessing the storage `index` of `address` would be warm as defined by
+    /// EIP-2929.
+    ///
+    /// Does not load the slot. Hosts that do not track accessed slots report them cold.
+    fn is_storage_warm(&self, address: Address, index: U256) -> bool {
+        let _ = (address, index);
+        false
+    }
+
+    /// Transfers `value` from `from` to `to` without a call, for the EIP-5920 `PAY` instruction.
+    ///
+    /// Returns [`TransferError::OutOfFunds`] if the balance of `from` does not cover `value`.
+    /// Hosts that do not track balances fail with [`TransferError::NotActivated`].
+    fn transfer(&mut self, from: Address, to: Address, value: U256) -> Result<(), TransferError> {
+        let _ = (from, to, value);
+        Err(TransferError::NotActivated)
+    }
+
+    /// Returns `true` if the call is serviced by the host instead of a new frame.
+    ///
+    /// Checked by interpreter drivers on the call [`Interpreter::run`](crate::Interpreter::run)
+    /// returns. The host services it and execution resumes by inserting its
+    /// [`CallOutcome`](crate::CallOutcome) with
+    /// [`Interpreter::insert_call_outcome`](crate::Interpreter::insert_call_outcome) and calling
+    /// `run` again. The gas limit of the inputs was already taken from the interpreter, the
+    /// outcome gives back its remaining gas. The `Evm` frame loop runs every call as a frame.
+    fn is_external_call(&mut self, inputs: &CallInputs) -> bool {
+        let _ = inputs;
+        false
+    }
 }
 
 /// Represents the result of an `sstore` operation.
@@ -257,6 +317,32 @@ pub struct SelfDestructResult {
     pub previously_destroyed: bool,
 }
 
+/// Error of a [`Host::transfer`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TransferError {
+    /// The balance of the sender does not cover the value.
+    OutOfFunds,
+    /// The balance of the recipient would overflow.
+    OverflowPayment,
+    /// The host does not support transfers.
+    NotActivated,
+    /// Fatal database error, stored in the host.
+    FatalExternalError,
+}
+
+impl From<TransferError> for InstructionResult {
+    #[inline]
+    fn from(error: TransferError) -> Self {
+        match error {
+            TransferError::OutOfFunds => Self::OutOfFunds,
+            TransferError::OverflowPayment => Self::OverflowPayment,
+            TransferError::NotActivated => Self::NotActivated,
+            TransferError::FatalExternalError => Self::FatalExternalError,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use database_interface::EmptyDB;
@@ -216,6 +216,11 @@ pub const fn instruction_table<H: Host + ?Sized, SPEC: Spec>() -> [crate::table:
     table[REVERT as usize] = control::revert::<H, SPEC>;
     table[INVALID as usize] = control::invalid;
     table[SELFDESTRUCT as usize] = host::selfdestruct::<H, SPEC>;
+
+    #[cfg(feature = "eip5920")]
+    {
+        table[host::PAY as usize] = host::pay;
+    }
     table
 }
 
@@ -235,8 +240,9 @@ mod tests {
 
         let unknown_istr = instr_table[unknown_instruction];
         for (i, instr) in instr_table.iter().enumerate() {
-            let is_opcode_unknown = OpCode::new(i as u8).is_none();
-            let is_instr_unknown = *instr == unknown_istr;
+            let is_opcode_unknown = OpCode::new(i as u8).is_none()
+                && !(cfg!(feature = "eip5920") && i == host::PAY as usize);
+            let is_instr_unknown = std::ptr::fn_addr_eq(*instr, unknown_istr);
             assert_eq!(
                 is_instr_unknown, is_opcode_unknown,
                 "Opcode 0x{:X?} is not handled",
@@ -1,6 +1,9 @@
 use crate::{gas, Host, Interpreter};
 use primitives::U256;
-use specification::hardfork::{Spec, SpecId::*};
+use specification::{
+    eip4844::MIN_BLOB_GASPRICE,
+    hardfork::{Spec, SpecId::*},
+};
 use wiring::Block;
 
 /// EIP-1344: ChainID opcode
@@ -43,15 +46,98 @@ pub fn gaslimit<H: Host + ?Sized>(interpreter: &mut Interpreter, host: &mut H) {
 pub fn basefee<H: Host + ?Sized, SPEC: Spec>(interpreter: &mut Interpreter, host: &mut H) {
     check!(interpreter, LONDON);
     gas!(interpreter, gas::BASE);
-    push!(interpreter, *host.env().block.basefee());
+    push!(interpreter, host.env().effective_basefee());
 }
 
 /// EIP-7516: BLOBBASEFEE opcode
+///
+/// Blocks without `excess_blob_gas` report the minimum blob base fee.
 pub fn blob_basefee<H: Host + ?Sized, SPEC: Spec>(interpreter: &mut Interpreter, host: &mut H) {
     check!(interpreter, CANCUN);
     gas!(interpreter, gas::BASE);
     push!(
         interpreter,
-        U256::from(host.env().block.blob_gasprice().unwrap_or_default())
+        U256::from(
+            host.env()
+                .block
+                .blob_gasprice()
+                .unwrap_or(MIN_BLOB_GASPRICE as u128)
+        )
     );
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Contract, DummyHost, InstructionResult};
+    use specification::hardfork::{BerlinSpec, CancunSpec, LondonSpec, ShanghaiSpec};
+    use wiring::{
+        default::{Env, EnvWiring},
+        DefaultEthereumWiring,
+    };
+
+    #[test]
+    fn test_basefee() {
+        let mut env = EnvWiring::<DefaultEthereumWiring>::default();
+        env.block.basefee = U256::from(7);
+        let mut host = DummyHost::<DefaultEthereumWiring>::new(env);
+        let mut interpreter = Interpreter::new(Contract::default(), u64::MAX, false);
+
+        basefee::<_, LondonSpec>(&mut interpreter, &mut host);
+        assert_eq!(interpreter.instruction_result, InstructionResult::Continue);
+        assert_eq!(interpreter.stack.pop(), Ok(U256::from(7)));
+        assert_eq!(interpreter.gas.spent(), gas::BASE);
+    }
+
+    #[test]
+    fn test_basefee_before_london() {
+        let mut env = EnvWiring::<DefaultEthereumWiring>::default();
+        env.block.basefee = U256::from(7);
+        let mut host = DummyHost::<DefaultEthereumWiring>::new(env);
+        let mut interpreter = Interpreter::new(Contract::default(), u64::MAX, false);
+
+        basefee::<_, BerlinSpec>(&mut interpreter, &mut host);
+        assert_eq!(
+            interpreter.instruction_result,
+            InstructionResult::NotActivated
+        );
+        assert!(interpreter.stack.is_empty());
+    }
+
+    #[test]
+    fn test_blob_basefee() {
+        let mut env = EnvWiring::<DefaultEthereumWiring>::default();
+        env.block.set_blob_excess_gas_and_price(10 * 1024 * 1024);
+        let mut host = DummyHost::<DefaultEthereumWiring>::new(env);
+        let mut interpreter = Interpreter::new(Contract::default(), u64::MAX, false);
+
+        blob_basefee::<_, CancunSpec>(&mut interpreter, &mut host);
+        assert_eq!(interpreter.instruction_result, InstructionResult::Continue);
+        assert_eq!(interpreter.stack.pop(), Ok(U256::from(23)));
+        assert_eq!(interpreter.gas.spent(), gas::BASE);
+    }
+
+    #[test]
+    fn test_blob_basefee_without_excess_blob_gas() {
+        let mut env = EnvWiring::<DefaultEthereumWiring>::default();
+        env.block.blob_excess_gas_and_price = None;
+        let mut host = DummyHost::<DefaultEthereumWiring>::new(env);
+        let mut interpreter = Interpreter::new(Contract::default(), u64::MAX, false);
+
+        blob_basefee::<_, CancunSpec>(&mut interpreter, &mut host);
+        assert_eq!(interpreter.stack.pop(), Ok(U256::from(MIN_BLOB_GASPRICE)));
+    }
+
+    #[test]
+    fn test_blob_basefee_before_cancun() {
+        let mut host = DummyHost::<DefaultEthereumWiring>::new(Env::default());
+        let mut interpreter = Interpreter::new(Contract::default(), u64::MAX, false);
+
+        blob_basefee::<_, ShanghaiSpec>(&mut interpreter, &mut host);
+        assert_eq!(
+            interpreter.instruction_result,
+            InstructionResult::NotActivated
+        );
+        assert!(interpreter.stack.is_empty());
+    }
+}
@@ -7,17 +7,17 @@ use crate::{
     interpreter::Interpreter,
     interpreter_action::NewFrameAction,
     CallInputs, CallScheme, CallValue, CreateInputs, EOFCreateInputs, Host, InstructionResult,
-    InterpreterAction, InterpreterResult, MAX_INITCODE_SIZE,
+    InterpreterAction, InterpreterResult,
 };
 use bytecode::eof::{Eof, EofHeader};
 use core::cmp::max;
-use primitives::{keccak256, Address, Bytes, B256, U256};
+use primitives::{Address, Bytes, B256, U256};
 use specification::hardfork::{BerlinSpec, Spec, SpecId::*};
 use std::boxed::Box;
 use wiring::default::CreateScheme;
 
 /// EOF Create instruction
-pub fn eofcreate<H: Host + ?Sized>(interpreter: &mut Interpreter, _host: &mut H) {
+pub fn eofcreate<H: Host + ?Sized>(interpreter: &mut Interpreter, host: &mut H) {
     require_eof!(interpreter);
     require_non_staticcall!(interpreter);
     gas!(interpreter, EOF_CREATE_GAS);
@@ -61,10 +61,12 @@ pub fn eofcreate<H: Host + ?Sized>(interpreter: &mut Interpreter, _host: &mut H)
         cost_per_word(sub_container.len() as u64, KECCAK256WORD)
     );
 
-    let created_address = interpreter
-        .contract
-        .target_address
-        .create2(salt.to_be_bytes(), keccak256(sub_container));
+    let hasher = host.env().cfg.hasher();
+    let created_address = hasher.create2_address(
+        interpreter.contract.target_address,
+        salt.to_be_bytes().into(),
+        hasher.hash(&sub_container),
+    );
 
     let gas_limit = interpreter.gas().remaining_63_of_64_parts();
     gas!(interpreter, gas_limit);
@@ -343,13 +345,7 @@ pub fn create<const IS_CREATE2: bool, H: Host + ?Sized, SPEC: Spec>(
         // EIP-3860: Limit and meter initcode
         if SPEC::enabled(SHANGHAI) {
             // Limit is set as double of max contract bytecode size
-            let max_initcode_size = host
-                .env()
-                .cfg
-                .limit_contract_code_size
-                .map(|limit| limit.saturating_mul(2))
-                .unwrap_or(MAX_INITCODE_SIZE);
-            if len > max_initcode_size {
+            if len > host.env().cfg.max_initcode_size() {
                 interpreter.instruction_result = InstructionResult::CreateInitCodeSizeLimit;
                 return;
             }
@@ -569,3 +565,68 @@ pub fn static_call<H: Host + ?Sized, SPEC: Spec>(interpreter: &mut Interpreter,
         })));
     interpreter.instruction_result = InstructionResult::CallOrCreate;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{table::make_instruction_table, DummyHost, Gas};
+    use bytecode::{
+        eof::{EofBody, TypesSection},
+        opcode::{CREATE, CREATE2, EOFCREATE, PUSH0, STOP},
+        Bytecode,
+    };
+    use specification::hardfork::PragueSpec;
+    use std::{sync::Arc, vec};
+    use wiring::DefaultEthereumWiring;
+
+    /// Runs the four pushes and the create instruction of `bytecode` in a static frame.
+    fn static_create(bytecode: Bytecode) -> InstructionResult {
+        let table = make_instruction_table::<DummyHost<DefaultEthereumWiring>, PragueSpec>();
+        let mut host = DummyHost::default();
+        let mut interp = Interpreter::new_bytecode(bytecode);
+        interp.gas = Gas::new(100_000);
+        interp.is_static = true;
+        for _ in 0..5 {
+            interp.step(&table, &mut host);
+        }
+        interp.instruction_result
+    }
+
+    #[test]
+    fn create_in_static_context() {
+        for create in [CREATE, CREATE2] {
+            let bytecode =
+                Bytecode::new_raw(Bytes::from(vec![PUSH0, PUSH0, PUSH0, PUSH0, create, STOP]));
+            assert_eq!(
+                static_create(bytecode),
+                InstructionResult::StateChangeDuringStaticCall
+            );
+        }
+    }
+
+    #[test]
+    fn eofcreate_in_static_context() {
+        let initcode = EofBody {
+            types_section: vec![TypesSection::new(0, 0x80, 0)],
+            code_section: vec![Bytes::from_static(&[STOP])],
+            container_section: vec![],
+            data_section: Bytes::new(),
+            is_data_filled: true,
+        }
+        .into_eof();
+        let eof = EofBody {
+            types_section: vec![TypesSection::new(0, 0x80, 4)],
+            code_section: vec![Bytes::from(vec![
+                PUSH0, PUSH0, PUSH0, PUSH0, EOFCREATE, 0, STOP,
+            ])],
+            container_section: vec![initcode.raw],
+            data_section: Bytes::new(),
+            is_data_filled: true,
+        }
+        .into_eof();
+        assert_eq!(
+            static_create(Bytecode::Eof(Arc::new(eof))),
+            InstructionResult::StateChangeDuringStaticCall
+        );
+    }
+}
@@ -4,9 +4,12 @@ use crate::{
     Host, InstructionResult,
 };
 use core::cmp::min;
-use primitives::{Bytes, Log, LogData, B256, U256};
+use primitives::{
+    Bytes, Log, LogData, B256, BLOCKHASH_SERVE_WINDOW, BLOCKHASH_STORAGE_ADDRESS, U256,
+};
 use specification::hardfork::{Spec, SpecId::*};
 use std::vec::Vec;
+use wiring::Block;
 
 pub fn balance<H: Host + ?Sized, SPEC: Spec>(interpreter: &mut Interpreter, host: &mut H) {
     pop_address!(interpreter, address);
@@ -111,6 +114,26 @@ pub fn blockhash<H: Host + ?Sized, SPEC: Spec>(interpreter: &mut Interpreter, ho
     pop_top!(interpreter, number);
 
     let number_u64 = as_u64_saturated!(number);
+
+    // EIP-2935: Serve historical block hashes from state
+    if SPEC::enabled(PRAGUE) {
+        let block_number = as_u64_saturated!(host.env().block.number());
+        let diff = block_number.saturating_sub(number_u64);
+        if diff == 0 || diff > BLOCKHASH_SERVE_WINDOW as u64 {
+            *number = U256::ZERO;
+            return;
+        }
+
+        let index = U256::from(number_u64 % BLOCKHASH_SERVE_WINDOW as u64);
+        let Some(hash) = host.sload(BLOCKHASH_STORAGE_ADDRESS, index) else {
+            interpreter.instruction_result = InstructionResult::FatalExternalError;
+            return;
+        };
+        gas!(interpreter, gas::sload_cost(SPEC::SPEC_ID, hash.is_cold));
+        *number = hash.data;
+        return;
+    }
+
     let Some(hash) = host.block_hash(number_u64) else {
         interpreter.instruction_result = InstructionResult::FatalExternalError;
         return;
@@ -124,12 +147,21 @@ pub fn sload<H: Host + ?Sized, SPEC: Spec>(interpreter: &mut Interpreter, host:
         interpreter.instruction_result = InstructionResult::FatalExternalError;
         return;
     };
-    gas!(interpreter, gas::sload_cost(SPEC::SPEC_ID, value.is_cold));
+    let cfg = &host.env().cfg;
+    gas!(
+        interpreter,
+        gas::custom_sload_cost(
+            SPEC::SPEC_ID,
+            value.is_cold,
+            cfg.sload_cold_gas().unwrap_or(gas::COLD_SLOAD_COST),
+            cfg.sload_warm_gas().unwrap_or(gas::WARM_STORAGE_READ_COST),
+        )
+    );
     *index = value.data;
 }
 
 pub fn sstore<H: Host + ?Sized, SPEC: Spec>(interpreter: &mut Interpreter, host: &mut H) {
-    require_non_staticcall!(interpreter);
+    require_non_staticcall!(interpreter, host);
 
     pop!(interpreter, index, value);
     let Some(state_load) = host.sstore(interpreter.contract.target_address, index, value) else {
@@ -142,13 +174,22 @@ pub fn sstore<H: Host + ?Sized, SPEC: Spec>(interpreter: &mut Interpreter, host:
         interpreter.instruction_result = InstructionResult::ReentrancySentryOOG;
         return;
     }
+    let cfg = &host.env().cfg;
+    let cold = cfg.sload_cold_gas().unwrap_or(gas::COLD_SLOAD_COST);
+    let warm = cfg.sload_warm_gas().unwrap_or(gas::WARM_STORAGE_READ_COST);
     gas!(
         interpreter,
-        gas::sstore_cost(SPEC::SPEC_ID, &state_load.data, state_load.is_cold)
+        gas::custom_sstore_cost(
+            SPEC::SPEC_ID,
+            &state_load.data,
+            state_load.is_cold,
+            cold,
+            warm
+        )
     );
     refund!(
         interpreter,
-        gas::sstore_refund(SPEC::SPEC_ID, &state_load.data)
+        gas::custom_sstore_refund(SPEC::SPEC_ID, &state_load.data, cold, warm)
     );
 }
 
@@ -156,7 +197,7 @@ pub fn sstore<H: Host + ?Sized, SPEC: Spec>(interpreter: &mut Interpreter, host:
 /// Store value to transient storage
 pub fn tstore<H: Host + ?Sized, SPEC: Spec>(interpreter: &mut Interpreter, host: &mut H) {
     check!(interpreter, CANCUN);
-    require_non_staticcall!(interpreter);
+    require_non_staticcall!(interpreter, host);
     gas!(interpreter, gas::WARM_STORAGE_READ_COST);
 
     pop!(interpreter, index, value);
@@ -176,7 +217,7 @@ pub fn tload<H: Host + ?Sized, SPEC: Spec>(interpreter: &mut Interpreter, host:
 }
 
 pub fn log<const N: usize, H: Host + ?Sized>(interpreter: &mut Interpreter, host: &mut H) {
-    require_non_staticcall!(interpreter);
+    require_non_staticcall!(interpreter, host);
 
     pop!(interpreter, offset, len);
     let len = as_usize_or_fail!(interpreter, len);
@@ -208,8 +249,37 @@ pub fn log<const N: usize, H: Host + ?Sized>(interpreter: &mut Interpreter, host
     host.log(log);
 }
 
+/// The `PAY` opcode of the EIP-5920 draft, at a slot unused by mainnet.
+pub const PAY: u8 = 0xFC;
+
+/// EIP-5920 draft: transfers value from the current contract to `target` without a call, so no
+/// code of `target` is executed. Enabled by the `eip5920` feature, in legacy bytecode only.
+///
+/// The frame fails with `OutOfFunds` if its balance does not cover the value.
+pub fn pay<H: Host + ?Sized>(interpreter: &mut Interpreter, host: &mut H) {
+    pop_address!(interpreter, target);
+    pop!(interpreter, value);
+    let transfers_value = !value.is_zero();
+    if transfers_value {
+        require_non_staticcall!(interpreter, host);
+    }
+
+    let Some(account_load) = host.load_account(target) else {
+        interpreter.instruction_result = InstructionResult::FatalExternalError;
+        return;
+    };
+    gas!(interpreter, gas::pay_cost(transfers_value, account_load));
+    if !transfers_value {
+        return;
+    }
+
+    if let Err(error) = host.transfer(interpreter.contract.target_address, target, value) {
+        interpreter.instruction_result = error.into();
+    }
+}
+
 pub fn selfdestruct<H: Host + ?Sized, SPEC: Spec>(interpreter: &mut Interpreter, host: &mut H) {
-    require_non_staticcall!(interpreter);
+    require_non_staticcall!(interpreter, host);
     pop_address!(interpreter, target);
 
     let Some(res) = host.selfdestruct(interpreter.contract.target_address, target) else {
@@ -225,3 +295,102 @@ pub fn selfdestruct<H: Host + ?Sized, SPEC: Spec>(interpreter: &mut Interpreter,
 
     interpreter.instruction_result = InstructionResult::SelfDestruct;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Contract, DummyHost};
+    use specification::hardfork::{CancunSpec, PragueSpec};
+    use wiring::{default::EnvWiring, DefaultEthereumWiring};
+
+    fn host_with_history(block_number: u64, stored: U256) -> DummyHost<DefaultEthereumWiring> {
+        let mut env = EnvWiring::<DefaultEthereumWiring>::default();
+        env.block.number = U256::from(block_number);
+        let mut host = DummyHost::<DefaultEthereumWiring>::new(env);
+        host.storage.insert(U256::from(900), stored);
+        host
+    }
+
+    #[test]
+    fn blockhash_from_history_storage() {
+        let stored = U256::from(0x1234);
+        let mut host = host_with_history(10_000, stored);
+        let mut interpreter = Interpreter::new(Contract::default(), u64::MAX, false);
+
+        // 9_092 % 8192 == 900
+        interpreter.stack.push(U256::from(9_092)).unwrap();
+        blockhash::<_, PragueSpec>(&mut interpreter, &mut host);
+        assert_eq!(interpreter.instruction_result, InstructionResult::Continue);
+        assert_eq!(interpreter.stack.pop(), Ok(stored));
+        // Dummy host reports seeded slots as warm.
+        assert_eq!(
+            interpreter.gas.spent(),
+            gas::BLOCKHASH + gas::WARM_STORAGE_READ_COST
+        );
+
+        // Blocks outside of the serve window and the current block are zero.
+        for number in [10_000, 10_000 - BLOCKHASH_SERVE_WINDOW as u64 - 1] {
+            interpreter.stack.push(U256::from(number)).unwrap();
+            blockhash::<_, PragueSpec>(&mut interpreter, &mut host);
+            assert_eq!(interpreter.stack.pop(), Ok(U256::ZERO));
+        }
+    }
+
+    #[test]
+    fn blockhash_before_prague() {
+        let mut host = host_with_history(10_000, U256::from(0x1234));
+        let mut interpreter = Interpreter::new(Contract::default(), u64::MAX, false);
+
+        interpreter.stack.push(U256::from(9_900)).unwrap();
+        blockhash::<_, CancunSpec>(&mut interpreter, &mut host);
+        assert_eq!(interpreter.instruction_result, InstructionResult::Continue);
+        // Dummy host block hashes are zero, history storage is not consulted.
+        assert_eq!(interpreter.stack.pop(), Ok(U256::ZERO));
+        assert_eq!(interpreter.gas.spent(), gas::BLOCKHASH);
+    }
+
+    #[test]
+    #[cfg(feature = "optional_sload_gas")]
+    fn sload_gas_override() {
+        let mut env = EnvWiring::<DefaultEthereumWiring>::default();
+        env.cfg.sload_cold_gas = Some(5_000);
+        env.cfg.sload_warm_gas = Some(7);
+        let mut host = DummyHost::<DefaultEthereumWiring>::new(env);
+        let mut interpreter = Interpreter::new(Contract::default(), u64::MAX, false);
+
+        // The dummy host reports the first load of a slot as cold.
+        interpreter.stack.push(U256::from(1)).unwrap();
+        sload::<_, CancunSpec>(&mut interpreter, &mut host);
+        assert_eq!(interpreter.gas.spent(), 5_000);
+
+        interpreter.stack.push(U256::from(1)).unwrap();
+        sload::<_, CancunSpec>(&mut interpreter, &mut host);
+        assert_eq!(interpreter.gas.spent(), 5_007);
+        assert_eq!(interpreter.instruction_result, InstructionResult::Continue);
+    }
+
+    #[test]
+    #[cfg(feature = "optional_sload_gas")]
+    fn sstore_gas_override() {
+        let mut env = EnvWiring::<DefaultEthereumWiring>::default();
+        env.cfg.sload_cold_gas = Some(5_000);
+        env.cfg.sload_warm_gas = Some(7);
+        let mut host = DummyHost::<DefaultEthereumWiring>::new(env);
+        let mut interpreter = Interpreter::new(Contract::default(), u64::MAX, false);
+        let mut store = |index: u64, value: u64| {
+            interpreter.stack.push(U256::from(value)).unwrap();
+            interpreter.stack.push(U256::from(index)).unwrap();
+            let spent = interpreter.gas.spent();
+            sstore::<_, CancunSpec>(&mut interpreter, &mut host);
+            assert_eq!(interpreter.instruction_result, InstructionResult::Continue);
+            (interpreter.gas.spent() - spent, interpreter.gas.refunded())
+        };
+
+        // The dummy host reports the first store to a slot as cold and original values as zero.
+        assert_eq!(store(1, 0), (5_000 + 7, 0));
+        assert_eq!(store(1, 0), (7, 0));
+        assert_eq!(store(2, 1), (5_000 + gas::SSTORE_SET, 0));
+        // Restoring the original value refunds the set cost minus the warm cost.
+        assert_eq!(store(2, 0), (7, (gas::SSTORE_SET - 7) as i64));
+    }
+}
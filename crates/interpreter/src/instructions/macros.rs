@@ -1,6 +1,9 @@
 //! Utility macros to help implementing opcode instruction functions.
 
 /// Fails the instruction if the current call is static.
+///
+/// When the host is passed, also fails if [`CfgEnv::read_only`](wiring::default::CfgEnv::read_only)
+/// is set.
 #[macro_export]
 macro_rules! require_non_staticcall {
     ($interp:expr) => {
@@ -9,6 +12,13 @@ macro_rules! require_non_staticcall {
             return;
         }
     };
+    ($interp:expr, $host:expr) => {
+        $crate::require_non_staticcall!($interp);
+        if $host.env().cfg.read_only {
+            $interp.instruction_result = $crate::InstructionResult::ReadOnlyViolation;
+            return;
+        }
+    };
 }
 
 /// Error if the current call is executing EOF.
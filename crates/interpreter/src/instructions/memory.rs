@@ -52,3 +52,92 @@ pub fn mcopy<H: Host + ?Sized, SPEC: Spec>(interpreter: &mut Interpreter, _host:
     // copy memory in place
     interpreter.shared_memory.copy(dst, src, len);
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{table::make_instruction_table, DummyHost, Gas, InstructionResult};
+    use bytecode::{opcode::MCOPY, Bytecode};
+    use specification::hardfork::{CancunSpec, ShanghaiSpec};
+    use wiring::DefaultEthereumWiring;
+
+    /// Interpreter running `MCOPY`s on 64 bytes of memory holding `0..64`.
+    fn mcopy_interpreter() -> Interpreter {
+        let mut interp = Interpreter::new_bytecode(Bytecode::LegacyRaw([MCOPY, MCOPY].into()));
+        interp.gas = Gas::new(10000);
+        interp.shared_memory.resize(64);
+        interp.shared_memory.set(0, &(0..64).collect::<Vec<u8>>());
+        interp
+    }
+
+    fn push_mcopy_args(interp: &mut Interpreter, dst: usize, src: usize, len: usize) {
+        interp.stack.push(U256::from(len)).unwrap();
+        interp.stack.push(U256::from(src)).unwrap();
+        interp.stack.push(U256::from(dst)).unwrap();
+    }
+
+    #[test]
+    fn mcopy_overlap() {
+        let table = make_instruction_table::<DummyHost<DefaultEthereumWiring>, CancunSpec>();
+        let mut host = DummyHost::default();
+        let expected = |range: core::ops::Range<u8>| range.collect::<Vec<u8>>();
+
+        // Forward, the destination is after the source.
+        let mut interp = mcopy_interpreter();
+        push_mcopy_args(&mut interp, 8, 0, 32);
+        interp.step(&table, &mut host);
+        assert_eq!(interp.instruction_result, InstructionResult::Continue);
+        assert_eq!(interp.shared_memory.slice(0, 8), expected(0..8));
+        assert_eq!(interp.shared_memory.slice(8, 32), expected(0..32));
+        assert_eq!(interp.shared_memory.slice(40, 24), expected(40..64));
+        assert_eq!(interp.gas.spent(), gas::VERYLOW + gas::COPY);
+
+        // Backward, the destination is before the source.
+        let mut interp = mcopy_interpreter();
+        push_mcopy_args(&mut interp, 0, 8, 32);
+        interp.step(&table, &mut host);
+        assert_eq!(interp.instruction_result, InstructionResult::Continue);
+        assert_eq!(interp.shared_memory.slice(0, 32), expected(8..40));
+        assert_eq!(interp.shared_memory.slice(32, 32), expected(32..64));
+
+        // The destination range expands the memory by a word.
+        push_mcopy_args(&mut interp, 48, 0, 33);
+        let spent = interp.gas.spent();
+        interp.step(&table, &mut host);
+        assert_eq!(interp.instruction_result, InstructionResult::Continue);
+        assert_eq!(interp.shared_memory.len(), 96);
+        assert_eq!(interp.shared_memory.slice(48, 33)[..8], expected(8..16));
+        assert_eq!(
+            interp.gas.spent() - spent,
+            gas::VERYLOW + 2 * gas::COPY + gas::memory_gas(3) - gas::memory_gas(2)
+        );
+    }
+
+    #[test]
+    fn mcopy_zero_length() {
+        let table = make_instruction_table::<DummyHost<DefaultEthereumWiring>, CancunSpec>();
+        let mut host = DummyHost::default();
+
+        let mut interp = Interpreter::new_bytecode(Bytecode::LegacyRaw([MCOPY].into()));
+        interp.gas = Gas::new(10000);
+        // Offsets are not used, even out of bounds ones.
+        interp.stack.push(U256::ZERO).unwrap();
+        interp.stack.push(U256::MAX).unwrap();
+        interp.stack.push(U256::MAX).unwrap();
+        interp.step(&table, &mut host);
+        assert_eq!(interp.instruction_result, InstructionResult::Continue);
+        assert_eq!(interp.shared_memory.len(), 0);
+        assert_eq!(interp.gas.spent(), gas::VERYLOW);
+    }
+
+    #[test]
+    fn mcopy_before_cancun() {
+        let table = make_instruction_table::<DummyHost<DefaultEthereumWiring>, ShanghaiSpec>();
+        let mut host = DummyHost::default();
+
+        let mut interp = mcopy_interpreter();
+        push_mcopy_args(&mut interp, 8, 0, 32);
+        interp.step(&table, &mut host);
+        assert_eq!(interp.instruction_result, InstructionResult::NotActivated);
+    }
+}
@@ -87,11 +87,32 @@ mod test {
 
     use super::*;
     use crate::{table::make_instruction_table, DummyHost, Gas, InstructionResult};
-    use bytecode::opcode::{DUPN, EXCHANGE, SWAPN};
+    use bytecode::opcode::{DUPN, EXCHANGE, PUSH0, SWAPN};
     use bytecode::Bytecode;
-    use specification::hardfork::PragueSpec;
+    use specification::hardfork::{MergeSpec, PragueSpec, ShanghaiSpec};
     use wiring::DefaultEthereumWiring;
 
+    #[test]
+    fn push0() {
+        let mut host = DummyHost::default();
+
+        let table = make_instruction_table::<DummyHost<DefaultEthereumWiring>, ShanghaiSpec>();
+        let mut interp = Interpreter::new_bytecode(Bytecode::LegacyRaw([PUSH0].into()));
+        interp.gas = Gas::new(10000);
+        interp.step(&table, &mut host);
+        assert_eq!(interp.instruction_result, InstructionResult::Continue);
+        assert_eq!(interp.stack.data(), &vec![U256::ZERO]);
+        assert_eq!(interp.gas.spent(), gas::BASE);
+
+        // Not activated before Shanghai.
+        let table = make_instruction_table::<DummyHost<DefaultEthereumWiring>, MergeSpec>();
+        let mut interp = Interpreter::new_bytecode(Bytecode::LegacyRaw([PUSH0].into()));
+        interp.gas = Gas::new(10000);
+        interp.step(&table, &mut host);
+        assert_eq!(interp.instruction_result, InstructionResult::NotActivated);
+        assert!(interp.stack.is_empty());
+    }
+
     #[test]
     fn dupn() {
         let table = make_instruction_table::<DummyHost<DefaultEthereumWiring>, PragueSpec>();
@@ -3,16 +3,26 @@ use core::ptr;
 use primitives::{B256, KECCAK_EMPTY, U256};
 use specification::hardfork::Spec;
 
-pub fn keccak256<H: Host + ?Sized>(interpreter: &mut Interpreter, _host: &mut H) {
+pub fn keccak256<H: Host + ?Sized>(interpreter: &mut Interpreter, host: &mut H) {
     pop_top!(interpreter, offset, len_ptr);
     let len = as_usize_or_fail!(interpreter, len_ptr);
-    gas_or_fail!(interpreter, gas::keccak256_cost(len as u64));
+    let cfg = &host.env().cfg;
+    gas_or_fail!(
+        interpreter,
+        gas::custom_keccak256_cost(
+            len as u64,
+            cfg.keccak256_gas().unwrap_or(gas::KECCAK256),
+            cfg.keccak256_word_gas().unwrap_or(gas::KECCAK256WORD),
+        )
+    );
     let hash = if len == 0 {
-        KECCAK_EMPTY
+        cfg.keccak256_hasher
+            .map_or(KECCAK_EMPTY, |hasher| hasher.hash(&[]))
     } else {
         let from = as_usize_or_fail!(interpreter, offset);
         resize_memory!(interpreter, from, len);
-        primitives::keccak256(interpreter.shared_memory.slice(from, len))
+        cfg.hasher()
+            .hash(interpreter.shared_memory.slice(from, len))
     };
     *len_ptr = hash.into();
 }
@@ -190,12 +200,71 @@ pub fn memory_resize(
 mod test {
     use super::*;
     use crate::{table::make_instruction_table, DummyHost, Gas, InstructionResult};
-    use bytecode::opcode::{RETURNDATACOPY, RETURNDATALOAD};
+    use bytecode::opcode::{GAS, POP, PUSH1, RETURNDATACOPY, RETURNDATALOAD};
     use bytecode::Bytecode;
     use primitives::bytes;
     use specification::hardfork::PragueSpec;
     use wiring::DefaultEthereumWiring;
 
+    #[test]
+    fn gas_after_own_cost() {
+        let table = make_instruction_table::<DummyHost<DefaultEthereumWiring>, PragueSpec>();
+        let mut host = DummyHost::<DefaultEthereumWiring>::default();
+        let mut interp =
+            Interpreter::new_bytecode(Bytecode::LegacyRaw([PUSH1, 0x01, POP, GAS].into()));
+        interp.gas = Gas::new(10000);
+        for _ in 0..3 {
+            interp.step(&table, &mut host);
+        }
+        assert_eq!(interp.instruction_result, InstructionResult::Continue);
+        // PUSH1 costs 3 gas, POP and GAS 2 gas each, GAS pushes after its own cost.
+        assert_eq!(
+            interp.stack.data(),
+            &vec![U256::from(10000 - gas::VERYLOW - gas::BASE - gas::BASE)]
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "optional_keccak256_gas")]
+    fn keccak256_gas_override() {
+        use bytecode::opcode::{KECCAK256, PUSH0};
+        use wiring::default::EnvWiring;
+
+        let table = make_instruction_table::<DummyHost<DefaultEthereumWiring>, PragueSpec>();
+
+        // Hashes 64 bytes of memory and returns the gas spent.
+        let spent = |keccak256_gas: Option<u64>, keccak256_word_gas: Option<u64>| {
+            let mut env = EnvWiring::<DefaultEthereumWiring>::default();
+            env.cfg.keccak256_gas = keccak256_gas;
+            env.cfg.keccak256_word_gas = keccak256_word_gas;
+            let mut host = DummyHost::new(env);
+            let mut interp = Interpreter::new_bytecode(Bytecode::LegacyRaw(
+                [PUSH1, 0x40, PUSH0, KECCAK256].into(),
+            ));
+            interp.gas = Gas::new(10000);
+            for _ in 0..3 {
+                interp.step(&table, &mut host);
+            }
+            assert_eq!(interp.instruction_result, InstructionResult::Continue);
+            interp.gas.spent()
+        };
+
+        let default = spent(None, None);
+        assert_eq!(
+            spent(Some(gas::KECCAK256), Some(gas::KECCAK256WORD)),
+            default
+        );
+        // Two words of input.
+        assert_eq!(
+            spent(None, Some(2 * gas::KECCAK256WORD)) - default,
+            2 * gas::KECCAK256WORD
+        );
+        assert_eq!(
+            spent(Some(2 * gas::KECCAK256), None) - default,
+            gas::KECCAK256
+        );
+    }
+
     #[test]
     fn returndataload() {
         let table = make_instruction_table::<DummyHost<DefaultEthereumWiring>, PragueSpec>();
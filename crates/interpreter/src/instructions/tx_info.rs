@@ -2,13 +2,11 @@ use crate::{gas, Host, Interpreter};
 use primitives::U256;
 use specification::hardfork::Spec;
 use transaction::Eip4844Tx;
-use wiring::{Block, Transaction, TransactionType};
+use wiring::{Transaction, TransactionType};
 
 pub fn gasprice<H: Host + ?Sized>(interpreter: &mut Interpreter, host: &mut H) {
     gas!(interpreter, gas::BASE);
-    let env = host.env();
-    let basefee = *env.block.basefee();
-    push!(interpreter, env.tx.effective_gas_price(basefee));
+    push!(interpreter, host.env().effective_gas_price());
 }
 
 pub fn origin<H: Host + ?Sized>(interpreter: &mut Interpreter, host: &mut H) {
@@ -13,10 +13,11 @@ use crate::{
     Host, InstructionResult, InterpreterAction,
 };
 use bytecode::{Bytecode, Eof};
-use core::cmp::min;
+use core::{cmp::min, num::NonZeroU64};
 use primitives::{Bytes, U256};
 use std::borrow::ToOwned;
 use std::sync::Arc;
+use std::vec::Vec;
 
 /// EVM bytecode interpreter.
 #[derive(Debug)]
@@ -152,6 +153,7 @@ impl Interpreter {
     /// - `Ok`: Pushes the address from `create_outcome` to the stack, updates gas costs, and records any gas refunds.
     /// - `Revert`: Pushes `U256::ZERO` to the stack and updates gas costs.
     /// - `FatalExternalError`: Sets the `instruction_result` to `InstructionResult::FatalExternalError`.
+    /// - `ReadOnlyViolation`: Halts the current frame with the same result.
     /// - `Default`: Pushes `U256::ZERO` to the stack.
     ///
     /// # Side Effects
@@ -186,6 +188,10 @@ impl Interpreter {
             InstructionResult::FatalExternalError => {
                 panic!("Fatal external error in insert_create_outcome");
             }
+            InstructionResult::ReadOnlyViolation => {
+                // Read-only mode halts the whole execution, not only the failing frame.
+                self.instruction_result = InstructionResult::ReadOnlyViolation;
+            }
             _ => {
                 push!(self, U256::ZERO);
             }
@@ -220,6 +226,10 @@ impl Interpreter {
             InstructionResult::FatalExternalError => {
                 panic!("Fatal external error in insert_eofcreate_outcome");
             }
+            InstructionResult::ReadOnlyViolation => {
+                // Read-only mode halts the whole execution, not only the failing frame.
+                self.instruction_result = InstructionResult::ReadOnlyViolation;
+            }
             _ => {
                 push!(self, U256::ZERO);
             }
@@ -247,6 +257,7 @@ impl Interpreter {
     /// - `return_ok!()`: Processes successful execution, refunds gas, and updates shared memory.
     /// - `return_revert!()`: Handles a revert by only updating the gas usage and shared memory.
     /// - `InstructionResult::FatalExternalError`: Sets the instruction result to a fatal external error.
+    /// - `InstructionResult::ReadOnlyViolation`: Halts the current frame with the same result.
     /// - Any other result: No specific action is taken.
     pub fn insert_call_outcome(
         &mut self,
@@ -259,6 +270,7 @@ impl Interpreter {
         let out_len = call_outcome.memory_length();
         let out_ins_result = *call_outcome.instruction_result();
         let out_gas = call_outcome.gas();
+        // Halted frames have no output, so a failed call only leaves return data if it reverted.
         self.return_data_buffer = call_outcome.result.output;
 
         let target_len = min(out_len, self.return_data_buffer.len());
@@ -292,6 +304,10 @@ impl Interpreter {
             InstructionResult::FatalExternalError => {
                 panic!("Fatal external error in insert_call_outcome");
             }
+            InstructionResult::ReadOnlyViolation => {
+                // Read-only mode halts the whole execution, not only the failing frame.
+                self.instruction_result = InstructionResult::ReadOnlyViolation;
+            }
             _ => {
                 push!(
                     self,
@@ -311,6 +327,20 @@ impl Interpreter {
         unsafe { *self.instruction_pointer }
     }
 
+    /// Returns the immediate bytes of the current instruction if it is `PUSH1` to `PUSH32`.
+    ///
+    /// These are the bytes following the opcode, i.e. the value that will be pushed.
+    #[inline]
+    pub fn current_immediate(&self) -> Option<&[u8]> {
+        let opcode = self.current_opcode();
+        if !(bytecode::opcode::PUSH1..=bytecode::opcode::PUSH32).contains(&opcode) {
+            return None;
+        }
+        let start = self.program_counter() + 1;
+        let len = (opcode - bytecode::opcode::PUSH0) as usize;
+        self.bytecode.get(start..start + len)
+    }
+
     /// Returns a reference to the contract.
     #[inline]
     pub fn contract(&self) -> &Contract {
@@ -323,6 +353,20 @@ impl Interpreter {
         &self.gas
     }
 
+    /// Returns the refund counter of the current frame.
+    ///
+    /// See [`Gas::refunded`].
+    #[inline]
+    pub fn refunded(&self) -> i64 {
+        self.gas.refunded()
+    }
+
+    /// Returns the input data of the current frame, the calldata of calls.
+    #[inline]
+    pub fn calldata(&self) -> &Bytes {
+        &self.contract.input
+    }
+
     /// Returns a reference to the interpreter's stack.
     #[inline]
     pub fn stack(&self) -> &Stack {
@@ -343,6 +387,56 @@ impl Interpreter {
         unsafe { self.instruction_pointer.offset_from(self.bytecode.as_ptr()) as usize }
     }
 
+    /// Takes a snapshot of the execution state: program counter, current code section,
+    /// stack, EOF function stack, gas and instruction result.
+    ///
+    /// Memory is not included as it can be large, use [`Interpreter::snapshot_with_memory`]
+    /// to capture it as well.
+    #[inline]
+    pub fn snapshot(&self) -> InterpreterSnapshot {
+        InterpreterSnapshot {
+            bytecode: self.bytecode.clone(),
+            program_counter: self.program_counter(),
+            stack: self.stack.clone(),
+            function_stack: self.function_stack.clone(),
+            gas: self.gas,
+            instruction_result: self.instruction_result,
+            memory: None,
+        }
+    }
+
+    /// Takes a snapshot of the execution state including a copy of the current context memory.
+    ///
+    /// See [`Interpreter::snapshot`].
+    #[inline]
+    pub fn snapshot_with_memory(&self) -> InterpreterSnapshot {
+        InterpreterSnapshot {
+            memory: Some(self.shared_memory.context_memory().to_vec()),
+            ..self.snapshot()
+        }
+    }
+
+    /// Restores the execution state from a snapshot taken on this interpreter.
+    ///
+    /// Memory is only restored if the snapshot was taken with
+    /// [`Interpreter::snapshot_with_memory`].
+    #[inline]
+    pub fn restore(&mut self, snapshot: InterpreterSnapshot) {
+        self.bytecode = snapshot.bytecode;
+        // SAFETY: program counter was taken from this bytecode.
+        self.instruction_pointer = unsafe { self.bytecode.as_ptr().add(snapshot.program_counter) };
+        self.stack = snapshot.stack;
+        self.function_stack = snapshot.function_stack;
+        self.gas = snapshot.gas;
+        self.instruction_result = snapshot.instruction_result;
+        if let Some(memory) = snapshot.memory {
+            self.shared_memory.resize(memory.len());
+            self.shared_memory
+                .context_memory_mut()
+                .copy_from_slice(&memory);
+        }
+    }
+
     /// Executes the instruction at the current instruction pointer.
     ///
     /// Internally it will increment instruction pointer by one.
@@ -378,11 +472,56 @@ impl Interpreter {
     where
         FN: Fn(&mut Interpreter, &mut H),
     {
+        self.run_with(shared_memory, host, |interpreter, host| {
+            interpreter.step(instruction_table, host)
+        })
+    }
+
+    /// Executes the interpreter until it returns or stops, multiplying the gas spent by each
+    /// instruction by `gas_factor`.
+    ///
+    /// Instructions starting a new frame are not scaled, as their cost includes the gas limit of the
+    /// new frame.
+    pub fn run_scaled<FN, H: Host + ?Sized>(
+        &mut self,
+        shared_memory: SharedMemory,
+        instruction_table: &[FN; 256],
+        host: &mut H,
+        gas_factor: NonZeroU64,
+    ) -> InterpreterAction
+    where
+        FN: Fn(&mut Interpreter, &mut H),
+    {
+        self.run_with(shared_memory, host, |interpreter, host| {
+            let remaining = interpreter.gas.remaining();
+            interpreter.step(instruction_table, host);
+            if !interpreter.next_action.is_none() {
+                return;
+            }
+            let spent = remaining - interpreter.gas.remaining();
+            if !interpreter
+                .gas
+                .record_cost(spent.saturating_mul(gas_factor.get() - 1))
+            {
+                interpreter.instruction_result = InstructionResult::OutOfGas;
+            }
+        })
+    }
+
+    /// Executes the interpreter until it returns or stops, calling `step` to execute each
+    /// instruction.
+    #[inline]
+    fn run_with<H: Host + ?Sized>(
+        &mut self,
+        shared_memory: SharedMemory,
+        host: &mut H,
+        mut step: impl FnMut(&mut Interpreter, &mut H),
+    ) -> InterpreterAction {
         self.next_action = InterpreterAction::None;
         self.shared_memory = shared_memory;
         // main loop
         while self.instruction_result == InstructionResult::Continue {
-            self.step(instruction_table, host);
+            step(self, host);
         }
 
         // Return next action if it is some.
@@ -408,6 +547,45 @@ impl Interpreter {
     }
 }
 
+/// Snapshot of the interpreter execution state, see [`Interpreter::snapshot`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InterpreterSnapshot {
+    /// Bytecode of the current code section.
+    bytecode: Bytes,
+    /// Program counter inside `bytecode`.
+    program_counter: usize,
+    /// Stack.
+    stack: Stack,
+    /// EOF function stack.
+    function_stack: FunctionStack,
+    /// Gas state.
+    gas: Gas,
+    /// Instruction result.
+    instruction_result: InstructionResult,
+    /// Context memory, if captured.
+    memory: Option<Vec<u8>>,
+}
+
+impl InterpreterSnapshot {
+    /// Returns the program counter at the time of the snapshot.
+    #[inline]
+    pub fn program_counter(&self) -> usize {
+        self.program_counter
+    }
+
+    /// Returns the stack at the time of the snapshot.
+    #[inline]
+    pub fn stack(&self) -> &Stack {
+        &self.stack
+    }
+
+    /// Returns the gas state at the time of the snapshot.
+    #[inline]
+    pub fn gas(&self) -> &Gas {
+        &self.gas
+    }
+}
+
 /// The result of an interpreter operation.
 #[derive(Clone, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
@@ -468,7 +646,8 @@ pub fn resize_memory(memory: &mut SharedMemory, gas: &mut Gas, new_size: usize)
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{table::InstructionTable, DummyHost};
+    use crate::{table::InstructionTable, CallInputs, CallScheme, CallValue, DummyHost};
+    use primitives::Address;
     use specification::hardfork::CancunSpec;
     use wiring::DefaultEthereumWiring;
 
@@ -490,4 +669,218 @@ mod tests {
             >();
         let _ = interp.run(EMPTY_SHARED_MEMORY, table, host);
     }
+
+    #[test]
+    fn calldata() {
+        let inputs = CallInputs {
+            input: Bytes::from_static(&[0xa9, 0x05, 0x9c, 0xbb, 0x01]),
+            return_memory_offset: 0..0,
+            gas_limit: 100_000,
+            bytecode_address: Address::with_last_byte(0xa0),
+            target_address: Address::with_last_byte(0xa0),
+            caller: Address::with_last_byte(0xca),
+            value: CallValue::Transfer(U256::ZERO),
+            scheme: CallScheme::Call,
+            is_static: false,
+            is_eof: false,
+        };
+        let contract =
+            Contract::new_with_context(inputs.input.clone(), Bytecode::default(), None, &inputs);
+        let interp = Interpreter::new(contract, inputs.gas_limit, inputs.is_static);
+        assert_eq!(interp.calldata(), &inputs.input);
+    }
+
+    #[test]
+    fn snapshot_restore() {
+        use bytecode::opcode::{INVALID, JUMP, JUMPDEST, PUSH1, STOP};
+
+        let table =
+            crate::table::make_instruction_table::<DummyHost<DefaultEthereumWiring>, CancunSpec>();
+        let mut host = DummyHost::default();
+        let mut interp = Interpreter::new_bytecode(Bytecode::LegacyRaw(
+            [PUSH1, 0x04, JUMP, INVALID, JUMPDEST, STOP].into(),
+        ));
+        interp.gas = Gas::new(10000);
+        interp.shared_memory = SharedMemory::new();
+        interp.shared_memory.resize(32);
+        interp.shared_memory.set_byte(0, 0xaa);
+
+        interp.step(&table, &mut host);
+        let snapshot = interp.snapshot_with_memory();
+        assert_eq!(snapshot.program_counter(), 2);
+
+        interp.step(&table, &mut host);
+        assert_eq!(interp.program_counter(), 4);
+        assert!(interp.stack.is_empty());
+        interp.shared_memory.resize(64);
+        interp.shared_memory.set_byte(0, 0xbb);
+
+        interp.restore(snapshot.clone());
+        assert_eq!(interp.program_counter(), 2);
+        assert_eq!(interp.current_opcode(), JUMP);
+        assert_eq!(interp.stack.data(), &vec![U256::from(4)]);
+        assert_eq!(interp.gas, *snapshot.gas());
+        assert_eq!(interp.shared_memory.len(), 32);
+        assert_eq!(interp.shared_memory.get_byte(0), 0xaa);
+
+        // Execution continues from the restored state.
+        interp.step(&table, &mut host);
+        assert_eq!(interp.program_counter(), 4);
+    }
+
+    #[test]
+    fn current_immediate() {
+        use bytecode::opcode::{PUSH0, PUSH4, STOP};
+
+        let table =
+            crate::table::make_instruction_table::<DummyHost<DefaultEthereumWiring>, CancunSpec>();
+        let mut host = DummyHost::default();
+        let mut interp = Interpreter::new_bytecode(Bytecode::LegacyRaw(
+            [PUSH0, PUSH4, 0x11, 0x22, 0x33, 0x44, STOP].into(),
+        ));
+        interp.gas = Gas::new(10000);
+
+        assert_eq!(interp.current_immediate(), None);
+        interp.step(&table, &mut host);
+        assert_eq!(
+            interp.current_immediate(),
+            Some(&[0x11, 0x22, 0x33, 0x44][..])
+        );
+        interp.step(&table, &mut host);
+        assert_eq!(interp.current_opcode(), STOP);
+        assert_eq!(interp.current_immediate(), None);
+    }
+
+    #[test]
+    fn external_call_resume() {
+        use crate::{
+            AccountLoad, CallInputs, Eip7702CodeLoad, NewFrameAction, SStoreResult,
+            SelfDestructResult, StateLoad,
+        };
+        use bytecode::opcode::{CALL, GAS, PUSH0, PUSH1, PUSH20, RETURN};
+        use primitives::{Address, Log, B256};
+        use wiring::default::EnvWiring;
+
+        /// Dummy host servicing calls to `magic` itself.
+        #[derive(Default)]
+        struct ExternalCallHost {
+            inner: DummyHost<DefaultEthereumWiring>,
+            magic: Address,
+        }
+
+        impl Host for ExternalCallHost {
+            type EvmWiringT = DefaultEthereumWiring;
+
+            fn env(&self) -> &EnvWiring<Self::EvmWiringT> {
+                self.inner.env()
+            }
+
+            fn env_mut(&mut self) -> &mut EnvWiring<Self::EvmWiringT> {
+                self.inner.env_mut()
+            }
+
+            fn load_account_delegated(&mut self, address: Address) -> Option<AccountLoad> {
+                self.inner.load_account_delegated(address)
+            }
+
+            fn block_hash(&mut self, number: u64) -> Option<B256> {
+                self.inner.block_hash(number)
+            }
+
+            fn balance(&mut self, address: Address) -> Option<StateLoad<U256>> {
+                self.inner.balance(address)
+            }
+
+            fn code(&mut self, address: Address) -> Option<Eip7702CodeLoad<Bytes>> {
+                self.inner.code(address)
+            }
+
+            fn code_hash(&mut self, address: Address) -> Option<Eip7702CodeLoad<B256>> {
+                self.inner.code_hash(address)
+            }
+
+            fn sload(&mut self, address: Address, index: U256) -> Option<StateLoad<U256>> {
+                self.inner.sload(address, index)
+            }
+
+            fn sstore(
+                &mut self,
+                address: Address,
+                index: U256,
+                value: U256,
+            ) -> Option<StateLoad<SStoreResult>> {
+                self.inner.sstore(address, index, value)
+            }
+
+            fn tload(&mut self, address: Address, index: U256) -> U256 {
+                self.inner.tload(address, index)
+            }
+
+            fn tstore(&mut self, address: Address, index: U256, value: U256) {
+                self.inner.tstore(address, index, value)
+            }
+
+            fn log(&mut self, log: Log) {
+                self.inner.log(log)
+            }
+
+            fn selfdestruct(
+                &mut self,
+                address: Address,
+                target: Address,
+            ) -> Option<StateLoad<SelfDestructResult>> {
+                self.inner.selfdestruct(address, target)
+            }
+
+            fn is_external_call(&mut self, inputs: &CallInputs) -> bool {
+                inputs.target_address == self.magic
+            }
+        }
+
+        let magic = Address::repeat_byte(0xee);
+        let mut host = ExternalCallHost {
+            magic,
+            ..Default::default()
+        };
+        let table = crate::table::make_instruction_table::<ExternalCallHost, CancunSpec>();
+
+        // Call `magic` writing 32 bytes of output at 0 and return them.
+        let mut code = vec![PUSH1, 0x20, PUSH0, PUSH0, PUSH0, PUSH0, PUSH20];
+        code.extend_from_slice(magic.as_slice());
+        code.extend_from_slice(&[GAS, CALL, PUSH1, 0x20, PUSH0, RETURN]);
+        let mut interp = Interpreter::new_bytecode(Bytecode::new_raw(code.into()));
+        interp.gas = Gas::new(100_000);
+
+        let action = interp.run(SharedMemory::new(), &table, &mut host);
+        let InterpreterAction::NewFrame(NewFrameAction::Call(inputs)) = action else {
+            panic!("expected call, got {action:?}");
+        };
+        assert!(host.is_external_call(&inputs));
+        let spent = interp.gas.spent();
+
+        // Service the call, using 1000 gas and returning 32 bytes.
+        let output = Bytes::from(B256::repeat_byte(0xab).to_vec());
+        let mut gas = Gas::new(inputs.gas_limit);
+        assert!(gas.record_cost(1000));
+        let outcome = CallOutcome::new(
+            InterpreterResult {
+                result: InstructionResult::Return,
+                output: output.clone(),
+                gas,
+            },
+            inputs.return_memory_offset.clone(),
+        );
+        let mut memory = interp.take_memory();
+        interp.insert_call_outcome(&mut memory, outcome);
+        assert_eq!(interp.stack.data(), &vec![U256::from(1)]);
+        assert_eq!(interp.gas.spent(), spent - inputs.gas_limit + 1000);
+
+        let action = interp.run(memory, &table, &mut host);
+        let InterpreterAction::Return { result } = action else {
+            panic!("expected return, got {action:?}");
+        };
+        assert_eq!(result.result, InstructionResult::Return);
+        assert_eq!(result.output, output);
+        assert_eq!(interp.return_data_buffer, output);
+    }
 }
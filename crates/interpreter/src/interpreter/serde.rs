@@ -115,6 +115,14 @@ impl<'de> Deserialize<'de> for Interpreter {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{table::make_instruction_table, DummyHost};
+    use bytecode::{
+        opcode::{MSTORE, PUSH1, RETURN},
+        Bytecode,
+    };
+    use primitives::U256;
+    use specification::hardfork::CancunSpec;
+    use wiring::DefaultEthereumWiring;
 
     #[test]
     fn test_serde() {
@@ -123,4 +131,52 @@ mod tests {
         let de: Interpreter = bincode::deserialize(&serialized).unwrap();
         assert_eq!(interp.program_counter(), de.program_counter());
     }
+
+    #[test]
+    fn test_serde_mid_execution() {
+        let table = make_instruction_table::<DummyHost<DefaultEthereumWiring>, CancunSpec>();
+        let mut host = DummyHost::default();
+        let mut interp = Interpreter::new_bytecode(Bytecode::new_raw(
+            [
+                PUSH1, 0x2a, PUSH1, 0x00, MSTORE, PUSH1, 0x20, PUSH1, 0x00, RETURN,
+            ]
+            .into(),
+        ));
+        interp.gas = Gas::new(10_000);
+        interp.shared_memory = SharedMemory::new();
+        interp.return_data_buffer = Bytes::from_static(&[0xaa, 0xbb]);
+
+        // Pause after MSTORE and the first push of the return arguments.
+        for _ in 0..4 {
+            interp.step(&table, &mut host);
+        }
+
+        let serialized = bincode::serialize(&interp).unwrap();
+        let mut de: Interpreter = bincode::deserialize(&serialized).unwrap();
+        assert_eq!(de.program_counter(), interp.program_counter());
+        assert_eq!(de.stack.data(), &vec![U256::from(0x20)]);
+        assert_eq!(
+            de.shared_memory.context_memory(),
+            interp.shared_memory.context_memory()
+        );
+        assert_eq!(de.gas, interp.gas);
+        assert_eq!(de.return_data_buffer, interp.return_data_buffer);
+        assert_eq!(de.instruction_result, interp.instruction_result);
+        assert_eq!(de.is_static, interp.is_static);
+        assert_eq!(de.is_eof, interp.is_eof);
+
+        // Both resume to the same result.
+        let memory = interp.take_memory();
+        let action = interp.run(memory, &table, &mut host);
+        let memory = de.take_memory();
+        let de_action = de.run(memory, &table, &mut host);
+        assert_eq!(de_action, action);
+        let InterpreterAction::Return { result } = action else {
+            panic!("unexpected action {action:?}");
+        };
+        assert_eq!(
+            result.output,
+            Bytes::from(U256::from(0x2a).to_be_bytes_vec())
+        );
+    }
 }
@@ -329,6 +329,20 @@ mod tests {
         assert_eq!(num_words(u64::MAX), u64::MAX / 32);
     }
 
+    #[test]
+    #[cfg(feature = "memory_limit")]
+    fn limit_reached() {
+        let mut shared_memory = SharedMemory::new_with_memory_limit(64);
+        assert!(!shared_memory.limit_reached(64));
+        assert!(shared_memory.limit_reached(65));
+
+        // The limit applies to the whole buffer, including parent contexts.
+        shared_memory.resize(32);
+        shared_memory.new_context();
+        assert!(!shared_memory.limit_reached(32));
+        assert!(shared_memory.limit_reached(33));
+    }
+
     #[test]
     fn new_free_context() {
         let mut shared_memory = SharedMemory::new();
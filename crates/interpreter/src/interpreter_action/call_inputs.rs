@@ -80,6 +80,15 @@ impl CallInputs {
         self.target_address
     }
 
+    /// Returns `true` if the call executes the code of the zero address, often used as a burn
+    /// address or a marker.
+    ///
+    /// The zero address has no code, so such calls only transfer value.
+    #[inline]
+    pub fn calls_zero_address(&self) -> bool {
+        self.bytecode_address.is_zero()
+    }
+
     /// Returns the call value, regardless of the transfer value type.
     ///
     /// NOTE: this value may not necessarily be transferred from caller to callee, see [`CallValue`].
@@ -1,5 +1,5 @@
 use primitives::{Address, Bytes, U256};
-use wiring::default::CreateScheme;
+use wiring::default::{CreateScheme, Keccak256Hasher};
 
 /// Inputs for a create call.
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
@@ -18,7 +18,14 @@ pub struct CreateInputs {
 }
 
 impl CreateInputs {
-    /// Returns the address that this create call will create.
+    /// Returns the address that this create call will create, without executing it.
+    ///
+    /// `CREATE` derives it from the caller and `nonce`, the caller's nonce before the creation:
+    /// `keccak256(rlp([caller, nonce]))[12..]`. `CREATE2` ignores `nonce` and derives it from the
+    /// caller, the salt and the init code hash: `keccak256(0xff ++ caller ++ salt ++
+    /// keccak256(init_code))[12..]`. Matches the address of the created frame unless
+    /// [`CfgEnv::keccak256_hasher`](wiring::default::CfgEnv::keccak256_hasher) is set, use
+    /// [`Self::created_address_with_hasher`] in that case.
     pub fn created_address(&self, nonce: u64) -> Address {
         match self.scheme {
             CreateScheme::Create => self.caller.create(nonce),
@@ -27,4 +34,105 @@ impl CreateInputs {
                 .create2_from_code(salt.to_be_bytes(), &self.init_code),
         }
     }
+
+    /// Returns the address that this create call will create, hashing with `hasher`.
+    ///
+    /// Pass [`CfgEnv::hasher`](wiring::default::CfgEnv::hasher) to match the address of the
+    /// created frame, see [`Self::created_address`].
+    pub fn created_address_with_hasher(&self, nonce: u64, hasher: &Keccak256Hasher) -> Address {
+        match self.scheme {
+            CreateScheme::Create => hasher.create_address(self.caller, nonce),
+            CreateScheme::Create2 { salt } => hasher.create2_address(
+                self.caller,
+                salt.to_be_bytes().into(),
+                hasher.hash(&self.init_code),
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use primitives::{address, b256, bytes, B256};
+
+    fn inputs(caller: Address, scheme: CreateScheme, init_code: Bytes) -> CreateInputs {
+        CreateInputs {
+            caller,
+            scheme,
+            value: U256::ZERO,
+            init_code,
+            gas_limit: 0,
+        }
+    }
+
+    #[test]
+    fn created_address_create() {
+        let create = inputs(
+            address!("6ac7ea33f8831ea9dcc53393aaa88b25a785dbf0"),
+            CreateScheme::Create,
+            Bytes::new(),
+        );
+        assert_eq!(
+            create.created_address(0),
+            address!("cd234a471b72ba2f1ccf0a70fcaba648a5eecd8d")
+        );
+        assert_eq!(
+            create.created_address(1),
+            address!("343c43a37d37dff08ae8c4a11544c718abb4fcf8")
+        );
+    }
+
+    #[test]
+    fn created_address_create2() {
+        // Examples of EIP-1014.
+        let vectors = [
+            (
+                Address::ZERO,
+                B256::ZERO,
+                bytes!("00"),
+                address!("4D1A2e2bB4F88F0250f26Ffff098B0b30B26BF38"),
+            ),
+            (
+                address!("deadbeef00000000000000000000000000000000"),
+                b256!("000000000000000000000000feed000000000000000000000000000000000000"),
+                bytes!("00"),
+                address!("D04116cDd17beBE565EB2422F2497E06cC1C9833"),
+            ),
+            (
+                Address::ZERO,
+                B256::ZERO,
+                bytes!("deadbeef"),
+                address!("70f2b2914A2a4b783FaEFb75f459A580616Fcb5e"),
+            ),
+        ];
+        for (caller, salt, init_code, expected) in vectors {
+            let create2 = inputs(
+                caller,
+                CreateScheme::Create2 { salt: salt.into() },
+                init_code,
+            );
+            // The nonce is ignored.
+            assert_eq!(create2.created_address(0), expected);
+            assert_eq!(create2.created_address(7), expected);
+            assert_eq!(
+                create2.created_address_with_hasher(0, &Keccak256Hasher::KECCAK256),
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn created_address_with_hasher() {
+        let hasher = Keccak256Hasher::new(|_| B256::repeat_byte(0xab));
+        let create = inputs(Address::ZERO, CreateScheme::Create, Bytes::new());
+        assert_eq!(
+            create.created_address_with_hasher(0, &hasher),
+            Address::repeat_byte(0xab)
+        );
+        assert_eq!(
+            create.created_address_with_hasher(0, &Keccak256Hasher::KECCAK256),
+            create.created_address(0)
+        );
+    }
 }
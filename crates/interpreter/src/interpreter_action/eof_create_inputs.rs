@@ -12,6 +12,7 @@ use primitives::{Address, Bytes, U256};
 /// Address is already known and is passed as an argument.
 #[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[allow(clippy::large_enum_variant)]
 pub enum EOFCreateKind {
     Tx {
         initdata: Bytes,
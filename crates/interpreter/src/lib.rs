@@ -31,11 +31,12 @@ pub use function_stack::{FunctionReturnFrame, FunctionStack};
 pub use gas::Gas;
 pub use host::{
     AccountLoad, DummyHost, Eip7702CodeLoad, Host, SStoreResult, SelfDestructResult, StateLoad,
+    TransferError,
 };
 pub use instruction_result::*;
 pub use interpreter::{
-    num_words, Contract, Interpreter, InterpreterResult, SharedMemory, Stack, EMPTY_SHARED_MEMORY,
-    STACK_LIMIT,
+    num_words, Contract, Interpreter, InterpreterResult, InterpreterSnapshot, SharedMemory, Stack,
+    EMPTY_SHARED_MEMORY, STACK_LIMIT,
 };
 pub use interpreter_action::{
     CallInputs, CallOutcome, CallScheme, CallValue, CreateInputs, CreateOutcome, EOFCreateInputs,
@@ -22,6 +22,7 @@ pub type BoxedInstructionTable<'a, H> = [BoxedInstruction<'a, H>; 256];
 /// Either a plain, static instruction table, or a boxed, dynamic instruction table.
 ///
 /// Note that `Plain` variant is about 10-20% faster in Interpreter execution.
+#[allow(clippy::large_enum_variant)]
 pub enum InstructionTables<'a, H: ?Sized> {
     Plain(InstructionTable<H>),
     Boxed(BoxedInstructionTable<'a, H>),
@@ -168,3 +169,27 @@ where
     let prev = core::mem::replace(instruction, Box::new(|_, _| {}));
     *instruction = Box::new(move |i, h| f(&prev, i, h));
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{DummyHost, InstructionResult};
+    use bytecode::{opcode::STOP, Bytecode};
+    use specification::hardfork::CancunSpec;
+    use wiring::DefaultEthereumWiring;
+
+    #[test]
+    fn boxed_table_wraps_every_instruction() {
+        let mut tables =
+            InstructionTables::<DummyHost<DefaultEthereumWiring>>::new_plain::<CancunSpec>();
+        tables.update_all(|prev, interp, host| prev(interp, host));
+        let InstructionTables::Boxed(table) = &tables else {
+            panic!("table should be boxed after update_all")
+        };
+
+        let mut host = DummyHost::default();
+        let mut interp = Interpreter::new_bytecode(Bytecode::LegacyRaw([STOP].into()));
+        interp.step(table, &mut host);
+        assert_eq!(interp.instruction_result, InstructionResult::Stop);
+    }
+}
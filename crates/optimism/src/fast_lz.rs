@@ -170,7 +170,10 @@ mod tests {
             .with_db(BenchmarkDB::new_bytecode(contract_bytecode.clone()))
             .with_default_ext_ctx()
             .modify_tx_env(|tx| {
-                let OpTransaction::Base { tx, enveloped_tx } = tx else {
+                let OpTransaction::Base {
+                    tx, enveloped_tx, ..
+                } = tx
+                else {
                     panic!("Default is base tx");
                 };
                 tx.caller = address!("1000000000000000000000000000000000000000");
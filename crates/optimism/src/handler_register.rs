@@ -6,7 +6,7 @@ use crate::{
         deposit::DepositTransaction, error::OpTransactionError, OpTransactionType, OpTxTrait,
     },
     wiring::{OptimismContextTrait, OptimismWiring},
-    OptimismHaltReason, OptimismSpec, OptimismSpecId,
+    L1BlockInfo, OptimismHaltReason, OptimismSpec, OptimismSpecId,
 };
 use crate::{BASE_FEE_RECIPIENT, L1_FEE_RECIPIENT};
 use core::ops::Mul;
@@ -27,7 +27,7 @@ use revm::{
             EVMError, EVMResult, EVMResultGeneric, ExecutionResult, InvalidTransaction,
             ResultAndState,
         },
-        Block, Transaction,
+        Transaction,
     },
     Context, ContextPrecompiles, FrameResult,
 };
@@ -68,8 +68,8 @@ pub fn validate_env<EvmWiringT: OptimismWiring, SPEC: OptimismSpec>(
     let tx_type = env.tx.tx_type();
     if tx_type == OpTransactionType::Deposit {
         let tx = env.tx.deposit();
-        // Do not allow for a system transaction to be processed if Regolith is enabled.
-        // TODO check if this is correct.
+        // System transactions were removed in Regolith, see `last_frame_return` for their
+        // gas accounting before it.
         if tx.is_system_transaction() && SPEC::optimism_enabled(OptimismSpecId::REGOLITH) {
             return Err(OpTransactionError::DepositSystemTxPostRegolith.into());
         }
@@ -178,6 +178,7 @@ pub fn refund<EvmWiringT: OptimismWiring, SPEC: OptimismSpec>(
     eip7702_refund: i64,
 ) {
     gas.record_refund(eip7702_refund);
+    context.evm.inner.gas_refunded_uncapped = gas.refunded().max(0) as u64;
 
     let env = context.evm.inner.env();
     let is_deposit = env.tx.tx_type() == OpTransactionType::Deposit;
@@ -186,7 +187,10 @@ pub fn refund<EvmWiringT: OptimismWiring, SPEC: OptimismSpec>(
     // Prior to Regolith, deposit transactions did not receive gas refunds.
     let is_gas_refund_disabled = env.cfg.is_gas_refund_disabled() || (is_deposit && !is_regolith);
     if !is_gas_refund_disabled {
-        gas.set_final_refund(SPEC::OPTIMISM_SPEC_ID.is_enabled_in(OptimismSpecId::LONDON));
+        let max_refund_quotient = env
+            .cfg
+            .max_refund_quotient(SPEC::OPTIMISM_SPEC_ID.is_enabled_in(OptimismSpecId::LONDON));
+        gas.set_final_refund_with_quotient(max_refund_quotient);
     }
 }
 
@@ -265,22 +269,14 @@ pub fn deduct_caller<EvmWiringT: OptimismWiring, SPEC: OptimismSpec>(
     // If the transaction is not a deposit transaction, subtract the L1 data fee from the
     // caller's balance directly after minting the requested amount of ETH.
     if !is_deposit {
-        // get envelope
-        let enveloped_tx = context
-            .evm
-            .inner
-            .env
-            .tx
-            .enveloped_tx()
-            .expect("all not deposit tx have enveloped tx");
-
-        let tx_l1_cost = context
+        let l1_block_info = context
             .evm
             .inner
             .chain
             .l1_block_info()
-            .expect("L1BlockInfo should be loaded")
-            .calculate_tx_l1_cost(enveloped_tx, SPEC::OPTIMISM_SPEC_ID);
+            .expect("L1BlockInfo should be loaded");
+        let tx_l1_cost = tx_l1_cost::<SPEC>(&context.evm.inner.env.tx, l1_block_info)
+            .expect("all not deposit tx have enveloped tx or rollup data gas");
         if tx_l1_cost.gt(&caller_account.info.balance) {
             return Err(EVMError::Transaction(
                 InvalidTransaction::LackOfFundForMaxFee {
@@ -295,6 +291,22 @@ pub fn deduct_caller<EvmWiringT: OptimismWiring, SPEC: OptimismSpec>(
     Ok(())
 }
 
+/// Returns the L1 cost of the non deposit transaction.
+///
+/// It is computed from the enveloped transaction if present, from its rollup data gas otherwise.
+/// Returns `None` if the transaction has neither.
+fn tx_l1_cost<SPEC: OptimismSpec>(
+    tx: &impl OpTxTrait,
+    l1_block_info: &L1BlockInfo,
+) -> Option<U256> {
+    if let Some(enveloped_tx) = tx.enveloped_tx() {
+        return Some(l1_block_info.calculate_tx_l1_cost(enveloped_tx, SPEC::OPTIMISM_SPEC_ID));
+    }
+    tx.rollup_data_gas().map(|data| {
+        l1_block_info.calculate_tx_l1_cost_from_rollup_data(data, SPEC::OPTIMISM_SPEC_ID)
+    })
+}
+
 /// Reward beneficiary with gas fee.
 #[inline]
 pub fn reward_beneficiary<EvmWiringT: OptimismWiring, SPEC: OptimismSpec>(
@@ -317,14 +329,12 @@ pub fn reward_beneficiary<EvmWiringT: OptimismWiring, SPEC: OptimismSpec>(
             .l1_block_info()
             .expect("L1BlockInfo should be loaded");
 
-        let Some(enveloped_tx) = &context.evm.inner.env.tx.enveloped_tx() else {
+        let Some(l1_cost) = tx_l1_cost::<SPEC>(&context.evm.inner.env.tx, l1_block_info) else {
             return Err(EVMError::Custom(
                 "[OPTIMISM] Failed to load enveloped transaction.".into(),
             ));
         };
 
-        let l1_cost = l1_block_info.calculate_tx_l1_cost(enveloped_tx, SPEC::OPTIMISM_SPEC_ID);
-
         // Send the L1 cost of the transaction to the L1 Fee Vault.
         let mut l1_fee_vault_account = context
             .evm
@@ -347,8 +357,7 @@ pub fn reward_beneficiary<EvmWiringT: OptimismWiring, SPEC: OptimismSpec>(
             .evm
             .inner
             .env
-            .block
-            .basefee()
+            .effective_basefee()
             .mul(U256::from(gas.spent() - gas.refunded() as u64));
     }
     Ok(())
@@ -433,6 +442,17 @@ pub fn end<EvmWiringT: OptimismWiring, SPEC: OptimismSpec>(
                     gas_used,
                 },
                 state,
+                // The intrinsic gas is part of the gas used, which is zero for pre-Regolith
+                // system transactions.
+                intrinsic_gas: mainnet::validate_initial_tx_gas::<EvmWiringT, SPEC>(
+                    &context.evm.inner.env,
+                )
+                .unwrap_or_default()
+                .min(gas_used),
+                // Failed deposits are not executed and pay no fees.
+                gas_refunded_uncapped: 0,
+                deferred_reward: U256::ZERO,
+                priority_fee_paid: U256::ZERO,
             })
         } else {
             Err(err)
@@ -444,16 +464,18 @@ pub fn end<EvmWiringT: OptimismWiring, SPEC: OptimismSpec>(
 mod tests {
     use super::*;
     use crate::{
-        transaction::deposit::TxDeposit, wiring::OptimismEvmWiring, BedrockSpec, L1BlockInfo,
-        LatestSpec, OpTransaction, RegolithSpec,
+        transaction::deposit::TxDeposit, wiring::OptimismEvmWiring, BedrockSpec, EcotoneSpec,
+        FjordSpec, L1BlockInfo, LatestSpec, OpTransaction, RegolithSpec, RollupDataGas,
     };
     use database::InMemoryDB;
+    use inspector::{inspector_handle_register, Inspector};
     use revm::{
         database_interface::EmptyDB,
         interpreter::{CallOutcome, InstructionResult, InterpreterResult},
-        primitives::{bytes, Address, Bytes, B256},
+        primitives::{bytes, Address, Bytes, TxKind, B256},
         state::AccountInfo,
         wiring::default::{block::BlockEnv, Env, TxEnv},
+        Evm, EvmContext,
     };
     use std::boxed::Box;
 
@@ -470,7 +492,7 @@ mod tests {
         SPEC: OptimismSpec,
     {
         let mut ctx = Context::<TestEmptyOpWiring>::new_with_db(EmptyDB::default());
-        ctx.evm.inner.env = Box::new(env);
+        *ctx.evm.inner.env = env;
         let mut first_frame = FrameResult::Call(CallOutcome::new(
             InterpreterResult {
                 result: instruction_result,
@@ -494,6 +516,7 @@ mod tests {
         env.tx = OpTransaction::Base {
             tx,
             enveloped_tx: None,
+            rollup_data_gas: None,
         };
 
         let gas =
@@ -680,6 +703,7 @@ mod tests {
         context.evm.inner.env.tx = OpTransaction::Base {
             tx: TxEnv::default(),
             enveloped_tx: Some(bytes!("FACADE")),
+            rollup_data_gas: None,
         };
         deduct_caller::<TestMemOpWiring, RegolithSpec>(&mut context).unwrap();
 
@@ -693,6 +717,55 @@ mod tests {
         assert_eq!(account.info.balance, U256::from(1));
     }
 
+    #[test]
+    fn test_remove_l1_cost_from_rollup_data() {
+        let caller = Address::ZERO;
+        let envelope = bytes!("FACADE");
+
+        // Returns the caller balance after the L1 cost is deducted.
+        let deduct = |enveloped_tx: Option<Bytes>, rollup_data_gas: Option<RollupDataGas>| {
+            let mut db = InMemoryDB::default();
+            db.insert_account_info(
+                caller,
+                AccountInfo {
+                    balance: U256::from(1049),
+                    ..Default::default()
+                },
+            );
+            let mut context = Context::<TestMemOpWiring>::new_with_db(db);
+            *context.evm.chain.l1_block_info_mut() = Some(L1BlockInfo {
+                l1_base_fee: U256::from(1_000),
+                l1_fee_overhead: Some(U256::from(1_000)),
+                l1_base_fee_scalar: U256::from(1_000),
+                ..Default::default()
+            });
+            context.evm.inner.env.tx = OpTransaction::Base {
+                tx: TxEnv::default(),
+                enveloped_tx,
+                rollup_data_gas,
+            };
+            deduct_caller::<TestMemOpWiring, RegolithSpec>(&mut context).unwrap();
+            context
+                .evm
+                .inner
+                .journaled_state
+                .load_account(caller, &mut context.evm.inner.db)
+                .unwrap()
+                .info
+                .balance
+        };
+
+        // l1block cost is 1048 fee.
+        let rollup_data_gas = RollupDataGas::from_envelope(&envelope);
+        assert_eq!(deduct(Some(envelope.clone()), None), U256::from(1));
+        assert_eq!(deduct(None, Some(rollup_data_gas)), U256::from(1));
+        // The envelope is preferred.
+        assert_eq!(
+            deduct(Some(envelope), Some(RollupDataGas::default())),
+            U256::from(1)
+        );
+    }
+
     #[test]
     fn test_remove_l1_cost_lack_of_funds() {
         let caller = Address::ZERO;
@@ -715,6 +788,7 @@ mod tests {
         context.evm.inner.env.tx = OpTransaction::Base {
             tx: TxEnv::default(),
             enveloped_tx: Some(bytes!("FACADE")),
+            rollup_data_gas: None,
         };
 
         assert_eq!(
@@ -782,4 +856,185 @@ mod tests {
         // Nonce and balance checks should be skipped for deposit transactions.
         assert!(validate_env::<TestEmptyOpWiring, LatestSpec>(&env).is_ok());
     }
+
+    #[test]
+    fn test_deposit_mint() {
+        let caller = Address::with_last_byte(0xa0);
+        let recipient = Address::with_last_byte(0xa1);
+
+        // The caller can't pay for the value nor has the expected nonce without the deposit path.
+        let mut db = InMemoryDB::default();
+        db.insert_account_info(
+            caller,
+            AccountInfo {
+                nonce: 5,
+                ..Default::default()
+            },
+        );
+
+        let mut evm = Evm::<TestMemOpWiring>::builder()
+            .with_db(db)
+            .with_default_ext_ctx()
+            .with_tx_env(OpTransaction::Deposit(TxDeposit {
+                from: caller,
+                to: TxKind::Call(recipient),
+                mint: Some(1_000),
+                value: U256::from(400),
+                gas_limit: 100_000,
+                ..Default::default()
+            }))
+            .build();
+
+        let ResultAndState { result, state, .. } = evm.transact().unwrap();
+        assert!(result.is_success());
+
+        let caller_account = &state[&caller];
+        assert!(caller_account.is_touched());
+        assert_eq!(caller_account.info.balance, U256::from(600));
+        assert_eq!(caller_account.info.nonce, 6);
+        assert_eq!(state[&recipient].info.balance, U256::from(400));
+    }
+
+    #[test]
+    fn test_failed_deposit_output() {
+        let caller = Address::with_last_byte(0xa0);
+        let mut context = Context::<TestMemOpWiring>::new_with_db(InMemoryDB::default());
+        context.evm.inner.env.tx = OpTransaction::Deposit(TxDeposit {
+            from: caller,
+            to: TxKind::Call(Address::with_last_byte(0xa1)),
+            gas_limit: 100_000,
+            ..Default::default()
+        });
+
+        let output = end::<TestMemOpWiring, RegolithSpec>(
+            &mut context,
+            Err(EVMError::Transaction(
+                OpTransactionError::HaltedDepositPostRegolith,
+            )),
+        )
+        .unwrap();
+        assert!(matches!(
+            output.result,
+            ExecutionResult::Halt {
+                reason: OptimismHaltReason::FailedDeposit,
+                gas_used: 100_000,
+            }
+        ));
+        assert_eq!(output.intrinsic_gas(), 21_000);
+        assert_eq!(output.gas_refunded_uncapped(), 0);
+        assert_eq!(output.priority_fee_paid(), U256::ZERO);
+        assert_eq!(output.state[&caller].info.nonce, 1);
+    }
+
+    #[test]
+    fn test_deposit_zero_gas_price() {
+        let caller = Address::with_last_byte(0xa0);
+        let coinbase = Address::with_last_byte(0xc0);
+
+        let mut evm = Evm::<TestMemOpWiring>::builder()
+            .with_default_db()
+            .with_default_ext_ctx()
+            .modify_block_env(|block| {
+                block.basefee = U256::from(1_000);
+                block.coinbase = coinbase;
+            })
+            .with_tx_env(OpTransaction::Deposit(TxDeposit {
+                from: caller,
+                to: TxKind::Call(Address::with_last_byte(0xa1)),
+                mint: Some(1_000),
+                gas_limit: 100_000,
+                ..Default::default()
+            }))
+            .build();
+
+        let ResultAndState { result, state, .. } = evm.transact().unwrap();
+        assert!(result.is_success());
+        assert_eq!(result.gas_used(), 21_000);
+        // The caller keeps the minted value, gas is neither charged nor reimbursed.
+        assert_eq!(state[&caller].info.balance, U256::from(1_000));
+        let coinbase_balance = state.get(&coinbase).map(|account| account.info.balance);
+        assert_eq!(coinbase_balance.unwrap_or_default(), U256::ZERO);
+    }
+
+    #[test]
+    fn test_system_deposit() {
+        let tx = TxDeposit {
+            from: Address::with_last_byte(0xa0),
+            to: TxKind::Call(Address::with_last_byte(0xa1)),
+            gas_limit: 100_000,
+            is_system_transaction: true,
+            ..Default::default()
+        };
+
+        // System transactions don't report any gas used before Regolith.
+        let mut evm = Evm::<TestMemOpWiring>::builder()
+            .with_default_db()
+            .with_default_ext_ctx()
+            .with_tx_env(OpTransaction::Deposit(tx.clone()))
+            .with_spec_id(OptimismSpecId::BEDROCK)
+            .build();
+        let result = evm.transact().unwrap().result;
+        assert!(result.is_success());
+        assert_eq!(result.gas_used(), 0);
+
+        // And are rejected after it.
+        let mut evm = Evm::<TestMemOpWiring>::builder()
+            .with_default_db()
+            .with_default_ext_ctx()
+            .with_tx_env(OpTransaction::Deposit(tx))
+            .with_spec_id(OptimismSpecId::REGOLITH)
+            .build();
+        assert_eq!(
+            evm.transact(),
+            Err(EVMError::Transaction(
+                OpTransactionError::DepositSystemTxPostRegolith
+            ))
+        );
+    }
+
+    #[test]
+    fn test_load_precompiles_p256verify() {
+        let p256verify = secp256r1::P256VERIFY.0;
+
+        let precompiles = load_precompiles::<TestEmptyOpWiring, EcotoneSpec>();
+        assert!(!precompiles.contains(&p256verify));
+
+        let precompiles = load_precompiles::<TestEmptyOpWiring, FjordSpec>();
+        assert!(precompiles.contains(&p256verify));
+
+        let precompiles = load_precompiles::<TestEmptyOpWiring, LatestSpec>();
+        assert!(precompiles.contains(&p256verify));
+    }
+
+    #[test]
+    fn test_inspector_observes_deposit_source_hash() {
+        #[derive(Default)]
+        struct SourceHashInspector {
+            source_hash: Option<B256>,
+        }
+
+        impl<EvmWiringT: OptimismWiring> Inspector<EvmWiringT> for SourceHashInspector {
+            fn transaction_start(&mut self, context: &mut EvmContext<EvmWiringT>) {
+                self.source_hash = context.env.tx.source_hash();
+            }
+        }
+
+        let source_hash = B256::repeat_byte(0x42);
+        let mut evm = Evm::<OptimismEvmWiring<InMemoryDB, SourceHashInspector>>::builder()
+            .with_default_db()
+            .with_default_ext_ctx()
+            .with_tx_env(OpTransaction::Deposit(TxDeposit {
+                source_hash,
+                from: Address::with_last_byte(1),
+                to: TxKind::Call(Address::with_last_byte(2)),
+                gas_limit: 100_000,
+                ..Default::default()
+            }))
+            .append_handler_register(inspector_handle_register)
+            .build();
+
+        let result = evm.transact().unwrap();
+        assert!(result.result.is_success());
+        assert_eq!(evm.context.external.source_hash, Some(source_hash));
+    }
 }
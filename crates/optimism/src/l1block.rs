@@ -31,6 +31,34 @@ const ECOTONE_L1_FEE_SCALARS_SLOT: U256 = U256::from_limbs([3u64, 0, 0, 0]);
 /// An empty 64-bit set of scalar values.
 const EMPTY_SCALARS: [u8; 8] = [0u8; 8];
 
+/// Precomputed data of an enveloped transaction used to compute its L1 cost.
+///
+/// Lets the L1 cost be computed when the raw envelope is not available, see
+/// [`L1BlockInfo::calculate_tx_l1_cost_from_rollup_data`]. The zero and non-zero byte counts are
+/// used before Fjord and the FastLZ compressed size since Fjord.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RollupDataGas {
+    /// Number of zero bytes of the envelope.
+    pub zero_bytes: u64,
+    /// Number of non-zero bytes of the envelope.
+    pub non_zero_bytes: u64,
+    /// FastLZ compressed size of the envelope.
+    pub fastlz_size: u64,
+}
+
+impl RollupDataGas {
+    /// Computes the rollup data of the given enveloped transaction.
+    pub fn from_envelope(input: &[u8]) -> Self {
+        let zero_bytes = input.iter().filter(|&&byte| byte == 0).count() as u64;
+        Self {
+            zero_bytes,
+            non_zero_bytes: input.len() as u64 - zero_bytes,
+            fastlz_size: flz_compress_len(input) as u64,
+        }
+    }
+}
+
 /// The address of L1 fee recipient.
 pub const L1_FEE_RECIPIENT: Address = address!("420000000000000000000000000000000000001A");
 
@@ -134,20 +162,24 @@ impl L1BlockInfo {
     /// account for the empty signature.
     pub fn data_gas(&self, input: &[u8], spec_id: OptimismSpecId) -> U256 {
         if spec_id.is_enabled_in(OptimismSpecId::FJORD) {
-            let estimated_size = self.tx_estimated_size_fjord(input);
+            return Self::data_gas_fjord(flz_compress_len(input) as u64);
+        }
+        let zero_bytes = input.iter().filter(|&&byte| byte == 0).count() as u64;
+        Self::data_gas_pre_fjord(zero_bytes, input.len() as u64 - zero_bytes, spec_id)
+    }
 
-            return estimated_size
-                .saturating_mul(U256::from(NON_ZERO_BYTE_COST))
-                .wrapping_div(U256::from(1_000_000));
-        };
+    fn data_gas_fjord(fastlz_size: u64) -> U256 {
+        Self::tx_estimated_size_fjord(fastlz_size)
+            .saturating_mul(U256::from(NON_ZERO_BYTE_COST))
+            .wrapping_div(U256::from(1_000_000))
+    }
 
-        let mut rollup_data_gas_cost = U256::from(input.iter().fold(0, |acc, byte| {
-            acc + if *byte == 0x00 {
-                ZERO_BYTE_COST
-            } else {
-                NON_ZERO_BYTE_COST
-            }
-        }));
+    fn data_gas_pre_fjord(zero_bytes: u64, non_zero_bytes: u64, spec_id: OptimismSpecId) -> U256 {
+        let mut rollup_data_gas_cost = U256::from(zero_bytes)
+            .saturating_mul(U256::from(ZERO_BYTE_COST))
+            .saturating_add(
+                U256::from(non_zero_bytes).saturating_mul(U256::from(NON_ZERO_BYTE_COST)),
+            );
 
         // Prior to regolith, an extra 68 non zero bytes were included in the rollup data costs.
         if !spec_id.is_enabled_in(OptimismSpecId::REGOLITH) {
@@ -160,10 +192,8 @@ impl L1BlockInfo {
     // Calculate the estimated compressed transaction size in bytes, scaled by 1e6.
     // This value is computed based on the following formula:
     // max(minTransactionSize, intercept + fastlzCoef*fastlzSize)
-    fn tx_estimated_size_fjord(&self, input: &[u8]) -> U256 {
-        let fastlz_size = U256::from(flz_compress_len(input));
-
-        fastlz_size
+    fn tx_estimated_size_fjord(fastlz_size: u64) -> U256 {
+        U256::from(fastlz_size)
             .saturating_mul(U256::from(836_500))
             .saturating_sub(U256::from(42_585_600))
             .max(U256::from(100_000_000))
@@ -177,17 +207,50 @@ impl L1BlockInfo {
         }
 
         if spec_id.is_enabled_in(OptimismSpecId::FJORD) {
-            self.calculate_tx_l1_cost_fjord(input)
-        } else if spec_id.is_enabled_in(OptimismSpecId::ECOTONE) {
-            self.calculate_tx_l1_cost_ecotone(input, spec_id)
+            self.calculate_tx_l1_cost_fjord(flz_compress_len(input) as u64)
         } else {
-            self.calculate_tx_l1_cost_bedrock(input, spec_id)
+            self.calculate_tx_l1_cost_pre_fjord(self.data_gas(input, spec_id), spec_id)
+        }
+    }
+
+    /// Calculate the gas cost of a transaction from its precomputed [RollupDataGas], for when
+    /// the enveloped transaction is not available.
+    ///
+    /// Gives the same cost as [`Self::calculate_tx_l1_cost`] with the envelope the data was
+    /// computed from. As deposits can't be told apart from the data, it must not be used for
+    /// deposit transactions.
+    pub fn calculate_tx_l1_cost_from_rollup_data(
+        &self,
+        data: &RollupDataGas,
+        spec_id: OptimismSpecId,
+    ) -> U256 {
+        if data.zero_bytes == 0 && data.non_zero_bytes == 0 {
+            return U256::ZERO;
+        }
+
+        if spec_id.is_enabled_in(OptimismSpecId::FJORD) {
+            self.calculate_tx_l1_cost_fjord(data.fastlz_size)
+        } else {
+            let data_gas = Self::data_gas_pre_fjord(data.zero_bytes, data.non_zero_bytes, spec_id);
+            self.calculate_tx_l1_cost_pre_fjord(data_gas, spec_id)
+        }
+    }
+
+    /// Calculate the gas cost of a transaction with the given rollup data gas, pre-Fjord.
+    fn calculate_tx_l1_cost_pre_fjord(
+        &self,
+        rollup_data_gas_cost: U256,
+        spec_id: OptimismSpecId,
+    ) -> U256 {
+        if spec_id.is_enabled_in(OptimismSpecId::ECOTONE) {
+            self.calculate_tx_l1_cost_ecotone(rollup_data_gas_cost)
+        } else {
+            self.calculate_tx_l1_cost_bedrock(rollup_data_gas_cost)
         }
     }
 
     /// Calculate the gas cost of a transaction based on L1 block data posted on L2, pre-Ecotone.
-    fn calculate_tx_l1_cost_bedrock(&self, input: &[u8], spec_id: OptimismSpecId) -> U256 {
-        let rollup_data_gas_cost = self.data_gas(input, spec_id);
+    fn calculate_tx_l1_cost_bedrock(&self, rollup_data_gas_cost: U256) -> U256 {
         rollup_data_gas_cost
             .saturating_add(self.l1_fee_overhead.unwrap_or_default())
             .saturating_mul(self.l1_base_fee)
@@ -205,15 +268,14 @@ impl L1BlockInfo {
     ///
     /// Function is actually computed as follows for better precision under integer arithmetic:
     /// `calldataGas*(l1BaseFee*16*l1BaseFeeScalar + l1BlobBaseFee*l1BlobBaseFeeScalar)/16e6`
-    fn calculate_tx_l1_cost_ecotone(&self, input: &[u8], spec_id: OptimismSpecId) -> U256 {
+    fn calculate_tx_l1_cost_ecotone(&self, rollup_data_gas_cost: U256) -> U256 {
         // There is an edgecase where, for the very first Ecotone block (unless it is activated at Genesis), we must
         // use the Bedrock cost function. To determine if this is the case, we can check if the Ecotone parameters are
         // unset.
         if self.empty_scalars {
-            return self.calculate_tx_l1_cost_bedrock(input, spec_id);
+            return self.calculate_tx_l1_cost_bedrock(rollup_data_gas_cost);
         }
 
-        let rollup_data_gas_cost = self.data_gas(input, spec_id);
         let l1_fee_scaled = self.calculate_l1_fee_scaled_ecotone();
 
         l1_fee_scaled
@@ -225,9 +287,9 @@ impl L1BlockInfo {
     ///
     /// [OptimismSpecId::FJORD] L1 cost function:
     /// `estimatedSize*(baseFeeScalar*l1BaseFee*16 + blobFeeScalar*l1BlobBaseFee)/1e12`
-    fn calculate_tx_l1_cost_fjord(&self, input: &[u8]) -> U256 {
+    fn calculate_tx_l1_cost_fjord(&self, fastlz_size: u64) -> U256 {
         let l1_fee_scaled = self.calculate_l1_fee_scaled_ecotone();
-        let estimated_size = self.tx_estimated_size_fjord(input);
+        let estimated_size = Self::tx_estimated_size_fjord(fastlz_size);
 
         estimated_size
             .saturating_mul(l1_fee_scaled)
@@ -412,7 +474,7 @@ mod tests {
 
         assert_eq!(gas_used, expected_l1_gas_used);
 
-        let l1_fee = l1_block_info.calculate_tx_l1_cost_ecotone(TX, OptimismSpecId::ECOTONE);
+        let l1_fee = l1_block_info.calculate_tx_l1_cost(TX, OptimismSpecId::ECOTONE);
 
         assert_eq!(l1_fee, expected_l1_fee)
     }
@@ -494,8 +556,39 @@ mod tests {
 
         assert_eq!(data_gas, expected_data_gas);
 
-        let l1_fee = l1_block_info.calculate_tx_l1_cost_fjord(TX);
+        let l1_fee = l1_block_info.calculate_tx_l1_cost(TX, OptimismSpecId::FJORD);
 
         assert_eq!(l1_fee, expected_l1_fee)
     }
+
+    #[test]
+    fn calculate_tx_l1_cost_from_rollup_data() {
+        let l1_block_info = L1BlockInfo {
+            l1_base_fee: U256::from(1_000),
+            l1_fee_overhead: Some(U256::from(1_000)),
+            l1_base_fee_scalar: U256::from(1_000),
+            l1_blob_base_fee: Some(U256::from(1_000)),
+            l1_blob_base_fee_scalar: Some(U256::from(1_000)),
+            ..Default::default()
+        };
+
+        for input in [
+            bytes!(""),
+            bytes!("FACADE"),
+            bytes!("02f86c0a00800001020000"),
+        ] {
+            let data = RollupDataGas::from_envelope(&input);
+            for spec_id in [
+                OptimismSpecId::BEDROCK,
+                OptimismSpecId::REGOLITH,
+                OptimismSpecId::ECOTONE,
+                OptimismSpecId::FJORD,
+            ] {
+                assert_eq!(
+                    l1_block_info.calculate_tx_l1_cost_from_rollup_data(&data, spec_id),
+                    l1_block_info.calculate_tx_l1_cost(&input, spec_id),
+                );
+            }
+        }
+    }
 }
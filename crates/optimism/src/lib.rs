@@ -19,7 +19,9 @@ pub use handler_register::{
     optimism_handle_register, output, refund, reward_beneficiary, validate_env,
     validate_tx_against_state,
 };
-pub use l1block::{L1BlockInfo, BASE_FEE_RECIPIENT, L1_BLOCK_CONTRACT, L1_FEE_RECIPIENT};
+pub use l1block::{
+    L1BlockInfo, RollupDataGas, BASE_FEE_RECIPIENT, L1_BLOCK_CONTRACT, L1_FEE_RECIPIENT,
+};
 pub use result::OptimismHaltReason;
 pub use spec::*;
 pub use transaction::{error::OpTransactionError, OpTransaction, OpTransactionType};
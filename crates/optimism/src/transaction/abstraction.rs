@@ -1,7 +1,7 @@
 use super::deposit::{DepositTransaction, TxDeposit};
-use crate::OpTransactionError;
+use crate::{l1block::RollupDataGas, OpTransactionError};
 use revm::{
-    primitives::Bytes,
+    primitives::{Bytes, B256},
     transaction::{CommonTxFields, Transaction, TransactionType},
     wiring::default::TxEnv,
 };
@@ -12,6 +12,24 @@ pub trait OpTxTrait: Transaction {
     fn deposit(&self) -> &Self::DepositTx;
 
     fn enveloped_tx(&self) -> Option<&Bytes>;
+
+    /// Returns the precomputed rollup data used to compute the L1 cost when there is no
+    /// enveloped transaction.
+    ///
+    /// By default it is `None`.
+    fn rollup_data_gas(&self) -> Option<&RollupDataGas> {
+        None
+    }
+
+    /// Returns the source hash of the deposit, or `None` if this is not a deposit transaction.
+    ///
+    /// Tracers can use it to correlate a deposit execution with its L1 origin, e.g. in
+    /// `Inspector::transaction_start`.
+    ///
+    /// By default it is `None`.
+    fn source_hash(&self) -> Option<B256> {
+        None
+    }
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
@@ -42,6 +60,9 @@ pub enum OpTransaction<T: Transaction> {
         /// opposed to requiring downstream apps to compute the cost
         /// externally.
         enveloped_tx: Option<Bytes>,
+        /// Rollup data of the enveloped transaction. Used to compute the L1 tx cost when
+        /// `enveloped_tx` is not set.
+        rollup_data_gas: Option<RollupDataGas>,
     },
     Deposit(TxDeposit),
 }
@@ -51,6 +72,7 @@ impl Default for OpTransaction<TxEnv> {
         Self::Base {
             tx: TxEnv::default(),
             enveloped_tx: None,
+            rollup_data_gas: None,
         }
     }
 }
@@ -89,14 +111,15 @@ impl<T: Transaction> Transaction for OpTransaction<T> {
     fn kind(&self) -> revm::primitives::TxKind {
         match self {
             Self::Base { tx, .. } => tx.kind(),
-            Self::Deposit(deposit) => deposit.to,
+            Self::Deposit(deposit) => deposit.to(),
         }
     }
 
     fn effective_gas_price(&self, base_fee: revm::primitives::U256) -> revm::primitives::U256 {
         match self {
             Self::Base { tx, .. } => tx.effective_gas_price(base_fee),
-            Self::Deposit(_) => base_fee,
+            // Deposits don't pay for gas, it is bought on L1.
+            Self::Deposit(_) => revm::primitives::U256::ZERO,
         }
     }
 
@@ -107,6 +130,13 @@ impl<T: Transaction> Transaction for OpTransaction<T> {
         }
     }
 
+    fn access_list(&self) -> Option<&Self::AccessList> {
+        match self {
+            Self::Base { tx, .. } => tx.access_list(),
+            Self::Deposit(_) => None,
+        }
+    }
+
     fn legacy(&self) -> &Self::Legacy {
         let Self::Base { tx, .. } = self else {
             panic!("Not a legacy transaction")
@@ -159,4 +189,46 @@ impl<T: Transaction> OpTxTrait for OpTransaction<T> {
             Self::Deposit(_) => None,
         }
     }
+
+    fn rollup_data_gas(&self) -> Option<&RollupDataGas> {
+        match self {
+            Self::Base {
+                rollup_data_gas, ..
+            } => rollup_data_gas.as_ref(),
+            Self::Deposit(_) => None,
+        }
+    }
+
+    fn source_hash(&self) -> Option<B256> {
+        match self {
+            Self::Base { .. } => None,
+            Self::Deposit(deposit) => Some(deposit.source_hash),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use revm::primitives::{Address, TxKind};
+
+    #[test]
+    fn test_tx_kind() {
+        let create = OpTransaction::Base {
+            tx: TxEnv {
+                transact_to: TxKind::Create,
+                ..Default::default()
+            },
+            enveloped_tx: None,
+            rollup_data_gas: None,
+        };
+        assert_eq!(create.kind(), TxKind::Create);
+
+        let to = TxKind::Call(Address::with_last_byte(0x42));
+        let deposit = OpTransaction::<TxEnv>::Deposit(TxDeposit {
+            to,
+            ..Default::default()
+        });
+        assert_eq!(deposit.kind(), to);
+    }
 }
@@ -26,7 +26,7 @@ pub const ADDRESS: u64 = 0x10;
 /// See also: <https://eips.ethereum.org/EIPS/eip-2537#abi-for-g2-multiexponentiation>
 pub(super) fn g2_msm(input: &Bytes, gas_limit: u64) -> PrecompileResult {
     let input_len = input.len();
-    if input_len == 0 || input_len % g2_mul::INPUT_LENGTH != 0 {
+    if input_len == 0 || !input_len.is_multiple_of(g2_mul::INPUT_LENGTH) {
         return Err(PrecompileError::Other(format!(
             "G2MSM input length should be multiple of {}, was {}",
             g2_mul::INPUT_LENGTH,
@@ -137,7 +137,9 @@ impl Precompile {
 /// Precompile errors.
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub enum PrecompileErrors {
+    /// The call failed and consumes all of its gas, see the [crate] documentation.
     Error(PrecompileError),
+    /// The precompile could not run, the transaction is aborted.
     Fatal { msg: String },
 }
 
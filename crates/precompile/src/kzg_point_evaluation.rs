@@ -126,4 +126,94 @@ mod tests {
         assert_eq!(output.gas_used, gas);
         assert_eq!(output.bytes[..], expected_output);
     }
+
+    /// Returns compressed `[scalar]G1`.
+    #[cfg(all(feature = "c-kzg", feature = "blst"))]
+    fn g1_mul(scalar: u64) -> [u8; 48] {
+        use blst::{blst_p1, blst_p1_compress, blst_p1_generator, blst_p1_mult};
+
+        let mut point = blst_p1::default();
+        let mut out = [0u8; 48];
+        unsafe {
+            blst_p1_mult(
+                &mut point,
+                blst_p1_generator(),
+                scalar.to_le_bytes().as_ptr(),
+                64,
+            );
+            blst_p1_compress(out.as_mut_ptr(), &point);
+        }
+        out
+    }
+
+    /// Builds a toy trusted setup with secret `tau`.
+    ///
+    /// Only `[1]G2` and `[tau]G2` take part in proof verification, so G1 points are just distinct
+    /// multiples of the generator which keeps them from looking like a monomial setup.
+    #[cfg(all(feature = "c-kzg", feature = "blst"))]
+    fn custom_kzg_settings(tau: u64) -> KzgSettings {
+        use blst::{blst_p2, blst_p2_compress, blst_p2_generator, blst_p2_mult};
+        use c_kzg::{BYTES_PER_G2_POINT, FIELD_ELEMENTS_PER_BLOB};
+
+        let g1 = (1..=FIELD_ELEMENTS_PER_BLOB as u64)
+            .map(g1_mul)
+            .collect::<Vec<_>>();
+
+        let mut point = unsafe { *blst_p2_generator() };
+        let g2 = (0..65)
+            .map(|_| {
+                let mut out = [0u8; BYTES_PER_G2_POINT];
+                unsafe {
+                    blst_p2_compress(out.as_mut_ptr(), &point);
+                    let mut next = blst_p2::default();
+                    blst_p2_mult(&mut next, &point, tau.to_le_bytes().as_ptr(), 64);
+                    point = next;
+                }
+                out
+            })
+            .collect::<Vec<_>>();
+
+        KzgSettings::load_trusted_setup(&g1, &g2).unwrap()
+    }
+
+    #[test]
+    #[cfg(all(feature = "c-kzg", feature = "blst"))]
+    fn custom_trusted_setup() {
+        use std::sync::Arc;
+        use wiring::kzg::EnvKzgSettings;
+
+        // Toy setup with tau = 5 and polynomial p(X) = 3 + 2X opened at z = 7:
+        // commitment = [p(tau)]G1 = [13]G1, y = p(z) = 17, proof = [(p(X) - y) / (X - z)]G1 = [2]G1.
+        let commitment = g1_mul(13).to_vec();
+        let proof = g1_mul(2).to_vec();
+        let versioned_hash = kzg_to_versioned_hash(&commitment).to_vec();
+        let mut z = [0u8; 32];
+        z[31] = 7;
+        let mut y = [0u8; 32];
+        y[31] = 17;
+
+        let input: Bytes = [versioned_hash, z.to_vec(), y.to_vec(), commitment, proof]
+            .concat()
+            .into();
+
+        // The mainnet setup must reject a proof made for another ceremony.
+        assert_eq!(
+            run(&input, GAS_COST, &CfgEnv::default()),
+            Err(PrecompileError::BlobVerifyKzgProofFailed.into())
+        );
+
+        let cfg = CfgEnv::default()
+            .with_kzg_settings(EnvKzgSettings::Custom(Arc::new(custom_kzg_settings(5))));
+        let output = run(&input, GAS_COST, &cfg).unwrap();
+        assert_eq!(output.gas_used, GAS_COST);
+        assert_eq!(output.bytes[..], RETURN_VALUE[..]);
+
+        // Versioned hash is still checked against the commitment.
+        let mut mismatched = input.to_vec();
+        mismatched[1] ^= 1;
+        assert_eq!(
+            run(&mismatched.into(), GAS_COST, &cfg),
+            Err(PrecompileError::BlobMismatchedVersion.into())
+        );
+    }
 }
@@ -1,6 +1,23 @@
 //! # revm-precompile
 //!
 //! Implementations of EVM precompiled contracts.
+//!
+//! ## Failure modes
+//!
+//! Precompiles reject invalid input in one of two ways, as defined by their specification:
+//!
+//! - Returning empty output: the call succeeds and is charged the precompile's gas cost.
+//!   `ecrecover` (`0x01`) does so for invalid signatures, `p256verify` (`0x100`) for invalid
+//!   signatures and inputs of the wrong length.
+//! - Returning a [`PrecompileError`]: the call fails and consumes all of its gas. `modexp`
+//!   (`0x05`) does so for lengths that do not fit in a `usize`, the alt_bn128 precompiles
+//!   (`0x06` to `0x08`) for points not on the curve and pairing inputs of the wrong length,
+//!   `blake2f` (`0x09`) for inputs of the wrong length or final block flag, the KZG point
+//!   evaluation (`0x0A`) for inputs of the wrong length or an invalid proof and the BLS12-381
+//!   precompiles (`0x0B` to `0x13`) for any invalid input.
+//!
+//! `sha256` (`0x02`), `ripemd160` (`0x03`) and `identity` (`0x04`) accept any input. All
+//! precompiles return [`PrecompileError::OutOfGas`] if the gas limit is below their cost.
 #![cfg_attr(not(test), warn(unused_crate_dependencies))]
 #![cfg_attr(not(feature = "std"), no_std)]
 
@@ -40,7 +57,7 @@ use once_cell::race::OnceBox;
 use std::{boxed::Box, vec::Vec};
 
 pub fn calc_linear_cost_u32(len: usize, base: u64, word: u64) -> u64 {
-    (len as u64 + 32 - 1) / 32 * word + base
+    (len as u64).div_ceil(32) * word + base
 }
 
 #[derive(Clone, Default, Debug)]
@@ -308,3 +325,79 @@ pub const fn u64_to_address(x: u64) -> Address {
         0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, x[0], x[1], x[2], x[3], x[4], x[5], x[6], x[7],
     ])
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use primitives::U256;
+    use wiring::default::CfgEnv;
+
+    /// How a precompile handles invalid input.
+    #[derive(Debug)]
+    enum Failure {
+        /// Returns empty output.
+        Empty,
+        /// Returns a non fatal error.
+        Error,
+        /// Accepts any input.
+        Infallible,
+    }
+
+    fn check(precompile: &Precompile, input: &[u8], failure: &Failure) {
+        let result = precompile.call_ref(
+            &Bytes::copy_from_slice(input),
+            30_000_000,
+            &CfgEnv::default(),
+        );
+        match (failure, result) {
+            (Failure::Empty, Ok(output)) => {
+                assert!(output.bytes.is_empty());
+                assert_ne!(output.gas_used, 0);
+            }
+            (Failure::Error, Err(PrecompileErrors::Error(e))) => assert!(!e.is_oog()),
+            (Failure::Infallible, Ok(_)) => {}
+            (failure, result) => panic!("expected {failure:?}, got {result:?}"),
+        }
+    }
+
+    #[test]
+    fn failure_modes() {
+        // A point not on the alt_bn128 curve.
+        let mut bn128_point = [0; 64];
+        bn128_point[31] = 1;
+        bn128_point[63] = 1;
+        let mut bn128_mul = bn128_point.to_vec();
+        bn128_mul.extend_from_slice(&[0; 31]);
+        bn128_mul.push(2);
+        // A base length that does not fit in a `usize`.
+        let modexp = (U256::from(1) << 64usize).to_be_bytes::<32>();
+
+        let mut matrix: Vec<(u64, &[u8], Failure)> = vec![
+            (0x01, &[0; 128], Failure::Empty),
+            (0x02, &[1, 2, 3], Failure::Infallible),
+            (0x03, &[1, 2, 3], Failure::Infallible),
+            (0x04, &[1, 2, 3], Failure::Infallible),
+            (0x05, &modexp, Failure::Error),
+            (0x06, &bn128_point, Failure::Error),
+            (0x07, &bn128_mul, Failure::Error),
+            (0x08, &[0; 100], Failure::Error),
+            (0x09, &[], Failure::Error),
+        ];
+        if cfg!(any(feature = "c-kzg", feature = "kzg-rs")) {
+            matrix.push((0x0a, &[], Failure::Error));
+        }
+        if cfg!(feature = "blst") {
+            matrix.extend((0x0b..=0x13).map(|address| (address, &[][..], Failure::Error)));
+        }
+
+        let precompiles = Precompiles::latest();
+        assert_eq!(precompiles.len(), matrix.len());
+        for (address, input, failure) in &matrix {
+            let precompile = precompiles.get(&u64_to_address(*address)).unwrap();
+            check(precompile, input, failure);
+        }
+
+        #[cfg(feature = "secp256r1")]
+        check(&secp256r1::P256VERIFY.1, &[0; 160], &Failure::Empty);
+    }
+}
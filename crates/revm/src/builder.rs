@@ -1,6 +1,7 @@
 use crate::{handler::register, Context, Evm, EvmContext, EvmWiring, Handler};
 use core::marker::PhantomData;
 use database_interface::EmptyDB;
+use interpreter::table::Instruction;
 use std::boxed::Box;
 use transaction::Transaction;
 use wiring::{
@@ -347,6 +348,22 @@ impl<'a, BuilderStage, EvmWiringT: EvmWiring> EvmBuilder<'a, BuilderStage, EvmWi
         self
     }
 
+    /// Replaces the instruction of `opcode` in the instruction table, e.g. to give an opcode
+    /// custom semantics without building a new table.
+    ///
+    /// The override is a handler register, so it is reapplied when the spec changes and is
+    /// ordered with the other registers: an override appended after an inspector register
+    /// replaces the inspector's wrapper of that opcode.
+    pub fn override_instruction(
+        self,
+        opcode: u8,
+        instruction: Instruction<Context<EvmWiringT>>,
+    ) -> EvmBuilder<'a, BuilderStage, EvmWiringT> {
+        self.append_handler_register_box(Box::new(move |handler| {
+            handler.instruction_table.insert(opcode, instruction)
+        }))
+    }
+
     /// Allows modification of Evm Database.
     pub fn modify_db(mut self, f: impl FnOnce(&mut EvmWiringT::Database)) -> Self {
         f(self.database.as_mut().unwrap());
@@ -467,10 +484,10 @@ mod test {
 
     use crate::{Context, Evm};
     use alloc::{boxed::Box, rc::Rc};
-    use bytecode::Bytecode;
+    use bytecode::{opcode, Bytecode};
     use core::cell::RefCell;
     use database::InMemoryDB;
-    use interpreter::Interpreter;
+    use interpreter::{instructions, Interpreter};
     use primitives::{address, TxKind, U256};
     use state::AccountInfo;
     use wiring::EthereumWiring;
@@ -532,6 +549,43 @@ mod test {
         assert_eq!(*custom_context.inner.borrow(), 1);
     }
 
+    #[test]
+    fn override_instruction() {
+        // ADD two and three, then store the result in slot zero.
+        let code = Bytecode::new_raw(
+            [
+                opcode::PUSH1,
+                0x02,
+                opcode::PUSH1,
+                0x03,
+                opcode::ADD,
+                opcode::PUSH1,
+                0x00,
+                opcode::SSTORE,
+                opcode::STOP,
+            ]
+            .into(),
+        );
+        let to_addr = address!("ffffffffffffffffffffffffffffffffffffffff");
+
+        let mut evm = Evm::<EthereumWiring<InMemoryDB, ()>>::builder()
+            .with_default_db()
+            .with_default_ext_ctx()
+            .modify_db(|db| db.insert_account_info(to_addr, AccountInfo::from_bytecode(code)))
+            .modify_tx_env(|tx| {
+                tx.transact_to = TxKind::Call(to_addr);
+                tx.gas_limit = 100_000;
+            })
+            .override_instruction(opcode::ADD, instructions::arithmetic::mul)
+            .build();
+
+        let state = evm.transact().unwrap().state;
+        assert_eq!(
+            state[&to_addr].storage[&U256::ZERO].present_value,
+            U256::from(6)
+        );
+    }
+
     // #[test]
     // fn simple_add_instruction() {
     //     const CUSTOM_INSTRUCTION_COST: u64 = 133;
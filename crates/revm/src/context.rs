@@ -1,7 +1,12 @@
+mod analysis_cache;
+mod block_hash_cache;
 mod context_precompiles;
 pub(crate) mod evm_context;
 mod inner_evm_context;
+mod result_cache;
 
+pub use analysis_cache::AnalysisCache;
+pub use block_hash_cache::BlockHashCache;
 pub use context_precompiles::{
     ContextPrecompile, ContextPrecompiles, ContextStatefulPrecompile, ContextStatefulPrecompileArc,
     ContextStatefulPrecompileBox, ContextStatefulPrecompileMut,
@@ -9,12 +14,13 @@ pub use context_precompiles::{
 use derive_where::derive_where;
 pub use evm_context::EvmContext;
 pub use inner_evm_context::InnerEvmContext;
+pub use result_cache::ResultCache;
 
 use crate::EvmWiring;
 use database_interface::{Database, EmptyDB};
 use interpreter::{
-    as_u64_saturated, AccountLoad, Eip7702CodeLoad, Host, SStoreResult, SelfDestructResult,
-    StateLoad,
+    as_u64_saturated, AccountLoad, Eip7702CodeLoad, Host, InstructionResult, SStoreResult,
+    SelfDestructResult, StateLoad, TransferError,
 };
 use primitives::{Address, Bytes, Log, B256, BLOCK_HASH_HISTORY, U256};
 use std::boxed::Box;
@@ -121,6 +127,13 @@ impl<EvmWiringT: EvmWiring> Host for Context<EvmWiringT> {
             .ok()
     }
 
+    fn load_account(&mut self, address: Address) -> Option<AccountLoad> {
+        self.evm
+            .load_account_undelegated(address)
+            .map_err(|e| self.evm.error = Err(e))
+            .ok()
+    }
+
     fn balance(&mut self, address: Address) -> Option<StateLoad<U256>> {
         self.evm
             .balance(address)
@@ -142,6 +155,14 @@ impl<EvmWiringT: EvmWiring> Host for Context<EvmWiringT> {
             .ok()
     }
 
+    fn delegation_target(&mut self, address: Address) -> Option<Address> {
+        self.evm
+            .delegation_target(address)
+            .map_err(|e| self.evm.error = Err(e))
+            .ok()
+            .flatten()
+    }
+
     fn sload(&mut self, address: Address, index: U256) -> Option<StateLoad<U256>> {
         self.evm
             .sload(address, index)
@@ -161,6 +182,14 @@ impl<EvmWiringT: EvmWiring> Host for Context<EvmWiringT> {
             .ok()
     }
 
+    fn is_address_warm(&self, address: Address) -> bool {
+        self.evm.journaled_state.is_address_warm(address)
+    }
+
+    fn is_storage_warm(&self, address: Address, index: U256) -> bool {
+        self.evm.journaled_state.is_storage_warm(address, index)
+    }
+
     fn tload(&mut self, address: Address, index: U256) -> U256 {
         self.evm.tload(address, index)
     }
@@ -185,4 +214,53 @@ impl<EvmWiringT: EvmWiring> Host for Context<EvmWiringT> {
             .map_err(|e| self.evm.error = Err(e))
             .ok()
     }
+
+    fn transfer(&mut self, from: Address, to: Address, value: U256) -> Result<(), TransferError> {
+        match self
+            .evm
+            .inner
+            .journaled_state
+            .transfer(&from, &to, value, &mut self.evm.inner.db)
+        {
+            Ok(None) => Ok(()),
+            Ok(Some(InstructionResult::OverflowPayment)) => Err(TransferError::OverflowPayment),
+            Ok(Some(_)) => Err(TransferError::OutOfFunds),
+            Err(e) => {
+                self.evm.error = Err(e);
+                Err(TransferError::FatalExternalError)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bytecode::{opcode::STOP, Bytecode};
+    use database::InMemoryDB;
+    use primitives::address;
+    use state::AccountInfo;
+
+    #[test]
+    fn test_host_delegation_target() {
+        let delegated = address!("00000000000000000000000000000000000000a0");
+        let target = address!("00000000000000000000000000000000000000b0");
+        let contract = address!("00000000000000000000000000000000000000c0");
+
+        let mut db = InMemoryDB::default();
+        db.insert_account_info(
+            delegated,
+            AccountInfo::from_bytecode(Bytecode::new_eip7702(target)),
+        );
+        db.insert_account_info(
+            contract,
+            AccountInfo::from_bytecode(Bytecode::new_raw(vec![STOP].into())),
+        );
+        let mut context = Context::<EthereumWiring<InMemoryDB, ()>>::new_with_db(db);
+
+        assert_eq!(context.delegation_target(delegated), Some(target));
+        assert_eq!(context.delegation_target(contract), None);
+        assert_eq!(context.delegation_target(target), None);
+        assert!(context.evm.error.is_ok());
+    }
 }
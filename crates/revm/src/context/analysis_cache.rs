@@ -0,0 +1,150 @@
+use bytecode::Bytecode;
+use primitives::{HashMap, B256};
+
+/// Cache of analyzed legacy bytecode keyed by code hash.
+///
+/// Used by [`InnerEvmContext`](crate::InnerEvmContext) when
+/// [`CfgEnv::analysis_cache_size`](wiring::default::CfgEnv::analysis_cache_size) is not zero, so
+/// repeated calls to the same code reuse its jump table. As entries are keyed by the hash of the
+/// executed code, they stay valid when accounts change code or EIP-7702 delegation.
+#[derive(Clone, Debug, Default)]
+pub struct AnalysisCache {
+    entries: HashMap<B256, Bytecode>,
+    analyses: u64,
+}
+
+impl AnalysisCache {
+    /// Returns the analyzed `bytecode` of `code_hash`, analyzing it if it is not cached.
+    ///
+    /// Newly analyzed legacy bytecode is cached unless `capacity` entries are already cached.
+    pub fn get_or_analyze(
+        &mut self,
+        code_hash: B256,
+        bytecode: Bytecode,
+        capacity: usize,
+    ) -> Bytecode {
+        if !matches!(bytecode, Bytecode::LegacyRaw(_)) {
+            return bytecode;
+        }
+        if let Some(analyzed) = self.entries.get(&code_hash) {
+            return analyzed.clone();
+        }
+
+        self.analyses += 1;
+        let analyzed = bytecode.into_analyzed();
+        if self.entries.len() < capacity {
+            self.entries.insert(code_hash, analyzed.clone());
+        }
+        analyzed
+    }
+
+    /// Returns the number of cached bytecodes.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if no bytecode is cached.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the number of analyses run through the cache.
+    pub fn analyses(&self) -> u64 {
+        self.analyses
+    }
+
+    /// Removes all cached bytecodes.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::call_evm_builder;
+    use bytecode::opcode::{CALL, GAS, POP, PUSH1, PUSH20, REVERT, STOP};
+    use database::InMemoryDB;
+    use primitives::address;
+    use specification::hardfork::SpecId;
+    use state::AccountInfo;
+    use wiring::result::ExecutionResult;
+
+    #[test]
+    fn test_analysis_cache() {
+        let contract = address!("00000000000000000000000000000000000000a0");
+        let callee = address!("00000000000000000000000000000000000000a1");
+
+        // Call the callee three times.
+        let mut code = vec![];
+        for _ in 0..3 {
+            code.extend_from_slice(&[PUSH1, 0, PUSH1, 0, PUSH1, 0, PUSH1, 0, PUSH1, 0, PUSH20]);
+            code.extend_from_slice(callee.as_slice());
+            code.extend_from_slice(&[GAS, CALL, POP]);
+        }
+        code.push(STOP);
+
+        let mut db = InMemoryDB::default();
+        db.insert_account_info(
+            contract,
+            AccountInfo::from_bytecode(Bytecode::new_raw(code.into())),
+        );
+        db.insert_account_info(
+            callee,
+            AccountInfo::from_bytecode(Bytecode::new_raw(vec![PUSH1, 0, POP, STOP].into())),
+        );
+
+        let mut evm = call_evm_builder(db, (), contract, 1_000_000)
+            .with_spec_id(SpecId::CANCUN)
+            .modify_cfg_env(|cfg| cfg.analysis_cache_size = 16)
+            .build();
+
+        assert!(evm.transact().unwrap().result.is_success());
+        let cache = &evm.context.evm.analysis_cache;
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.analyses(), 2);
+
+        // The cache is kept across transactions.
+        assert!(evm.transact().unwrap().result.is_success());
+        assert_eq!(evm.context.evm.analysis_cache.analyses(), 2);
+    }
+
+    #[test]
+    fn test_analysis_cache_eip7702_delegation() {
+        let authority = address!("00000000000000000000000000000000000000a0");
+        let stop = address!("00000000000000000000000000000000000000a1");
+        let revert = address!("00000000000000000000000000000000000000a2");
+
+        let mut db = InMemoryDB::default();
+        db.insert_account_info(
+            authority,
+            AccountInfo::from_bytecode(Bytecode::new_eip7702(stop)),
+        );
+        db.insert_account_info(
+            stop,
+            AccountInfo::from_bytecode(Bytecode::new_raw(vec![STOP].into())),
+        );
+        db.insert_account_info(
+            revert,
+            AccountInfo::from_bytecode(Bytecode::new_raw(vec![PUSH1, 0, PUSH1, 0, REVERT].into())),
+        );
+
+        let mut evm = call_evm_builder(db, (), authority, 100_000)
+            .with_spec_id(SpecId::PRAGUE)
+            .modify_cfg_env(|cfg| cfg.analysis_cache_size = 16)
+            .build();
+
+        assert!(evm.transact().unwrap().result.is_success());
+
+        // Changing the delegation executes the new code.
+        evm.db_mut().insert_account_info(
+            authority,
+            AccountInfo::from_bytecode(Bytecode::new_eip7702(revert)),
+        );
+        assert!(matches!(
+            evm.transact().unwrap().result,
+            ExecutionResult::Revert { .. }
+        ));
+        assert_eq!(evm.context.evm.analysis_cache.analyses(), 2);
+    }
+}
@@ -0,0 +1,52 @@
+use primitives::{B256, BLOCK_HASH_HISTORY};
+use std::vec::Vec;
+
+/// Ring buffer of the block hashes fetched for `BLOCKHASH`, indexed by block number.
+///
+/// Used by [`InnerEvmContext::block_hash`](crate::InnerEvmContext::block_hash) so repeated
+/// lookups within a block, or across the transactions of a block, hit the database once. As it
+/// holds at most [`BLOCK_HASH_HISTORY`] hashes, the ones that can be requested, entries never
+/// collide. The cache is cleared when the current block number changes and when the database is
+/// replaced or accessed mutably through the [`Evm`](crate::Evm), code replacing
+/// [`InnerEvmContext::db`](crate::InnerEvmContext::db) directly should clear it as well.
+#[derive(Clone, Debug, Default)]
+pub struct BlockHashCache {
+    /// Block number the cached hashes were fetched for.
+    block_number: u64,
+    /// Cached `(number, hash)` pairs, allocated on first insert.
+    entries: Vec<Option<(u64, B256)>>,
+}
+
+impl BlockHashCache {
+    /// Returns the cached hash of block `number` while executing block `block_number`.
+    pub fn get(&self, block_number: u64, number: u64) -> Option<B256> {
+        if block_number != self.block_number {
+            return None;
+        }
+        match self.entries.get(Self::index(number)) {
+            Some(&Some((cached, hash))) if cached == number => Some(hash),
+            _ => None,
+        }
+    }
+
+    /// Caches the hash of block `number` fetched while executing block `block_number`.
+    pub fn insert(&mut self, block_number: u64, number: u64, hash: B256) {
+        if block_number != self.block_number {
+            self.clear();
+            self.block_number = block_number;
+        }
+        if self.entries.is_empty() {
+            self.entries.resize(BLOCK_HASH_HISTORY as usize, None);
+        }
+        self.entries[Self::index(number)] = Some((number, hash));
+    }
+
+    /// Removes all cached hashes.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    fn index(number: u64) -> usize {
+        (number % BLOCK_HASH_HISTORY) as usize
+    }
+}
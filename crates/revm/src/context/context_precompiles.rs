@@ -162,7 +162,7 @@ impl<EvmWiringT: EvmWiring> Extend<(Address, ContextPrecompile<EvmWiringT>)>
         &mut self,
         iter: T,
     ) {
-        self.to_mut().extend(iter.into_iter().map(Into::into))
+        self.to_mut().extend(iter)
     }
 }
 
@@ -1,5 +1,5 @@
 use super::inner_evm_context::InnerEvmContext;
-use crate::{ContextPrecompiles, EvmWiring, FrameOrResult, CALL_STACK_LIMIT};
+use crate::{ContextPrecompiles, EvmWiring, FrameOrResult};
 use bytecode::{Bytecode, Eof, EOF_MAGIC_BYTES};
 use core::ops::{Deref, DerefMut};
 use database_interface::Database;
@@ -10,7 +10,7 @@ use interpreter::{
     InstructionResult, Interpreter, InterpreterResult,
 };
 use precompile::PrecompileErrors;
-use primitives::{keccak256, Address, Bytes, B256};
+use primitives::{Address, Bytes, B256};
 use specification::hardfork::SpecId::{self, *};
 use std::{boxed::Box, sync::Arc};
 use wiring::{
@@ -156,10 +156,15 @@ where
         };
 
         // Check depth
-        if self.journaled_state.depth() > CALL_STACK_LIMIT {
+        if self.journaled_state.depth() > self.env.cfg.max_depth {
             return return_result(InstructionResult::CallTooDeep);
         }
 
+        // Value transfers are state changes and are not allowed in read-only mode.
+        if self.env.cfg.read_only && inputs.transfers_value() {
+            return return_result(InstructionResult::ReadOnlyViolation);
+        }
+
         // Make account warm and loaded
         let _ = self
             .inner
@@ -218,6 +223,7 @@ where
 
             let code_hash = account.info.code_hash();
             let mut bytecode = account.info.code.clone().unwrap_or_default();
+            let mut analyzed_code_hash = code_hash;
 
             // ExtDelegateCall is not allowed to call non-EOF contracts.
             if inputs.scheme.is_ext_delegate_call()
@@ -232,16 +238,17 @@ where
             }
 
             if let Bytecode::Eip7702(eip7702_bytecode) = bytecode {
-                bytecode = self
+                let delegated = &self
                     .inner
                     .journaled_state
                     .load_code(eip7702_bytecode.delegated_address, &mut self.inner.db)
                     .map_err(EVMError::Database)?
-                    .info
-                    .code
-                    .clone()
-                    .unwrap_or_default();
+                    .info;
+                analyzed_code_hash = delegated.code_hash();
+                bytecode = delegated.code.clone().unwrap_or_default();
             }
+            // Keyed by the executed code, so a changed delegation does not reuse stale analysis.
+            let bytecode = self.inner.analyze_bytecode(analyzed_code_hash, bytecode);
 
             let contract =
                 Contract::new_with_context(inputs.input.clone(), bytecode, Some(code_hash), inputs);
@@ -273,10 +280,15 @@ where
         };
 
         // Check depth
-        if self.journaled_state.depth() > CALL_STACK_LIMIT {
+        if self.journaled_state.depth() > self.env.cfg.max_depth {
             return return_error(InstructionResult::CallTooDeep);
         }
 
+        // Contract creation is not allowed in read-only mode.
+        if self.env.cfg.read_only {
+            return return_error(InstructionResult::ReadOnlyViolation);
+        }
+
         // Prague EOF
         if spec_id.is_enabled_in(PRAGUE_EOF) && inputs.init_code.starts_with(&EOF_MAGIC_BYTES) {
             return return_error(InstructionResult::CreateInitCodeStartingEF00);
@@ -300,11 +312,12 @@ where
 
         // Create address
         let mut init_code_hash = B256::ZERO;
+        let hasher = self.env.cfg.hasher();
         let created_address = match inputs.scheme {
-            CreateScheme::Create => inputs.caller.create(old_nonce),
+            CreateScheme::Create => hasher.create_address(inputs.caller, old_nonce),
             CreateScheme::Create2 { salt } => {
-                init_code_hash = keccak256(&inputs.init_code);
-                inputs.caller.create2(salt.to_be_bytes(), init_code_hash)
+                init_code_hash = hasher.hash(&inputs.init_code);
+                hasher.create2_address(inputs.caller, salt.to_be_bytes().into(), init_code_hash)
             }
         };
 
@@ -388,17 +401,26 @@ where
 
                 // Use nonce from tx to calculate address.
                 let tx = self.env.tx.common_fields();
-                let create_address = tx.caller().create(tx.nonce());
+                let create_address = self
+                    .env
+                    .cfg
+                    .hasher()
+                    .create_address(tx.caller(), tx.nonce());
 
                 (input, eof, Some(create_address))
             }
         };
 
         // Check depth
-        if self.journaled_state.depth() > CALL_STACK_LIMIT {
+        if self.journaled_state.depth() > self.env.cfg.max_depth {
             return return_error(InstructionResult::CallTooDeep);
         }
 
+        // Contract creation is not allowed in read-only mode.
+        if self.env.cfg.read_only {
+            return return_error(InstructionResult::ReadOnlyViolation);
+        }
+
         // Fetch balance of caller.
         let caller_balance = self.balance(inputs.caller)?;
 
@@ -414,7 +436,12 @@ where
         };
         let old_nonce = nonce - 1;
 
-        let created_address = created_address.unwrap_or_else(|| inputs.caller.create(old_nonce));
+        let created_address = created_address.unwrap_or_else(|| {
+            self.env
+                .cfg
+                .hasher()
+                .create_address(inputs.caller, old_nonce)
+        });
 
         // created address is not allowed to be a precompile.
         if self.precompiles.contains(&created_address) {
@@ -464,7 +491,7 @@ where
 #[cfg(any(test, feature = "test-utils"))]
 pub(crate) mod test_utils {
     use super::*;
-    use crate::journaled_state::JournaledState;
+    use crate::{journaled_state::JournaledState, AnalysisCache, BlockHashCache, ResultCache};
     use database::CacheDB;
     use database_interface::EmptyDB;
     use interpreter::CallScheme;
@@ -525,6 +552,13 @@ pub(crate) mod test_utils {
                 db,
                 chain: Default::default(),
                 error: Ok(()),
+                pending_rewards: U256::ZERO,
+                beneficiary_reward: U256::ZERO,
+                intrinsic_gas: 0,
+                gas_refunded_uncapped: 0,
+                analysis_cache: AnalysisCache::default(),
+                block_hashes: BlockHashCache::default(),
+                result_cache: ResultCache::default(),
             },
             precompiles: ContextPrecompiles::default(),
         }
@@ -542,6 +576,13 @@ pub(crate) mod test_utils {
                 db,
                 chain: Default::default(),
                 error: Ok(()),
+                pending_rewards: U256::ZERO,
+                beneficiary_reward: U256::ZERO,
+                intrinsic_gas: 0,
+                gas_refunded_uncapped: 0,
+                analysis_cache: AnalysisCache::default(),
+                block_hashes: BlockHashCache::default(),
+                result_cache: ResultCache::default(),
             },
             precompiles: ContextPrecompiles::default(),
         }
@@ -551,11 +592,11 @@ pub(crate) mod test_utils {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{Frame, JournalEntry};
+    use crate::{Frame, JournalEntry, CALL_STACK_LIMIT};
     use bytecode::Bytecode;
     use database::CacheDB;
     use database_interface::EmptyDB;
-    use primitives::{address, U256};
+    use primitives::{address, keccak256, U256};
     use state::AccountInfo;
     use std::boxed::Box;
     use test_utils::*;
@@ -624,6 +665,35 @@ mod tests {
         assert_eq!(result.interpreter_result().result, InstructionResult::Stop);
     }
 
+    #[test]
+    fn test_make_call_frame_zero_address() {
+        type CacheEthWiring = EthereumWiring<CacheDB<EmptyDB>, ()>;
+        let env = EnvWiring::<CacheEthWiring>::default();
+        let mut cdb = CacheDB::new(EmptyDB::default());
+        let caller = address!("dead10000000000000000000000000000001dead");
+        cdb.insert_account_info(caller, AccountInfo::from_balance(U256::from(10)));
+        let mut context = create_cache_db_evm_context::<CacheEthWiring>(Box::new(env), cdb);
+
+        let mut call_inputs = test_utils::create_mock_call_inputs(Address::ZERO);
+        call_inputs.caller = caller;
+        call_inputs.value = CallValue::Transfer(U256::from(5));
+        assert!(call_inputs.calls_zero_address());
+
+        // Value is transferred without running a frame.
+        let Ok(FrameOrResult::Result(result)) = context.make_call_frame(&call_inputs) else {
+            panic!("Expected FrameOrResult::Result");
+        };
+        assert_eq!(result.interpreter_result().result, InstructionResult::Stop);
+        assert_eq!(
+            context.journaled_state.state[&Address::ZERO].info.balance,
+            U256::from(5)
+        );
+        assert_eq!(
+            context.journaled_state.state[&caller].info.balance,
+            U256::from(5)
+        );
+    }
+
     #[test]
     fn test_make_call_frame_succeeds() {
         type CacheEthWiring = EthereumWiring<CacheDB<EmptyDB>, ()>;
@@ -650,4 +720,119 @@ mod tests {
         };
         assert_eq!(call_frame.return_memory_range, 0..0,);
     }
+
+    #[test]
+    fn test_make_call_frame_max_depth() {
+        type CacheEthWiring = EthereumWiring<CacheDB<EmptyDB>, ()>;
+        let mut env = EnvWiring::<CacheEthWiring>::default();
+        env.cfg.max_depth = 10;
+        let cdb = CacheDB::new(EmptyDB::default());
+        let bal = U256::from(3_000_000_000_u128);
+        let mut context =
+            create_cache_db_evm_context_with_balance::<CacheEthWiring>(Box::new(env), cdb, bal);
+        let contract = address!("dead10000000000000000000000000000001dead");
+        let call_inputs = test_utils::create_mock_call_inputs(contract);
+
+        // At the limit the call still executes.
+        context.journaled_state.depth = 10;
+        let Ok(FrameOrResult::Result(result)) = context.make_call_frame(&call_inputs) else {
+            panic!("Expected FrameOrResult::Result");
+        };
+        assert_eq!(result.interpreter_result().result, InstructionResult::Stop);
+
+        // One past the limit is too deep.
+        context.journaled_state.depth = 11;
+        let Ok(FrameOrResult::Result(result)) = context.make_call_frame(&call_inputs) else {
+            panic!("Expected FrameOrResult::Result");
+        };
+        assert_eq!(
+            result.interpreter_result().result,
+            InstructionResult::CallTooDeep
+        );
+    }
+
+    #[test]
+    fn test_make_create_frame_max_depth() {
+        type CacheEthWiring = EthereumWiring<CacheDB<EmptyDB>, ()>;
+        let mut env = EnvWiring::<CacheEthWiring>::default();
+        env.cfg.max_depth = 10;
+        let cdb = CacheDB::new(EmptyDB::default());
+        let bal = U256::from(3_000_000_000_u128);
+        let mut context =
+            create_cache_db_evm_context_with_balance::<CacheEthWiring>(Box::new(env), cdb, bal);
+        let create_inputs = CreateInputs {
+            caller: MOCK_CALLER,
+            scheme: CreateScheme::Create,
+            value: U256::ZERO,
+            init_code: Bytes::new(),
+            gas_limit: 100_000,
+        };
+
+        context.journaled_state.depth = 11;
+        let Ok(FrameOrResult::Result(result)) =
+            context.make_create_frame(SpecId::CANCUN, &create_inputs)
+        else {
+            panic!("Expected FrameOrResult::Result");
+        };
+        assert_eq!(
+            result.interpreter_result().result,
+            InstructionResult::CallTooDeep
+        );
+
+        context.journaled_state.depth = 10;
+        let res = context.make_create_frame(SpecId::CANCUN, &create_inputs);
+        assert!(matches!(res, Ok(FrameOrResult::Frame(Frame::Create(_)))));
+    }
+
+    /// Database counting its block hash lookups.
+    #[derive(Debug, Default)]
+    struct BlockHashCountingDB {
+        lookups: u64,
+    }
+
+    impl Database for BlockHashCountingDB {
+        type Error = core::convert::Infallible;
+
+        fn basic(&mut self, _address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+            Ok(None)
+        }
+
+        fn code_by_hash(&mut self, _code_hash: B256) -> Result<Bytecode, Self::Error> {
+            Ok(Bytecode::default())
+        }
+
+        fn storage(&mut self, _address: Address, _index: U256) -> Result<U256, Self::Error> {
+            Ok(U256::ZERO)
+        }
+
+        fn block_hash(&mut self, number: u64) -> Result<B256, Self::Error> {
+            self.lookups += 1;
+            Ok(keccak256(number.to_be_bytes()))
+        }
+    }
+
+    #[test]
+    fn test_block_hash_cache() {
+        let mut context = InnerEvmContext::<EthereumWiring<BlockHashCountingDB, ()>>::new(
+            BlockHashCountingDB::default(),
+        );
+        context.env.block.number = U256::from(100);
+
+        let hash = keccak256(90u64.to_be_bytes());
+        assert_eq!(context.block_hash(90), Ok(hash));
+        assert_eq!(context.block_hash(90), Ok(hash));
+        assert_eq!(context.db.lookups, 1);
+        assert_eq!(context.block_hash(91), Ok(keccak256(91u64.to_be_bytes())));
+        assert_eq!(context.db.lookups, 2);
+
+        // The cache is cleared when the block number advances.
+        context.env.block.number = U256::from(101);
+        assert_eq!(context.block_hash(90), Ok(hash));
+        assert_eq!(context.db.lookups, 3);
+
+        // Every lookup reads the database when the cache is disabled.
+        context.env.cfg.disable_block_hash_cache = true;
+        assert_eq!(context.block_hash(90), Ok(hash));
+        assert_eq!(context.db.lookups, 4);
+    }
 }
@@ -1,10 +1,12 @@
-use crate::{journaled_state::JournaledState, JournalCheckpoint};
+use crate::{
+    journaled_state::JournaledState, AnalysisCache, BlockHashCache, JournalCheckpoint, ResultCache,
+};
 use bytecode::{Bytecode, Eof, EOF_MAGIC_BYTES, EOF_MAGIC_HASH};
 use database_interface::Database;
 use derive_where::derive_where;
 use interpreter::{
-    gas, return_ok, AccountLoad, Eip7702CodeLoad, InstructionResult, InterpreterResult,
-    SStoreResult, SelfDestructResult, StateLoad,
+    as_u64_saturated, gas, return_ok, AccountLoad, Eip7702CodeLoad, InstructionResult,
+    InterpreterResult, SStoreResult, SelfDestructResult, StateLoad,
 };
 use primitives::{Address, Bytes, HashSet, B256, U256};
 use specification::hardfork::{
@@ -16,7 +18,7 @@ use std::{boxed::Box, sync::Arc};
 use transaction::AccessListTrait;
 use wiring::{
     default::{AnalysisKind, CfgEnv, EnvWiring},
-    EvmWiring, Transaction,
+    Block, EvmWiring, Transaction,
 };
 
 /// EVM contexts contains data that EVM needs for execution.
@@ -33,6 +35,21 @@ pub struct InnerEvmContext<EvmWiringT: EvmWiring> {
     pub chain: EvmWiringT::ChainContext,
     /// Error that happened during execution.
     pub error: Result<(), <EvmWiringT::Database as Database>::Error>,
+    /// Beneficiary rewards of the committed transactions deferred by
+    /// [`CfgEnv::is_lazy_beneficiary_reward_enabled`](wiring::default::CfgEnv::is_lazy_beneficiary_reward_enabled).
+    pub pending_rewards: U256,
+    /// Beneficiary reward of the transaction being executed, moved to its result.
+    pub beneficiary_reward: U256,
+    /// Intrinsic gas of the transaction being executed, moved to its result.
+    pub intrinsic_gas: u64,
+    /// Gas refund of the transaction being executed before it was capped, moved to its result.
+    pub gas_refunded_uncapped: u64,
+    /// Analyzed bytecode reused across calls, see [`CfgEnv::analysis_cache_size`].
+    pub analysis_cache: AnalysisCache,
+    /// Block hashes fetched for `BLOCKHASH` in the current block.
+    pub block_hashes: BlockHashCache,
+    /// Transaction results reused by [`Evm::transact_cached`](crate::Evm::transact_cached).
+    pub result_cache: ResultCache<EvmWiringT::HaltReason>,
 }
 
 impl<EvmWiringT> InnerEvmContext<EvmWiringT>
@@ -46,6 +63,13 @@ where
             db,
             chain: Default::default(),
             error: Ok(()),
+            pending_rewards: U256::ZERO,
+            beneficiary_reward: U256::ZERO,
+            intrinsic_gas: 0,
+            gas_refunded_uncapped: 0,
+            analysis_cache: AnalysisCache::default(),
+            block_hashes: BlockHashCache::default(),
+            result_cache: ResultCache::default(),
         }
     }
 }
@@ -60,6 +84,13 @@ impl<EvmWiringT: EvmWiring> InnerEvmContext<EvmWiringT> {
             db,
             chain: Default::default(),
             error: Ok(()),
+            pending_rewards: U256::ZERO,
+            beneficiary_reward: U256::ZERO,
+            intrinsic_gas: 0,
+            gas_refunded_uncapped: 0,
+            analysis_cache: AnalysisCache::default(),
+            block_hashes: BlockHashCache::default(),
+            result_cache: ResultCache::default(),
         }
     }
 
@@ -79,6 +110,13 @@ impl<EvmWiringT: EvmWiring> InnerEvmContext<EvmWiringT> {
             db,
             chain: Default::default(),
             error: Ok(()),
+            pending_rewards: U256::ZERO,
+            beneficiary_reward: U256::ZERO,
+            intrinsic_gas: 0,
+            gas_refunded_uncapped: 0,
+            analysis_cache: self.analysis_cache,
+            block_hashes: BlockHashCache::default(),
+            result_cache: ResultCache::default(),
         }
     }
 
@@ -118,6 +156,18 @@ impl<EvmWiringT: EvmWiring> InnerEvmContext<EvmWiringT> {
         &self.env.cfg
     }
 
+    /// Returns the analyzed `bytecode` of `code_hash`, reusing the [`AnalysisCache`] if
+    /// [`CfgEnv::analysis_cache_size`] is not zero.
+    #[inline]
+    pub fn analyze_bytecode(&mut self, code_hash: B256, bytecode: Bytecode) -> Bytecode {
+        match self.env.cfg.analysis_cache_size {
+            0 => bytecode.into_analyzed(),
+            capacity => self
+                .analysis_cache
+                .get_or_analyze(code_hash, bytecode, capacity),
+        }
+    }
+
     /// Returns the error by replacing it with `Ok(())`, if any.
     #[inline]
     pub fn take_error(&mut self) -> Result<(), <EvmWiringT::Database as Database>::Error> {
@@ -125,12 +175,24 @@ impl<EvmWiringT: EvmWiring> InnerEvmContext<EvmWiringT> {
     }
 
     /// Fetch block hash from database.
+    ///
+    /// Hashes are cached for the current block, see [`BlockHashCache`], unless
+    /// [`CfgEnv::disable_block_hash_cache`] is set.
     #[inline]
     pub fn block_hash(
         &mut self,
         number: u64,
     ) -> Result<B256, <EvmWiringT::Database as Database>::Error> {
-        self.db.block_hash(number)
+        if self.env.cfg.disable_block_hash_cache {
+            return self.db.block_hash(number);
+        }
+        let block_number = as_u64_saturated!(*self.env.block.number());
+        if let Some(hash) = self.block_hashes.get(block_number, number) {
+            return Ok(hash);
+        }
+        let hash = self.db.block_hash(number)?;
+        self.block_hashes.insert(block_number, number, hash);
+        Ok(hash)
     }
 
     /// Mark account as touched as only touched accounts will be added to state.
@@ -160,6 +222,21 @@ impl<EvmWiringT: EvmWiring> InnerEvmContext<EvmWiringT> {
             .load_account_delegated(address, &mut self.db)
     }
 
+    /// Load account from database to JournaledState without following its EIP-7702 delegation.
+    #[inline]
+    pub fn load_account_undelegated(
+        &mut self,
+        address: Address,
+    ) -> Result<AccountLoad, <EvmWiringT::Database as Database>::Error> {
+        let spec = self.spec_id();
+        self.journaled_state
+            .load_account(address, &mut self.db)
+            .map(|account| AccountLoad {
+                is_empty: account.state_clear_aware_is_empty(spec),
+                load: Eip7702CodeLoad::new_not_delegated((), account.is_cold),
+            })
+    }
+
     /// Return account balance and is_cold flag.
     #[inline]
     pub fn balance(
@@ -171,6 +248,21 @@ impl<EvmWiringT: EvmWiring> InnerEvmContext<EvmWiringT> {
             .map(|acc| acc.map(|a| a.info.balance))
     }
 
+    /// Returns the address the code of `address` is delegated to with EIP-7702.
+    ///
+    /// Returns `None` if the account has no delegation designator.
+    #[inline]
+    pub fn delegation_target(
+        &mut self,
+        address: Address,
+    ) -> Result<Option<Address>, <EvmWiringT::Database as Database>::Error> {
+        let account = self.journaled_state.load_code(address, &mut self.db)?;
+        Ok(match &account.info.code {
+            Some(Bytecode::Eip7702(code)) => Some(code.address()),
+            _ => None,
+        })
+    }
+
     /// Return account code bytes and if address is cold loaded.
     ///
     /// In case of EOF account it will return `EOF_MAGIC` (0xEF00) as code.
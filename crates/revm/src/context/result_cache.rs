@@ -0,0 +1,128 @@
+use primitives::{HashMap, B256};
+use wiring::{result::ResultAndState, HaltReasonTrait};
+
+/// Cache of transaction results keyed by a hash of the transaction and the pre-state root.
+///
+/// Used by [`Evm::transact_cached`](crate::Evm::transact_cached). As the EVM does not compute
+/// state roots, the key is provided by the caller and must commit to both the transaction and
+/// the state it executes on. Only successful executions are cached, up to
+/// [`CfgEnv::result_cache_size`](wiring::default::CfgEnv::result_cache_size) of them. The cache
+/// is cleared when the EVM commits a state change and when its database, block, configuration
+/// or spec is modified.
+#[derive(Clone, Debug)]
+pub struct ResultCache<HaltReasonT: HaltReasonTrait> {
+    entries: HashMap<B256, ResultAndState<HaltReasonT>>,
+    hits: u64,
+    misses: u64,
+}
+
+impl<HaltReasonT: HaltReasonTrait> Default for ResultCache<HaltReasonT> {
+    fn default() -> Self {
+        Self {
+            entries: HashMap::default(),
+            hits: 0,
+            misses: 0,
+        }
+    }
+}
+
+impl<HaltReasonT: HaltReasonTrait> ResultCache<HaltReasonT> {
+    /// Returns the cached result of `key`, counting the lookup as a hit or a miss.
+    pub fn get(&mut self, key: &B256) -> Option<&ResultAndState<HaltReasonT>> {
+        let result = self.entries.get(key);
+        if result.is_some() {
+            self.hits += 1;
+        } else {
+            self.misses += 1;
+        }
+        result
+    }
+
+    /// Caches the result of `key` unless `capacity` results are already cached.
+    pub fn insert(&mut self, key: B256, result: ResultAndState<HaltReasonT>, capacity: usize) {
+        if self.entries.len() < capacity {
+            self.entries.insert(key, result);
+        }
+    }
+
+    /// Returns the number of cached results.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if no result is cached.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the number of lookups served from the cache.
+    pub fn hits(&self) -> u64 {
+        self.hits
+    }
+
+    /// Returns the number of lookups not served from the cache, each followed by an execution.
+    pub fn misses(&self) -> u64 {
+        self.misses
+    }
+
+    /// Removes all cached results.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::call_evm_builder;
+    use bytecode::{
+        opcode::{POP, PUSH1, SLOAD, STOP},
+        Bytecode,
+    };
+    use database::InMemoryDB;
+    use primitives::address;
+    use state::AccountInfo;
+
+    #[test]
+    fn test_result_cache() {
+        let contract = address!("00000000000000000000000000000000000000a0");
+        let mut db = InMemoryDB::default();
+        db.insert_account_info(
+            contract,
+            AccountInfo::from_bytecode(Bytecode::new_raw(vec![PUSH1, 0, SLOAD, POP, STOP].into())),
+        );
+
+        let mut evm = call_evm_builder(db, (), contract, 100_000).build();
+
+        // Stands for the hash of the transaction and the state root.
+        let key = B256::repeat_byte(0x01);
+        let first = evm.transact_cached(key).unwrap();
+        assert!(first.result.is_success());
+        assert_eq!(evm.transact_cached(key).unwrap(), first);
+        let cache = &evm.context.evm.result_cache;
+        assert_eq!((cache.hits(), cache.misses()), (1, 1));
+
+        // Modifying the database invalidates the cache.
+        evm.db_mut();
+        assert!(evm.context.evm.result_cache.is_empty());
+
+        // Failed executions are not cached.
+        let failing = B256::repeat_byte(0x02);
+        evm.tx_mut().gas_limit = 21_010;
+        assert!(!evm.transact_cached(failing).unwrap().result.is_success());
+        assert!(evm.context.evm.result_cache.is_empty());
+
+        // Results past the capacity are not cached.
+        evm.cfg_mut().result_cache_size = 0;
+        evm.tx_mut().gas_limit = 100_000;
+        assert!(evm.transact_cached(key).unwrap().result.is_success());
+        assert!(evm.context.evm.result_cache.is_empty());
+
+        // Committing a state change invalidates the cache.
+        evm.cfg_mut().result_cache_size = 1;
+        evm.transact_cached(key).unwrap();
+        assert_eq!(evm.context.evm.result_cache.len(), 1);
+        evm.transact_commit().unwrap();
+        assert!(evm.context.evm.result_cache.is_empty());
+    }
+}
@@ -2,20 +2,38 @@ use crate::{
     builder::{EvmBuilder, SetGenericStage},
     handler::Handler,
     Context, ContextWithEvmWiring, EvmContext, EvmWiring, Frame, FrameOrResult, FrameResult,
-    InnerEvmContext,
+    InnerEvmContext, JournalEntry, JournaledState,
 };
 use core::fmt::{self, Debug};
 use database_interface::{Database, DatabaseCommit};
-use interpreter::{Host, InterpreterAction, NewFrameAction, SharedMemory};
+use interpreter::{Gas, Host, InterpreterAction, NewFrameAction, SharedMemory};
+use primitives::{B256, U256};
+use state::EvmState;
 use std::{boxed::Box, vec::Vec};
 use wiring::{
     default::{CfgEnv, EnvWiring},
-    result::{EVMError, EVMResult, EVMResultGeneric, ExecutionResult, ResultAndState},
-    Transaction,
+    result::{
+        EVMError, EVMErrorForChain, EVMResult, EVMResultGeneric, ExecutionResult, ResultAndState,
+    },
+    Block, Transaction,
 };
 
 /// EVM call stack limit.
-pub const CALL_STACK_LIMIT: u64 = 1024;
+pub use specification::constants::CALL_STACK_LIMIT;
+
+/// Index and result of the first failed transaction of a batch.
+type BatchFailure<HaltReasonT> = (usize, ExecutionResult<HaltReasonT>);
+
+/// Index and error of the transaction of a batch that could not be executed.
+type BatchError<EvmWiringT> = (usize, EVMErrorForChain<EvmWiringT>);
+
+/// Journal of a validated and pre-executed transaction, see [`Evm::pre_execution_snapshot`].
+#[derive(Clone, Debug)]
+pub struct PreExecutionSnapshot {
+    journaled_state: JournaledState,
+    initial_gas_spend: u64,
+    eip7702_gas_refund: i64,
+}
 
 /// EVM instance containing both internal EVM context and external context
 /// and the handler that dictates the logic of EVM (or hardfork specification).
@@ -45,10 +63,82 @@ impl<EvmWiringT: EvmWiring<Database: DatabaseCommit>> Evm<'_, EvmWiringT> {
     pub fn transact_commit(
         &mut self,
     ) -> EVMResultGeneric<ExecutionResult<EvmWiringT::HaltReason>, EvmWiringT> {
-        let ResultAndState { result, state } = self.transact()?;
-        self.context.evm.db.commit(state);
+        let ResultAndState {
+            result,
+            state,
+            deferred_reward,
+            ..
+        } = self.transact()?;
+        let pending_rewards = &mut self.context.evm.inner.pending_rewards;
+        *pending_rewards = pending_rewards.saturating_add(deferred_reward);
+        self.commit(state);
         Ok(result)
     }
+
+    /// Commits `state` to the database, clearing the cached results if it changes state.
+    fn commit(&mut self, state: EvmState) {
+        if !state.is_empty() {
+            self.context.evm.result_cache.clear();
+        }
+        self.context.evm.db.commit(state);
+    }
+
+    /// Executes and commits the transactions in order, stopping at the first one that does not
+    /// succeed, because it reverted or halted.
+    ///
+    /// The failed transaction is committed as any other and its index and result are returned.
+    /// Transactions after it are not executed. Returns `None` if all transactions succeeded.
+    ///
+    /// If a transaction can not be executed, for example because it is invalid, its index and
+    /// error are returned and nothing is committed for it.
+    pub fn transact_batch_fail_fast(
+        &mut self,
+        txs: impl IntoIterator<Item = EvmWiringT::Transaction>,
+    ) -> Result<Option<BatchFailure<EvmWiringT::HaltReason>>, BatchError<EvmWiringT>> {
+        for (index, tx) in txs.into_iter().enumerate() {
+            *self.tx_mut() = tx;
+            let result = self.transact_commit().map_err(|error| (index, error))?;
+            if !result.is_success() {
+                return Ok(Some((index, result)));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Credits a fixed `block_reward` to the block beneficiary and commits it.
+    ///
+    /// Meant to be called once after all transactions of a block on chains that pay a block
+    /// reward on top of the transaction tips.
+    pub fn finalize_block(&mut self, block_reward: U256) -> EVMResultGeneric<(), EvmWiringT> {
+        self.credit_beneficiary(block_reward)
+    }
+
+    /// Pays the beneficiary rewards accumulated with
+    /// [`CfgEnv::is_lazy_beneficiary_reward_enabled`] to the block beneficiary and commits it.
+    ///
+    /// The accumulator is cleared.
+    pub fn settle_rewards(&mut self) -> EVMResultGeneric<(), EvmWiringT> {
+        let pending_rewards = core::mem::take(&mut self.context.evm.inner.pending_rewards);
+        self.credit_beneficiary(pending_rewards)
+    }
+
+    /// Credits `amount` to the block beneficiary and commits it.
+    fn credit_beneficiary(&mut self, amount: U256) -> EVMResultGeneric<(), EvmWiringT> {
+        let beneficiary = *self.context.evm.env.block.coinbase();
+        let inner = &mut self.context.evm.inner;
+
+        let coinbase_account = inner
+            .journaled_state
+            .load_account(beneficiary, &mut inner.db)
+            .map_err(EVMError::Database)?;
+        coinbase_account.data.mark_touch();
+        coinbase_account.data.info.balance =
+            coinbase_account.data.info.balance.saturating_add(amount);
+
+        let (state, _) = inner.journaled_state.finalize();
+        self.commit(state);
+        Ok(())
+    }
 }
 
 impl<'a, EvmWiringT: EvmWiring> Evm<'a, EvmWiringT>
@@ -99,8 +189,17 @@ impl<'a, EvmWiringT: EvmWiring> Evm<'a, EvmWiringT> {
         &mut self,
         first_frame: Frame,
     ) -> EVMResultGeneric<FrameResult, EvmWiringT> {
+        self.run_frames(first_frame).map(|(result, _)| result)
+    }
+
+    /// Runs main call loop, returning the result and the number of frames that ran.
+    fn run_frames(
+        &mut self,
+        first_frame: Frame,
+    ) -> EVMResultGeneric<(FrameResult, usize), EvmWiringT> {
         let mut call_stack: Vec<Frame> = Vec::with_capacity(1025);
         call_stack.push(first_frame);
+        let mut frame_count = 1;
 
         #[cfg(feature = "memory_limit")]
         let mut shared_memory =
@@ -166,12 +265,13 @@ impl<'a, EvmWiringT: EvmWiring> Evm<'a, EvmWiringT> {
                 FrameOrResult::Frame(frame) => {
                     shared_memory.new_context();
                     call_stack.push(frame);
+                    frame_count += 1;
                     stack_frame = call_stack.last_mut().unwrap();
                 }
                 FrameOrResult::Result(result) => {
                     let Some(top_frame) = call_stack.last_mut() else {
                         // Break the loop if there are no more frames.
-                        return Ok(result);
+                        return Ok((result, frame_count));
                     };
                     stack_frame = top_frame;
                     let ctx = &mut self.context;
@@ -239,6 +339,9 @@ impl<EvmWiringT: EvmWiring> Evm<'_, EvmWiringT> {
     /// Pre verify transaction inner.
     #[inline]
     fn preverify_transaction_inner(&mut self) -> EVMResultGeneric<u64, EvmWiringT> {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::info_span!(target: "revm", "validation").entered();
+
         self.handler.validation().env(&self.context.evm.env)?;
         let initial_gas_spend = self
             .handler
@@ -265,6 +368,149 @@ impl<EvmWiringT: EvmWiring> Evm<'_, EvmWiringT> {
         output
     }
 
+    /// Transact transaction, reusing the result cached for `key` if there is one.
+    ///
+    /// `key` must be a hash of the transaction and of the pre-state root, see
+    /// [`ResultCache`](crate::ResultCache). Successful executions are cached. Like
+    /// [`Evm::transact`], the state changes and the deferred reward they return are not
+    /// committed.
+    pub fn transact_cached(&mut self, key: B256) -> EVMResult<EvmWiringT> {
+        if let Some(result) = self.context.evm.result_cache.get(&key) {
+            return Ok(result.clone());
+        }
+        let result = self.transact()?;
+        if result.result.is_success() {
+            let capacity = self.context.evm.env.cfg.result_cache_size;
+            self.context
+                .evm
+                .result_cache
+                .insert(key, result.clone(), capacity);
+        }
+        Ok(result)
+    }
+
+    /// Transact transaction and only return the gas used.
+    ///
+    /// This function will validate the transaction and run the whole post-execution, like
+    /// [`Evm::transact`], so the gas used matches its result. The state diff and logs are
+    /// discarded, for validity checks such as mempool admission.
+    #[inline]
+    pub fn transact_gas_only(&mut self) -> EVMResultGeneric<u64, EvmWiringT> {
+        self.transact().map(|output| output.result.gas_used())
+    }
+
+    /// Transact transaction with a lower gas limit and only return its success and gas used.
+    ///
+    /// Meant for the attempts of a gas estimation binary search. The transaction is validated
+    /// with its own gas limit, the upper bound of the search, and executed with `gas_limit`,
+    /// capped to that bound. Unlike [`Evm::transact`], the output is not built, and nothing is
+    /// committed. Attempts below the intrinsic gas fail.
+    ///
+    /// Set [`CfgEnv::analysis_cache_size`] so attempts reuse the analyzed bytecode, and use
+    /// [`Evm::transact_with_gas_limit_from`] so they reuse the validated and pre-executed
+    /// journal.
+    pub fn transact_with_gas_limit(
+        &mut self,
+        gas_limit: u64,
+    ) -> EVMResultGeneric<(bool, u64), EvmWiringT> {
+        let snapshot = self.pre_execution_snapshot()?;
+        self.transact_with_gas_limit_from(&snapshot, gas_limit)
+    }
+
+    /// Validates the transaction and runs its pre-execution, then returns the journal so
+    /// executions of the same transaction can start from it.
+    ///
+    /// The snapshot holds the accounts loaded so far, the gas deducted from the caller and the
+    /// applied EIP-7702 authorizations. It is only valid as long as the environment and the
+    /// database do not change.
+    pub fn pre_execution_snapshot(&mut self) -> EVMResultGeneric<PreExecutionSnapshot, EvmWiringT> {
+        let output = self
+            .preverify_transaction_inner()
+            .and_then(|initial_gas_spend| {
+                let eip7702_gas_refund = self.pre_execute_inner()?;
+                Ok(PreExecutionSnapshot {
+                    journaled_state: self.context.evm.journaled_state.clone(),
+                    initial_gas_spend,
+                    eip7702_gas_refund,
+                })
+            });
+        self.clear();
+        output
+    }
+
+    /// Like [`Evm::transact_with_gas_limit`], but starts the execution from a journal taken by
+    /// [`Evm::pre_execution_snapshot`] instead of validating the transaction again.
+    pub fn transact_with_gas_limit_from(
+        &mut self,
+        snapshot: &PreExecutionSnapshot,
+        gas_limit: u64,
+    ) -> EVMResultGeneric<(bool, u64), EvmWiringT> {
+        let gas_limit = gas_limit.min(self.context.evm.env.tx.common_fields().gas_limit());
+        if gas_limit < snapshot.initial_gas_spend {
+            return Ok((false, gas_limit));
+        }
+        self.context
+            .evm
+            .journaled_state
+            .clone_from(&snapshot.journaled_state);
+        let precompiles = self.handler.pre_execution().load_precompiles();
+        self.context.evm.set_precompiles(precompiles);
+
+        let output = self
+            .execute_frames_inner(
+                snapshot.initial_gas_spend,
+                gas_limit,
+                snapshot.eip7702_gas_refund,
+            )
+            .map(|result| {
+                let gas = result.gas();
+                (
+                    result.interpreter_result().is_ok(),
+                    gas.spent() - gas.refunded() as u64,
+                )
+            });
+        self.clear();
+        output
+    }
+
+    /// Transact transaction and return whether it changes state beyond paying for gas.
+    ///
+    /// Nothing is committed. The journal of the execution is checked for value transfers,
+    /// storage writes, created or destroyed accounts, code changes and nonce changes other than
+    /// the caller's. Transactions with an EIP-7702 authorization list are always considered state
+    /// changing. Useful for RPC nodes to classify view calls.
+    #[inline]
+    pub fn is_state_changing(&mut self) -> EVMResultGeneric<bool, EvmWiringT> {
+        let output = self
+            .preverify_transaction_inner()
+            .and_then(|initial_gas_spend| {
+                self.execute_preverified_inner(initial_gas_spend)?;
+
+                let tx = &self.context.evm.env.tx;
+                if tx.authorization_list().next().is_some() {
+                    return Ok(true);
+                }
+                let caller = tx.common_fields().caller();
+                let mut journal = self.context.evm.journaled_state.journal.iter().flatten();
+                Ok(journal.any(|entry| match entry {
+                    JournalEntry::AccountWarmed { .. }
+                    | JournalEntry::AccountTouched { .. }
+                    | JournalEntry::StorageWarmed { .. }
+                    | JournalEntry::TransientStorageChange { .. } => false,
+                    JournalEntry::BalanceTransfer { from, to, balance } => {
+                        from != to && !balance.is_zero()
+                    }
+                    JournalEntry::NonceChange { address } => *address != caller,
+                    JournalEntry::AccountDestroyed { .. }
+                    | JournalEntry::AccountCreated { .. }
+                    | JournalEntry::StorageChanged { .. }
+                    | JournalEntry::CodeChange { .. } => true,
+                }))
+            });
+        self.clear();
+        output
+    }
+
     /// Returns the reference of Env configuration
     #[inline]
     pub fn cfg(&self) -> &CfgEnv {
@@ -272,8 +518,11 @@ impl<EvmWiringT: EvmWiring> Evm<'_, EvmWiringT> {
     }
 
     /// Returns the mutable reference of Env configuration
+    ///
+    /// Clears the cached transaction results.
     #[inline]
     pub fn cfg_mut(&mut self) -> &mut CfgEnv {
+        self.context.evm.result_cache.clear();
         &mut self.context.evm.env.cfg
     }
 
@@ -296,8 +545,12 @@ impl<EvmWiringT: EvmWiring> Evm<'_, EvmWiringT> {
     }
 
     /// Returns the mutable reference of database
+    ///
+    /// Clears the cached transaction results and block hashes.
     #[inline]
     pub fn db_mut(&mut self) -> &mut EvmWiringT::Database {
+        self.context.evm.result_cache.clear();
+        self.context.evm.block_hashes.clear();
         &mut self.context.evm.db
     }
 
@@ -308,13 +561,17 @@ impl<EvmWiringT: EvmWiring> Evm<'_, EvmWiringT> {
     }
 
     /// Returns the mutable reference of block
+    ///
+    /// Clears the cached transaction results.
     #[inline]
     pub fn block_mut(&mut self) -> &mut EvmWiringT::Block {
+        self.context.evm.result_cache.clear();
         &mut self.context.evm.env.block
     }
 
     /// Modify spec id, this will create new EVM that matches this spec id.
     pub fn modify_spec_id(&mut self, spec_id: EvmWiringT::Hardfork) {
+        self.context.evm.result_cache.clear();
         self.context.evm.journaled_state.set_spec_id(spec_id.into());
         self.handler.modify_spec_id(spec_id);
     }
@@ -349,6 +606,68 @@ impl<EvmWiringT: EvmWiring> Evm<'_, EvmWiringT> {
 
     /// Transact pre-verified transaction.
     fn transact_preverified_inner(&mut self, initial_gas_spend: u64) -> EVMResult<EvmWiringT> {
+        self.context.evm.inner.intrinsic_gas = initial_gas_spend;
+        let result = self.execute_preverified_inner(initial_gas_spend)?;
+
+        #[cfg(feature = "tracing")]
+        let span = tracing::info_span!(
+            target: "revm",
+            "post_execution",
+            gas_used = tracing::field::Empty,
+            result = tracing::field::Empty,
+        )
+        .entered();
+
+        let ctx = &mut self.context;
+        let post_exec = self.handler.post_execution();
+        // Reimburse the caller
+        post_exec.reimburse_caller(ctx, result.gas())?;
+        // Reward beneficiary, a caller that is also the coinbase is credited on top of its
+        // reimbursement as both go through the journaled account.
+        post_exec.reward_beneficiary(ctx, result.gas())?;
+        // Returns output of transaction.
+        let output = post_exec.output(ctx, result)?;
+
+        #[cfg(feature = "tracing")]
+        {
+            span.record("gas_used", output.result.gas_used());
+            span.record(
+                "result",
+                match output.result {
+                    ExecutionResult::Success { .. } => "success",
+                    ExecutionResult::Revert { .. } => "revert",
+                    ExecutionResult::Halt { .. } => "halt",
+                },
+            );
+        }
+        Ok(output)
+    }
+
+    /// Executes pre-verified transaction up to and including the final refund calculation.
+    fn execute_preverified_inner(
+        &mut self,
+        initial_gas_spend: u64,
+    ) -> EVMResultGeneric<FrameResult, EvmWiringT> {
+        let gas_limit = self.context.evm.env.tx.common_fields().gas_limit();
+        self.execute_inner(initial_gas_spend, gas_limit)
+    }
+
+    /// Executes pre-verified transaction with `gas_limit`, at most the transaction gas limit.
+    fn execute_inner(
+        &mut self,
+        initial_gas_spend: u64,
+        gas_limit: u64,
+    ) -> EVMResultGeneric<FrameResult, EvmWiringT> {
+        let eip7702_gas_refund = self.pre_execute_inner()?;
+        self.execute_frames_inner(initial_gas_spend, gas_limit, eip7702_gas_refund)
+    }
+
+    /// Loads the accounts and precompiles, deducts the caller and applies the EIP-7702
+    /// authorizations. Returns the EIP-7702 gas refund.
+    fn pre_execute_inner(&mut self) -> EVMResultGeneric<i64, EvmWiringT> {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::info_span!(target: "revm", "pre_execution").entered();
+
         let ctx = &mut self.context;
         let pre_exec = self.handler.pre_execution();
 
@@ -362,16 +681,36 @@ impl<EvmWiringT: EvmWiring> Evm<'_, EvmWiringT> {
         // deduce caller balance with its limit.
         pre_exec.deduct_caller(ctx)?;
 
-        let gas_limit = ctx.evm.env.tx.common_fields().gas_limit() - initial_gas_spend;
-
         // apply EIP-7702 auth list.
-        let eip7702_gas_refund = pre_exec.apply_eip7702_auth_list(ctx)? as i64;
+        Ok(pre_exec.apply_eip7702_auth_list(ctx)? as i64)
+    }
+
+    /// Runs the frames of the pre-executed transaction with `gas_limit` and calculates the
+    /// refund.
+    fn execute_frames_inner(
+        &mut self,
+        initial_gas_spend: u64,
+        gas_limit: u64,
+        eip7702_gas_refund: i64,
+    ) -> EVMResultGeneric<FrameResult, EvmWiringT> {
+        let ctx = &mut self.context;
+        let tx_gas_limit = ctx.evm.env.tx.common_fields().gas_limit();
+        let frame_gas_limit = gas_limit - initial_gas_spend;
+
+        #[cfg(feature = "tracing")]
+        let span = tracing::info_span!(
+            target: "revm",
+            "execution",
+            frame_count = 1,
+            gas_used = tracing::field::Empty,
+        )
+        .entered();
 
         // start execution
         let exec = self.handler.execution();
 
         // create first frame action
-        let first_frame_action = exec.first_frame_creation(ctx, gas_limit)?;
+        let first_frame_action = exec.first_frame_creation(ctx, frame_gas_limit)?;
 
         // call handler to create first frame.
         let first_frame_or_result = match first_frame_action {
@@ -382,7 +721,12 @@ impl<EvmWiringT: EvmWiring> Evm<'_, EvmWiringT> {
 
         // Starts the main running loop or return the result.
         let mut result = match first_frame_or_result {
-            FrameOrResult::Frame(first_frame) => self.run_the_loop(first_frame)?,
+            FrameOrResult::Frame(first_frame) => {
+                let (result, _frame_count) = self.run_frames(first_frame)?;
+                #[cfg(feature = "tracing")]
+                span.record("frame_count", _frame_count);
+                result
+            }
             FrameOrResult::Result(result) => result,
         };
 
@@ -393,34 +737,58 @@ impl<EvmWiringT: EvmWiring> Evm<'_, EvmWiringT> {
             .execution()
             .last_frame_return(ctx, &mut result)?;
 
-        let post_exec = self.handler.post_execution();
+        // The transaction gas limit was spent, spend the lower limit instead.
+        if gas_limit < tx_gas_limit {
+            let gas = result.gas_mut();
+            let (remaining, refunded) = (gas.remaining(), gas.refunded());
+            *gas = Gas::new_spent(gas_limit);
+            gas.erase_cost(remaining);
+            gas.record_refund(refunded);
+        }
+
         // calculate final refund and add EIP-7702 refund to gas.
-        post_exec.refund(ctx, result.gas_mut(), eip7702_gas_refund);
-        // Reimburse the caller
-        post_exec.reimburse_caller(ctx, result.gas())?;
-        // Reward beneficiary
-        post_exec.reward_beneficiary(ctx, result.gas())?;
-        // Returns output of transaction.
-        post_exec.output(ctx, result)
+        self.handler
+            .post_execution()
+            .refund(ctx, result.gas_mut(), eip7702_gas_refund);
+
+        #[cfg(feature = "tracing")]
+        span.record(
+            "gas_used",
+            result.gas().spent() - result.gas().refunded() as u64,
+        );
+        Ok(result)
     }
 }
 
+#[cfg(all(test, feature = "tracing"))]
+mod tracing_tests;
+
 #[cfg(test)]
 mod tests {
 
     use super::*;
+    use crate::test_utils::{call_evm_builder, TEST_CALLER};
     use bytecode::{
-        opcode::{PUSH1, SSTORE},
+        opcode::{
+            CALL, CREATE, GAS, KECCAK256, POP, PUSH0, PUSH1, PUSH2, PUSH20, RETURN, REVERT, SLOAD,
+            SSTORE, STOP,
+        },
         Bytecode,
     };
-    use database::BenchmarkDB;
-    use primitives::{address, TxKind, U256};
+    use database::{BenchmarkDB, InMemoryDB};
+    use interpreter::gas;
+    use primitives::{address, Address, Bytes, TxKind, U256};
     use specification::{
         eip7702::{Authorization, RecoveredAuthorization, Signature},
         hardfork::SpecId,
     };
+    use state::AccountInfo;
     use transaction::TransactionType;
-    use wiring::EthereumWiring;
+    use wiring::{
+        default::{Keccak256Hasher, TxEnv},
+        result::{EVMError, ExecutionResult, HaltReason, InvalidTransaction},
+        EthereumWiring,
+    };
 
     #[test]
     fn sanity_eip7702_tx() {
@@ -462,4 +830,826 @@ mod tests {
             U256::from(1)
         );
     }
+
+    #[test]
+    fn empty_calldata_call_to_eoa() {
+        let caller = address!("0000000000000000000000000000000000000001");
+        let eoa = address!("00000000000000000000000000000000000000ee");
+
+        // CALL(gas, eoa, 0, 0, 0, 0, 0)
+        let mut code = vec![
+            PUSH1, 0x00, PUSH1, 0x00, PUSH1, 0x00, PUSH1, 0x00, PUSH1, 0x00,
+        ];
+        code.push(PUSH20);
+        code.extend_from_slice(eoa.as_slice());
+        code.extend_from_slice(&[GAS, CALL, STOP]);
+        let bytecode = Bytecode::new_legacy(code.into());
+
+        let mut evm = call_evm_builder(
+            BenchmarkDB::new_bytecode(bytecode),
+            (),
+            Address::ZERO,
+            100_000,
+        )
+        .with_spec_id(SpecId::CANCUN)
+        .modify_tx_env(|tx| {
+            tx.caller = caller;
+        })
+        .build();
+
+        let ok = evm.transact().unwrap();
+        assert!(ok.result.is_success());
+
+        // No frame runs for the EOA so only the call itself is charged:
+        // six pushes, GAS and the cold account access of CALL.
+        let expected = 21_000 + 6 * gas::VERYLOW + gas::BASE + gas::COLD_ACCOUNT_ACCESS_COST;
+        assert_eq!(ok.result.gas_used(), expected);
+
+        // The EOA was loaded and touched by the call.
+        let eoa_acc = ok.state.get(&eoa).unwrap();
+        assert!(eoa_acc.is_touched());
+        assert!(eoa_acc.info.is_empty());
+    }
+
+    #[test]
+    fn eip1559_priority_fee_paid() {
+        let caller = address!("0000000000000000000000000000000000000001");
+        let coinbase = address!("00000000000000000000000000000000000000cb");
+        let basefee = U256::from(10);
+
+        let mut evm = call_evm_builder(
+            BenchmarkDB::new_bytecode(Bytecode::new()),
+            (),
+            Address::ZERO,
+            100_000,
+        )
+        .with_spec_id(SpecId::CANCUN)
+        .modify_block_env(|block| {
+            block.basefee = basefee;
+            block.coinbase = coinbase;
+        })
+        .modify_tx_env(|tx| {
+            tx.tx_type = TransactionType::Eip1559;
+            tx.caller = caller;
+            tx.gas_price = U256::from(20);
+            tx.gas_priority_fee = Some(U256::from(3));
+        })
+        .build();
+
+        let effective_gas_price = evm.context.evm.env.effective_gas_price();
+        assert_eq!(effective_gas_price, U256::from(13));
+
+        let ok = evm.transact().unwrap();
+        let tip = ok.priority_fee_paid();
+        assert_eq!(tip, U256::from(3 * ok.result.gas_used()));
+        assert_eq!(ok.state.get(&coinbase).unwrap().info.balance, tip);
+    }
+
+    #[test]
+    fn read_only_halts_on_sstore() {
+        let code = vec![PUSH1, 0x01, PUSH1, 0x00, SSTORE, STOP];
+        let bytecode = Bytecode::new_legacy(code.into());
+
+        let mut evm = call_evm_builder(
+            BenchmarkDB::new_bytecode(bytecode),
+            (),
+            Address::ZERO,
+            100_000,
+        )
+        .with_spec_id(SpecId::CANCUN)
+        .modify_cfg_env(|cfg| cfg.read_only = true)
+        .modify_tx_env(|tx| {
+            tx.caller = address!("0000000000000000000000000000000000000001");
+        })
+        .build();
+
+        let ok = evm.transact().unwrap();
+        assert_eq!(
+            ok.result,
+            ExecutionResult::Halt {
+                reason: HaltReason::ReadOnlyViolation,
+                gas_used: 100_000,
+            }
+        );
+        assert!(ok.state.get(&Address::ZERO).unwrap().storage.is_empty());
+    }
+
+    #[test]
+    fn batch_fail_fast_stops_at_first_failure() {
+        let caller = address!("0000000000000000000000000000000000000001");
+        let ok_contract = address!("00000000000000000000000000000000000000a0");
+        let revert_contract = address!("00000000000000000000000000000000000000a1");
+
+        let mut db = InMemoryDB::default();
+        db.insert_account_info(
+            ok_contract,
+            AccountInfo::from_bytecode(Bytecode::new_legacy(vec![STOP].into())),
+        );
+        db.insert_account_info(
+            revert_contract,
+            AccountInfo::from_bytecode(Bytecode::new_legacy(
+                vec![PUSH1, 0x00, PUSH1, 0x00, REVERT].into(),
+            )),
+        );
+
+        let mut evm = Evm::<EthereumWiring<InMemoryDB, ()>>::builder()
+            .with_spec_id(SpecId::CANCUN)
+            .with_db(db)
+            .with_default_ext_ctx()
+            .build();
+
+        let txs = [ok_contract, revert_contract, ok_contract]
+            .into_iter()
+            .enumerate()
+            .map(|(nonce, to)| TxEnv {
+                caller,
+                transact_to: TxKind::Call(to),
+                nonce: nonce as u64,
+                gas_limit: 100_000,
+                ..Default::default()
+            });
+
+        let (index, result) = evm.transact_batch_fail_fast(txs).unwrap().unwrap();
+        assert_eq!(index, 1);
+        assert!(matches!(result, ExecutionResult::Revert { .. }));
+
+        // Only the first two transactions were executed.
+        assert_eq!(evm.db().accounts[&caller].info.nonce, 2);
+
+        // Halts stop the batch as well, this one runs out of gas before reverting.
+        let halt = TxEnv {
+            caller,
+            transact_to: TxKind::Call(revert_contract),
+            nonce: 2,
+            gas_limit: 21_002,
+            ..Default::default()
+        };
+        let (index, result) = evm.transact_batch_fail_fast([halt]).unwrap().unwrap();
+        assert_eq!(index, 0);
+        assert!(matches!(result, ExecutionResult::Halt { .. }));
+
+        // Errors are returned with the index of the transaction.
+        let txs = [3, 5].map(|nonce| TxEnv {
+            caller,
+            transact_to: TxKind::Call(ok_contract),
+            nonce,
+            gas_limit: 100_000,
+            ..Default::default()
+        });
+        let (index, error) = evm.transact_batch_fail_fast(txs).unwrap_err();
+        assert_eq!(index, 1);
+        assert!(matches!(
+            error,
+            EVMError::Transaction(InvalidTransaction::NonceTooHigh { .. })
+        ));
+    }
+
+    #[test]
+    #[cfg(feature = "memory_limit")]
+    fn memory_limit() {
+        use bytecode::opcode::{MSTORE, PUSH4};
+        use wiring::result::OutOfGasError;
+
+        // MSTORE at `offset`, expanding memory to `offset + 32` bytes.
+        let transact = |offset: u32, memory_limit: Option<u64>| {
+            let mut code = vec![PUSH1, 0x01, PUSH4];
+            code.extend_from_slice(&offset.to_be_bytes());
+            code.extend_from_slice(&[MSTORE, STOP]);
+
+            let mut evm = call_evm_builder(
+                BenchmarkDB::new_bytecode(Bytecode::new_legacy(code.into())),
+                (),
+                Address::ZERO,
+                1_000_000,
+            )
+            .with_spec_id(SpecId::CANCUN)
+            .modify_cfg_env(|cfg| {
+                if let Some(memory_limit) = memory_limit {
+                    cfg.memory_limit = memory_limit;
+                }
+            })
+            .modify_tx_env(|tx| {
+                tx.caller = address!("0000000000000000000000000000000000000001");
+            })
+            .build();
+            evm.transact().unwrap().result
+        };
+        let limit_reached = |result: ExecutionResult<HaltReason>| {
+            matches!(
+                result,
+                ExecutionResult::Halt {
+                    reason: HaltReason::OutOfGas(OutOfGasError::MemoryLimit),
+                    ..
+                }
+            )
+        };
+
+        // Default limit is ~4GiB, checked before charging gas for the expansion.
+        assert!(limit_reached(transact(u32::MAX - 31, None)));
+
+        // Lower limit is respected.
+        assert!(transact(992, Some(1024)).is_success());
+        assert!(limit_reached(transact(993, Some(1024))));
+    }
+
+    #[test]
+    fn intrinsic_gas() {
+        let code = vec![PUSH1, 0x01, PUSH1, 0x00, SSTORE, STOP];
+        let bytecode = Bytecode::new_legacy(code.into());
+
+        let mut evm = call_evm_builder(
+            BenchmarkDB::new_bytecode(bytecode),
+            (),
+            Address::ZERO,
+            100_000,
+        )
+        .with_spec_id(SpecId::CANCUN)
+        .modify_tx_env(|tx| {
+            tx.caller = address!("0000000000000000000000000000000000000001");
+            tx.data = vec![0x00, 0x01, 0x02, 0x03].into();
+        })
+        .build();
+
+        let ok = evm.transact().unwrap();
+        assert!(ok.result.is_success());
+
+        // Base cost, one zero byte and three non-zero bytes (EIP-2028).
+        let expected = 21_000 + 4 + 3 * 16;
+        assert_eq!(ok.intrinsic_gas(), expected);
+        assert!(ok.intrinsic_gas() < ok.result.gas_used());
+    }
+
+    #[test]
+    fn finalize_block_rewards_coinbase() {
+        let caller = address!("0000000000000000000000000000000000000001");
+        let coinbase = address!("00000000000000000000000000000000000000cb");
+
+        let mut db = InMemoryDB::default();
+        db.insert_account_info(caller, AccountInfo::from_balance(U256::from(1_000_000)));
+
+        let mut evm = Evm::<EthereumWiring<InMemoryDB, ()>>::builder()
+            .with_spec_id(SpecId::CANCUN)
+            .with_db(db)
+            .with_default_ext_ctx()
+            .modify_block_env(|block| block.coinbase = coinbase)
+            .build();
+
+        let mut gas_used = 0;
+        for nonce in 0..2 {
+            *evm.tx_mut() = TxEnv {
+                caller,
+                transact_to: TxKind::Call(address!("00000000000000000000000000000000000000a0")),
+                nonce,
+                gas_limit: 21_000,
+                gas_price: U256::from(2),
+                ..Default::default()
+            };
+            gas_used += evm.transact_commit().unwrap().gas_used();
+        }
+
+        evm.finalize_block(U256::from(1_000)).unwrap();
+
+        let tips = U256::from(2 * gas_used);
+        assert_eq!(
+            evm.db().accounts[&coinbase].info.balance,
+            U256::from(1_000) + tips
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "optional_lazy_beneficiary_reward")]
+    fn lazy_beneficiary_reward_settlement() {
+        let caller = address!("0000000000000000000000000000000000000001");
+        let coinbase = address!("00000000000000000000000000000000000000cb");
+
+        let mut db = InMemoryDB::default();
+        db.insert_account_info(caller, AccountInfo::from_balance(U256::from(1_000_000)));
+
+        let mut evm = Evm::<EthereumWiring<InMemoryDB, ()>>::builder()
+            .with_spec_id(SpecId::CANCUN)
+            .with_db(db)
+            .with_default_ext_ctx()
+            .modify_cfg_env(|cfg| cfg.lazy_beneficiary_reward = true)
+            .modify_block_env(|block| block.coinbase = coinbase)
+            .build();
+
+        let mut gas_used = 0;
+        for nonce in 0..3 {
+            *evm.tx_mut() = TxEnv {
+                caller,
+                transact_to: TxKind::Call(address!("00000000000000000000000000000000000000a0")),
+                nonce,
+                gas_limit: 21_000,
+                gas_price: U256::from(2),
+                ..Default::default()
+            };
+            gas_used += evm.transact_commit().unwrap().gas_used();
+        }
+
+        // Coinbase was not touched by the transactions.
+        assert!(!evm.db().accounts.contains_key(&coinbase));
+        assert_eq!(evm.context.evm.pending_rewards, U256::from(2 * gas_used));
+
+        // Runs that are not committed do not accumulate.
+        *evm.tx_mut() = TxEnv {
+            caller,
+            transact_to: TxKind::Call(address!("00000000000000000000000000000000000000a0")),
+            nonce: 3,
+            gas_limit: 21_000,
+            gas_price: U256::from(2),
+            ..Default::default()
+        };
+        let uncommitted = evm.transact().unwrap();
+        assert_eq!(uncommitted.deferred_reward, U256::from(2 * 21_000));
+        assert_eq!(uncommitted.priority_fee_paid(), uncommitted.deferred_reward);
+        evm.transact_gas_only().unwrap();
+        assert_eq!(evm.context.evm.pending_rewards, U256::from(2 * gas_used));
+
+        evm.settle_rewards().unwrap();
+        assert_eq!(evm.context.evm.pending_rewards, U256::ZERO);
+        assert_eq!(
+            evm.db().accounts[&coinbase].info.balance,
+            U256::from(2 * gas_used)
+        );
+    }
+
+    #[test]
+    fn limit_contract_code_size() {
+        use specification::constants::{MAX_CODE_SIZE, MAX_INITCODE_SIZE};
+
+        // Initcode returning `size` bytes of zeroed memory as runtime code, padded with zeros to
+        // `initcode_size`.
+        let deploy = |size: usize, initcode_size: usize, cfg: fn(&mut CfgEnv)| {
+            let [high, low] = (size as u16).to_be_bytes();
+            let mut initcode = vec![PUSH2, high, low, PUSH0, RETURN];
+            initcode.resize(initcode_size, 0);
+            let mut evm = Evm::<EthereumWiring<InMemoryDB, ()>>::builder()
+                .with_spec_id(SpecId::CANCUN)
+                .with_db(InMemoryDB::default())
+                .with_default_ext_ctx()
+                .modify_cfg_env(cfg)
+                .modify_tx_env(|tx| {
+                    tx.transact_to = TxKind::Create;
+                    tx.data = initcode.into();
+                    tx.gas_limit = 10_000_000;
+                })
+                .build();
+            evm.transact().map(|output| output.result)
+        };
+
+        let result = deploy(MAX_CODE_SIZE + 1, 5, |_| {}).unwrap();
+        assert!(matches!(
+            result,
+            ExecutionResult::Halt {
+                reason: HaltReason::CreateContractSizeLimit,
+                ..
+            }
+        ));
+        let result = deploy(MAX_CODE_SIZE + 1, 5, |cfg| {
+            cfg.limit_contract_code_size = Some(MAX_CODE_SIZE + 1)
+        })
+        .unwrap();
+        assert!(result.is_success(), "{result:?}");
+
+        assert!(matches!(
+            deploy(1, MAX_INITCODE_SIZE + 1, |_| {}),
+            Err(EVMError::Transaction(
+                InvalidTransaction::CreateInitCodeSizeLimit
+            ))
+        ));
+        #[cfg(feature = "optional_initcode_size_limit")]
+        {
+            let result = deploy(1, MAX_INITCODE_SIZE + 1, |cfg| {
+                cfg.limit_contract_initcode_size = Some(MAX_INITCODE_SIZE + 1)
+            })
+            .unwrap();
+            assert!(result.is_success(), "{result:?}");
+        }
+    }
+
+    #[test]
+    fn return_data_size_after_failed_call() {
+        use bytecode::opcode::{JUMP, JUMPDEST, RETURNDATASIZE};
+
+        let contract = address!("00000000000000000000000000000000000000a0");
+        let reverter = address!("00000000000000000000000000000000000000a1");
+        let looper = address!("00000000000000000000000000000000000000a2");
+
+        // Calls the reverter with all gas and the looper with 10000 gas, storing the return data
+        // size after each call in slots 0 and 1.
+        let mut code = vec![PUSH0, PUSH0, PUSH0, PUSH0, PUSH0, PUSH20];
+        code.extend_from_slice(reverter.as_slice());
+        code.extend_from_slice(&[GAS, CALL, POP, RETURNDATASIZE, PUSH0, SSTORE]);
+        code.extend_from_slice(&[PUSH0, PUSH0, PUSH0, PUSH0, PUSH0, PUSH20]);
+        code.extend_from_slice(looper.as_slice());
+        code.extend_from_slice(&[PUSH2, 0x27, 0x10, CALL, POP]);
+        code.extend_from_slice(&[RETURNDATASIZE, PUSH1, 0x01, SSTORE, STOP]);
+
+        let mut db = InMemoryDB::default();
+        db.insert_account_info(
+            contract,
+            AccountInfo::from_bytecode(Bytecode::new_raw(code.into())),
+        );
+        // Reverts with 4 zero bytes.
+        db.insert_account_info(
+            reverter,
+            AccountInfo::from_bytecode(Bytecode::new_raw(vec![PUSH1, 0x04, PUSH0, REVERT].into())),
+        );
+        // Loops until it runs out of gas.
+        db.insert_account_info(
+            looper,
+            AccountInfo::from_bytecode(Bytecode::new_raw(vec![JUMPDEST, PUSH0, JUMP].into())),
+        );
+
+        let mut evm = call_evm_builder(db, (), contract, 1_000_000).build();
+
+        let ResultAndState { result, state, .. } = evm.transact().unwrap();
+        assert!(result.is_success(), "{result:?}");
+        let storage = &state[&contract].storage;
+        assert_eq!(storage[&U256::from(0)].present_value, U256::from(4));
+        // The out of gas call clears the return data of the reverted call.
+        assert_eq!(storage[&U256::from(1)].present_value, U256::ZERO);
+    }
+
+    #[cfg(feature = "eip5920")]
+    #[test]
+    fn pay_opcode() {
+        use bytecode::opcode::INVALID;
+        use interpreter::instructions::host::PAY;
+
+        let contract = address!("00000000000000000000000000000000000000a0");
+        let target = address!("00000000000000000000000000000000000000a1");
+        let delegate = address!("00000000000000000000000000000000000000a2");
+
+        // Pays 3 wei to the target.
+        let mut code = vec![PUSH1, 3, PUSH20];
+        code.extend_from_slice(target.as_slice());
+        code.extend_from_slice(&[PAY, STOP]);
+
+        let mut db = InMemoryDB::default();
+        db.insert_account_info(
+            contract,
+            AccountInfo {
+                balance: U256::from(10),
+                ..AccountInfo::from_bytecode(Bytecode::new_raw(code.into()))
+            },
+        );
+        // The target is delegated and a call to it would fail.
+        db.insert_account_info(
+            target,
+            AccountInfo::from_bytecode(Bytecode::new_eip7702(delegate)),
+        );
+        db.insert_account_info(
+            delegate,
+            AccountInfo::from_bytecode(Bytecode::new_raw(vec![INVALID].into())),
+        );
+
+        let mut evm = call_evm_builder(db, (), contract, 100_000).build();
+
+        let ResultAndState { result, state, .. } = evm.transact().unwrap();
+        assert!(result.is_success(), "{result:?}");
+        // Two pushes, the cold target access and the value transfer.
+        assert_eq!(
+            result.gas_used(),
+            21_000 + 3 + 3 + gas::COLD_ACCOUNT_ACCESS_COST + gas::CALLVALUE
+        );
+        assert_eq!(state[&contract].info.balance, U256::from(7));
+        assert_eq!(state[&target].info.balance, U256::from(3));
+        // The delegation of the target is not followed.
+        assert!(!state.contains_key(&delegate));
+    }
+
+    #[test]
+    #[cfg(feature = "optional_size_limits")]
+    fn disable_size_limits() {
+        // Initcode returning 30KB of zeroed memory as runtime code.
+        let initcode = Bytes::from(vec![PUSH2, 0x78, 0x00, PUSH1, 0x00, RETURN]);
+
+        let deploy = |disable_size_limits| {
+            let mut evm = Evm::<EthereumWiring<InMemoryDB, ()>>::builder()
+                .with_spec_id(SpecId::CANCUN)
+                .with_db(InMemoryDB::default())
+                .with_default_ext_ctx()
+                .modify_cfg_env(|cfg| cfg.disable_size_limits = disable_size_limits)
+                .modify_tx_env(|tx| {
+                    tx.transact_to = TxKind::Create;
+                    tx.data = initcode.clone();
+                    tx.gas_limit = 10_000_000;
+                })
+                .build();
+            evm.transact().unwrap().result
+        };
+
+        let result = deploy(true);
+        assert!(result.is_success(), "{result:?}");
+
+        let result = deploy(false);
+        assert!(matches!(
+            result,
+            ExecutionResult::Halt {
+                reason: HaltReason::CreateContractSizeLimit,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn transact_gas_only() {
+        let caller = TEST_CALLER;
+        let contract = address!("00000000000000000000000000000000000000a0");
+
+        // Store to and clear slot 0, which refunds gas.
+        let code = Bytecode::new_raw(
+            vec![
+                PUSH1, 0x01, PUSH1, 0x00, SSTORE, PUSH1, 0x00, PUSH1, 0x00, SSTORE, STOP,
+            ]
+            .into(),
+        );
+        let mut db = InMemoryDB::default();
+        db.insert_account_info(caller, AccountInfo::from_balance(U256::from(1_000_000)));
+        db.insert_account_info(contract, AccountInfo::from_bytecode(code));
+
+        let mut evm = call_evm_builder(db, (), contract, 100_000)
+            .with_spec_id(SpecId::CANCUN)
+            .modify_tx_env(|tx| {
+                tx.gas_price = U256::from(1);
+            })
+            .build();
+
+        let gas_used = evm.transact_gas_only().unwrap();
+        let result = evm.transact().unwrap().result;
+        assert!(result.is_success());
+        assert_eq!(gas_used, result.gas_used());
+        assert_ne!(gas_used, 0);
+    }
+
+    #[test]
+    #[cfg(feature = "optional_depth_gas_multiplier")]
+    fn depth_gas_multiplier() {
+        use bytecode::opcode::{SUB, SWAP1};
+        use core::num::NonZeroU64;
+
+        let first = address!("00000000000000000000000000000000000000a1");
+        let second = address!("00000000000000000000000000000000000000a2");
+        let measured = address!("00000000000000000000000000000000000000a3");
+
+        let call = |target: Address| {
+            let mut code = vec![PUSH1, 0, PUSH1, 0, PUSH1, 0, PUSH1, 0, PUSH1, 0, PUSH20];
+            code.extend_from_slice(target.as_slice());
+            code.extend_from_slice(&[GAS, CALL, STOP]);
+            AccountInfo::from_bytecode(Bytecode::new_raw(code.into()))
+        };
+        let mut db = InMemoryDB::default();
+        db.insert_account_info(first, call(second));
+        db.insert_account_info(second, call(measured));
+        // Stores the gas spent by `PUSH1 0, POP, GAS` at slot 0.
+        db.insert_account_info(
+            measured,
+            AccountInfo::from_bytecode(Bytecode::new_raw(
+                vec![GAS, PUSH1, 0, POP, GAS, SWAP1, SUB, PUSH1, 0, SSTORE, STOP].into(),
+            )),
+        );
+
+        let mut evm = call_evm_builder(db, (), first, 1_000_000)
+            .modify_cfg_env(|cfg| cfg.depth_gas_multiplier = NonZeroU64::new(2))
+            .build();
+
+        let base = gas::VERYLOW + gas::BASE + gas::BASE;
+        let mut spent = |target: Address| {
+            evm.tx_mut().transact_to = TxKind::Call(target);
+            let ResultAndState { result, state, .. } = evm.transact().unwrap();
+            assert!(result.is_success());
+            state[&measured].storage[&U256::ZERO].present_value
+        };
+        // At depth 2 and 0.
+        assert_eq!(spent(first), U256::from(4 * base));
+        assert_eq!(spent(measured), U256::from(base));
+    }
+
+    #[test]
+    fn transact_with_gas_limit() {
+        let contract = address!("00000000000000000000000000000000000000a0");
+        let mut db = InMemoryDB::default();
+        db.insert_account_info(
+            contract,
+            AccountInfo::from_bytecode(Bytecode::new_raw(
+                vec![PUSH1, 1, PUSH1, 0, SSTORE, STOP].into(),
+            )),
+        );
+
+        let mut evm = call_evm_builder(db, (), contract, 1_000_000)
+            .modify_cfg_env(|cfg| cfg.analysis_cache_size = 16)
+            .build();
+        let gas_used = evm.transact_gas_only().unwrap();
+        let snapshot = evm.pre_execution_snapshot().unwrap();
+
+        // Binary search of the lowest gas limit the transaction succeeds with.
+        let (mut low, mut high) = (21_000, 1_000_000);
+        for _ in 0..20 {
+            let mid = (low + high) / 2;
+            match evm.transact_with_gas_limit_from(&snapshot, mid).unwrap() {
+                (true, used) => {
+                    assert_eq!(used, gas_used);
+                    high = mid;
+                }
+                (false, used) => {
+                    assert!(used <= mid);
+                    low = mid;
+                }
+            }
+        }
+        assert_eq!(high, low + 1);
+        assert_eq!(high, gas_used);
+        assert_eq!(
+            evm.transact_with_gas_limit(20_000).unwrap(),
+            (false, 20_000)
+        );
+        // Limits above the transaction gas limit are capped to it.
+        assert_eq!(
+            evm.transact_with_gas_limit(u64::MAX).unwrap(),
+            (true, gas_used)
+        );
+        assert_eq!(evm.context.evm.analysis_cache.analyses(), 1);
+    }
+
+    #[test]
+    fn transact_with_gas_limit_from_snapshot() {
+        let caller = TEST_CALLER;
+        let mut db = InMemoryDB::default();
+        db.insert_account_info(caller, AccountInfo::from_balance(U256::from(1_000_000)));
+
+        let mut evm = call_evm_builder(db, (), Address::ZERO, 30_000)
+            .modify_tx_env(|tx| {
+                tx.gas_price = U256::from(1);
+            })
+            .build();
+        let snapshot = evm.pre_execution_snapshot().unwrap();
+
+        // The snapshot holds the validated caller, so attempts do not load it again.
+        evm.db_mut()
+            .insert_account_info(caller, AccountInfo::default());
+        assert!(matches!(
+            evm.transact_with_gas_limit(21_000),
+            Err(EVMError::Transaction(
+                InvalidTransaction::LackOfFundForMaxFee { .. }
+            ))
+        ));
+        assert_eq!(
+            evm.transact_with_gas_limit_from(&snapshot, 21_000).unwrap(),
+            (true, 21_000)
+        );
+        assert_eq!(
+            evm.transact_with_gas_limit_from(&snapshot, 20_999).unwrap(),
+            (false, 20_999)
+        );
+    }
+
+    #[test]
+    fn is_state_changing() {
+        let caller = TEST_CALLER;
+        let view = address!("00000000000000000000000000000000000000a0");
+        let write = address!("00000000000000000000000000000000000000a1");
+        let reverted_write = address!("00000000000000000000000000000000000000a2");
+
+        let mut db = InMemoryDB::default();
+        db.insert_account_info(caller, AccountInfo::from_balance(U256::from(1_000_000)));
+        db.insert_account_info(
+            view,
+            AccountInfo::from_bytecode(Bytecode::new_raw(
+                vec![PUSH1, 0x00, SLOAD, POP, STOP].into(),
+            )),
+        );
+        db.insert_account_info(
+            write,
+            AccountInfo::from_bytecode(Bytecode::new_raw(
+                vec![PUSH1, 0x01, PUSH1, 0x00, SSTORE, STOP].into(),
+            )),
+        );
+        db.insert_account_info(
+            reverted_write,
+            AccountInfo::from_bytecode(Bytecode::new_raw(
+                vec![
+                    PUSH1, 0x01, PUSH1, 0x00, SSTORE, PUSH1, 0x00, PUSH1, 0x00, REVERT,
+                ]
+                .into(),
+            )),
+        );
+
+        let mut evm = call_evm_builder(db, (), view, 100_000)
+            .with_spec_id(SpecId::CANCUN)
+            .build();
+
+        let mut is_state_changing = |to, value| {
+            evm.tx_mut().transact_to = TxKind::Call(to);
+            evm.tx_mut().value = U256::from(value);
+            evm.is_state_changing().unwrap()
+        };
+        assert!(!is_state_changing(view, 0));
+        assert!(is_state_changing(write, 0));
+        assert!(!is_state_changing(reverted_write, 0));
+        // Value transfers change balances.
+        assert!(is_state_changing(view, 1));
+    }
+
+    #[test]
+    fn keccak256_hasher() {
+        let contract = address!("00000000000000000000000000000000000000a0");
+        let mut db = InMemoryDB::default();
+        // Stores the hash of empty memory at slot 0 and the address of an empty creation at slot 1.
+        db.insert_account_info(
+            contract,
+            AccountInfo::from_bytecode(Bytecode::new_raw(
+                vec![
+                    PUSH0, PUSH0, KECCAK256, PUSH0, SSTORE, PUSH0, PUSH0, PUSH0, CREATE, PUSH1,
+                    0x01, SSTORE, STOP,
+                ]
+                .into(),
+            )),
+        );
+
+        let mut evm = call_evm_builder(db, (), contract, 1_000_000)
+            .modify_cfg_env(|cfg| {
+                cfg.keccak256_hasher = Some(Keccak256Hasher::new(|_| B256::repeat_byte(0xab)))
+            })
+            .build();
+
+        let ResultAndState { result, state, .. } = evm.transact().unwrap();
+        assert!(result.is_success());
+        let hash = B256::repeat_byte(0xab);
+        let created = Address::from_word(hash);
+        let storage = &state[&contract].storage;
+        assert_eq!(storage[&U256::ZERO].present_value, hash.into());
+        assert_eq!(
+            storage[&U256::from(1)].present_value,
+            created.into_word().into()
+        );
+        assert!(state.contains_key(&created));
+    }
+
+    #[test]
+    fn to_receipt() {
+        use bytecode::opcode::LOG1;
+        use primitives::{alloy_primitives::BloomInput, Log};
+
+        let contract = address!("00000000000000000000000000000000000000a0");
+        let topic = B256::with_last_byte(0xaa);
+        let mut db = InMemoryDB::default();
+        // Emits a log with an empty body and the topic 0xaa.
+        db.insert_account_info(
+            contract,
+            AccountInfo::from_bytecode(Bytecode::new_raw(
+                vec![PUSH1, 0xaa, PUSH0, PUSH0, LOG1, STOP].into(),
+            )),
+        );
+
+        let mut evm = call_evm_builder(db, (), contract, 100_000).build();
+
+        let output = evm.transact().unwrap();
+        let receipt = output.to_receipt(50_000 + output.result.gas_used());
+        assert!(receipt.status);
+        assert_eq!(
+            receipt.cumulative_gas_used,
+            50_000 + output.result.gas_used()
+        );
+        assert_eq!(
+            receipt.logs,
+            [Log::new_unchecked(contract, vec![topic], Bytes::new())]
+        );
+        assert!(receipt
+            .logs_bloom
+            .contains_input(BloomInput::Raw(contract.as_slice())));
+        assert!(receipt
+            .logs_bloom
+            .contains_input(BloomInput::Raw(topic.as_slice())));
+        assert!(!receipt
+            .logs_bloom
+            .contains_input(BloomInput::Raw(B256::ZERO.as_slice())));
+    }
+
+    #[test]
+    fn create_at_precompile_address() {
+        // A hasher deriving every created address as the ecrecover precompile.
+        let mut evm = Evm::<EthereumWiring<InMemoryDB, ()>>::builder()
+            .with_db(InMemoryDB::default())
+            .with_default_ext_ctx()
+            .modify_cfg_env(|cfg| {
+                cfg.keccak256_hasher = Some(Keccak256Hasher::new(|_| B256::with_last_byte(0x01)))
+            })
+            .modify_tx_env(|tx| {
+                tx.transact_to = TxKind::Create;
+                tx.data = Bytes::from(vec![STOP]);
+                tx.gas_limit = 100_000;
+            })
+            .build();
+
+        let ResultAndState { result, state, .. } = evm.transact().unwrap();
+        assert_eq!(
+            result,
+            ExecutionResult::Halt {
+                reason: HaltReason::CreateCollision,
+                gas_used: 100_000,
+            }
+        );
+        assert!(!state.contains_key(&Address::with_last_byte(0x01)));
+    }
 }
@@ -0,0 +1,108 @@
+//! Tests of the tracing spans around the handler stages.
+
+use crate::Evm;
+use bytecode::{
+    opcode::{CALL, GAS, PUSH1, PUSH20, STOP},
+    Bytecode,
+};
+use core::fmt::Debug;
+use database::InMemoryDB;
+use primitives::{address, TxKind};
+use state::AccountInfo;
+use std::sync::{Arc, Mutex};
+use tracing::{
+    field::{Field, Visit},
+    span, Subscriber,
+};
+use tracing_subscriber::{
+    layer::{Context, SubscriberExt},
+    registry::LookupSpan,
+    Layer, Registry,
+};
+use wiring::EthereumWiring;
+
+type SpanFields = Vec<(&'static str, String)>;
+
+/// Records the names and fields of the spans, in creation order.
+#[derive(Clone, Default)]
+struct Spans(Arc<Mutex<Vec<(&'static str, SpanFields)>>>);
+
+struct Fields<'a>(&'a mut SpanFields);
+
+impl Visit for Fields<'_> {
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        self.0.push((field.name(), format!("{value:?}")));
+    }
+}
+
+impl<S: Subscriber + for<'a> LookupSpan<'a>> Layer<S> for Spans {
+    fn on_new_span(&self, attrs: &span::Attributes<'_>, id: &span::Id, ctx: Context<'_, S>) {
+        let mut fields = Vec::new();
+        attrs.record(&mut Fields(&mut fields));
+        let mut spans = self.0.lock().unwrap();
+        ctx.span(id).unwrap().extensions_mut().insert(spans.len());
+        spans.push((attrs.metadata().name(), fields));
+    }
+
+    fn on_record(&self, id: &span::Id, values: &span::Record<'_>, ctx: Context<'_, S>) {
+        let index = *ctx.span(id).unwrap().extensions().get::<usize>().unwrap();
+        values.record(&mut Fields(&mut self.0.lock().unwrap()[index].1));
+    }
+}
+
+#[test]
+fn tracing_spans() {
+    let contract = address!("00000000000000000000000000000000000000a0");
+    let callee = address!("00000000000000000000000000000000000000a1");
+    let mut code = vec![PUSH1, 0, PUSH1, 0, PUSH1, 0, PUSH1, 0, PUSH1, 0, PUSH20];
+    code.extend_from_slice(callee.as_slice());
+    code.extend_from_slice(&[GAS, CALL, STOP]);
+
+    let mut db = InMemoryDB::default();
+    db.insert_account_info(
+        contract,
+        AccountInfo::from_bytecode(Bytecode::new_raw(code.into())),
+    );
+    db.insert_account_info(
+        callee,
+        AccountInfo::from_bytecode(Bytecode::new_raw(vec![STOP].into())),
+    );
+
+    let mut evm = Evm::<EthereumWiring<InMemoryDB, ()>>::builder()
+        .with_db(db)
+        .with_default_ext_ctx()
+        .modify_tx_env(|tx| {
+            tx.caller = address!("1000000000000000000000000000000000000000");
+            tx.transact_to = TxKind::Call(contract);
+            tx.gas_limit = 100_000;
+        })
+        .build();
+
+    let spans = Spans::default();
+    let subscriber = Registry::default().with(spans.clone());
+    let result = tracing::subscriber::with_default(subscriber, || evm.transact())
+        .unwrap()
+        .result;
+    assert!(result.is_success());
+
+    let spans = spans.0.lock().unwrap();
+    let names: Vec<_> = spans.iter().map(|(name, _)| *name).collect();
+    assert_eq!(
+        names,
+        ["validation", "pre_execution", "execution", "post_execution"]
+    );
+    // Returns the last recorded value of a field.
+    let field = |span: usize, name: &str| {
+        spans[span]
+            .1
+            .iter()
+            .rev()
+            .find(|(field, _)| *field == name)
+            .map(|(_, value)| value.clone())
+    };
+    let gas_used = Some(result.gas_used().to_string());
+    assert_eq!(field(2, "frame_count"), Some("2".to_string()));
+    assert_eq!(field(2, "gas_used"), gas_used);
+    assert_eq!(field(3, "gas_used"), gas_used);
+    assert_eq!(field(3, "result"), Some("\"success\"".to_string()));
+}
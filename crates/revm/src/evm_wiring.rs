@@ -3,15 +3,35 @@ use crate::{
     EvmHandler,
 };
 use database_interface::Database;
-use interpreter::table::InstructionTables;
+use interpreter::{table::InstructionTables, InstructionResult, SuccessOrHalt};
 use specification::spec_to_generic;
 use std::fmt::Debug;
 use std::vec::Vec;
 use wiring::{EthereumWiring, EvmWiring as PrimitiveEvmWiring};
 
+/// Wiring of a chain, the extension point for chains other than Ethereum.
+///
+/// A custom chain implements [`wiring::EvmWiring`] to choose its database, block, transaction,
+/// hardfork and halt reason types, then this trait to build its [`EvmHandler`]. The usual
+/// starting point is [`EvmHandler::mainnet_with_spec`], after which any handle of
+/// [`validation`](EvmHandler::validation), [`pre_execution`](EvmHandler::pre_execution),
+/// [`execution`](EvmHandler::execution) or [`post_execution`](EvmHandler::post_execution)
+/// can be replaced, or a register appended as Optimism does. Registers are reapplied when the
+/// hardfork changes, so they are preferred for handles that depend on it.
+///
+/// The `custom_chain` example wires a chain without base fee by overriding the env validation
+/// and beneficiary reward handles of the mainnet handler.
 pub trait EvmWiring: PrimitiveEvmWiring {
     /// Creates a new handler with the given hardfork.
     fn handler<'evm>(hardfork: Self::Hardfork) -> EvmHandler<'evm, Self>;
+
+    /// Maps the result of the first frame to the outcome of the transaction.
+    ///
+    /// Used by the mainnet output handle. Chains with their own halt reasons override it to map
+    /// instruction results to them; by default the mainnet halt reasons are converted.
+    fn success_or_halt(result: InstructionResult) -> SuccessOrHalt<Self::HaltReason> {
+        result.into()
+    }
 }
 
 impl<DB: Database, EXT: Debug> EvmWiring for EthereumWiring<DB, EXT> {
@@ -33,3 +53,102 @@ impl<DB: Database, EXT: Debug> EvmWiring for EthereumWiring<DB, EXT> {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Evm;
+    use bytecode::{opcode, Bytecode};
+    use database::InMemoryDB;
+    use primitives::{address, Bytes, TxKind};
+    use specification::hardfork::SpecId;
+    use state::AccountInfo;
+    use wiring::{
+        default::{block::BlockEnv, TxEnv},
+        result::{ExecutionResult, HaltReason},
+    };
+
+    /// Halt reason of a chain that reports the designated invalid opcode separately.
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    enum CustomHaltReason {
+        Base(HaltReason),
+        DesignatedInvalid,
+    }
+
+    impl From<HaltReason> for CustomHaltReason {
+        fn from(value: HaltReason) -> Self {
+            Self::Base(value)
+        }
+    }
+
+    #[derive(Debug)]
+    struct CustomHaltWiring;
+
+    impl PrimitiveEvmWiring for CustomHaltWiring {
+        type ExternalContext = ();
+        type ChainContext = ();
+        type Database = InMemoryDB;
+        type Block = BlockEnv;
+        type Transaction = TxEnv;
+        type Hardfork = SpecId;
+        type HaltReason = CustomHaltReason;
+    }
+
+    impl EvmWiring for CustomHaltWiring {
+        fn handler<'evm>(hardfork: Self::Hardfork) -> EvmHandler<'evm, Self> {
+            EvmHandler::mainnet_with_spec(hardfork)
+        }
+
+        fn success_or_halt(result: InstructionResult) -> SuccessOrHalt<Self::HaltReason> {
+            match result {
+                InstructionResult::InvalidFEOpcode => {
+                    SuccessOrHalt::Halt(CustomHaltReason::DesignatedInvalid)
+                }
+                result => result.into(),
+            }
+        }
+    }
+
+    #[test]
+    fn custom_halt_reason() {
+        let contract = address!("00000000000000000000000000000000000000a0");
+
+        let transact = |code: &'static [u8]| {
+            let mut db = InMemoryDB::default();
+            db.insert_account_info(
+                contract,
+                AccountInfo::from_bytecode(Bytecode::new_raw(Bytes::from_static(code))),
+            );
+            let mut evm = Evm::<CustomHaltWiring>::builder()
+                .with_db(db)
+                .with_default_ext_ctx()
+                .with_tx_env(TxEnv {
+                    transact_to: TxKind::Call(contract),
+                    gas_limit: 100_000,
+                    ..Default::default()
+                })
+                .build();
+            evm.transact().unwrap().result
+        };
+
+        assert!(matches!(
+            transact(&[opcode::INVALID]),
+            ExecutionResult::Halt {
+                reason: CustomHaltReason::DesignatedInvalid,
+                ..
+            }
+        ));
+        // Other results keep the mainnet mapping.
+        assert!(matches!(
+            transact(&[opcode::PUSH1]),
+            ExecutionResult::Success { .. }
+        ));
+        assert!(matches!(
+            transact(&[0x0c]),
+            ExecutionResult::Halt {
+                reason: CustomHaltReason::Base(HaltReason::OpcodeNotFound),
+                ..
+            }
+        ));
+    }
+}
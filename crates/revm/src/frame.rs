@@ -3,10 +3,16 @@ use core::ops::Range;
 use interpreter::{
     CallOutcome, CreateOutcome, Gas, InstructionResult, Interpreter, InterpreterResult,
 };
-use primitives::Address;
-use std::boxed::Box;
+use primitives::{hex, Address, U256};
+use std::{boxed::Box, format, string::String};
 use wiring::result::Output;
 
+/// Selector of `Error(string)`, the error of `revert` and `require` with a message.
+const ERROR_SELECTOR: [u8; 4] = [0x08, 0xc3, 0x79, 0xa0];
+
+/// Selector of `Panic(uint256)`, the error of failed assertions and arithmetic checks.
+const PANIC_SELECTOR: [u8; 4] = [0x4e, 0x48, 0x7b, 0x71];
+
 /// Call CallStackFrame.
 #[derive(Debug)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -130,6 +136,61 @@ impl FrameResult {
     pub fn instruction_result(&self) -> InstructionResult {
         self.interpreter_result().result
     }
+
+    /// Returns the revert reason decoded from the output, `None` if the frame did not revert.
+    ///
+    /// `Error(string)` is decoded to its message and `Panic(uint256)` to a description of its
+    /// code. Other output, e.g. custom errors, is returned as hex.
+    pub fn decode_revert_reason(&self) -> Option<String> {
+        if !self.instruction_result().is_revert() {
+            return None;
+        }
+        Some(decode_revert_reason(&self.interpreter_result().output))
+    }
+}
+
+/// Decodes `Error(string)` and `Panic(uint256)` revert data, see
+/// [`FrameResult::decode_revert_reason`].
+fn decode_revert_reason(output: &[u8]) -> String {
+    let message = match output.split_first_chunk::<4>() {
+        Some((&ERROR_SELECTOR, data)) => decode_string(data),
+        Some((&PANIC_SELECTOR, data)) if data.len() == 32 => {
+            let code = U256::from_be_slice(data);
+            let description = match code.as_limbs()[0] {
+                _ if code > U256::from(u8::MAX) => None,
+                0x00 => Some("generic panic"),
+                0x01 => Some("assertion failed"),
+                0x11 => Some("arithmetic underflow or overflow"),
+                0x12 => Some("division or modulo by zero"),
+                0x21 => Some("invalid enum value"),
+                0x22 => Some("invalid storage byte array encoding"),
+                0x31 => Some("pop on empty array"),
+                0x32 => Some("array index out of bounds"),
+                0x41 => Some("out of memory"),
+                0x51 => Some("call to zero-initialized function"),
+                _ => None,
+            };
+            Some(match description {
+                Some(description) => format!("panic: {description} ({code:#x})"),
+                None => format!("panic: {code:#x}"),
+            })
+        }
+        _ => None,
+    };
+    message.unwrap_or_else(|| hex::encode_prefixed(output))
+}
+
+/// Decodes the ABI encoding of a single string, `None` if malformed or not UTF-8.
+fn decode_string(data: &[u8]) -> Option<String> {
+    let word = |offset: usize| -> Option<usize> {
+        let word = data.get(offset..offset.checked_add(32)?)?;
+        usize::try_from(U256::from_be_slice(word)).ok()
+    };
+    let offset = word(0)?;
+    let len = word(offset)?;
+    let start = offset.checked_add(32)?;
+    let bytes = data.get(start..start.checked_add(len)?)?;
+    String::from_utf8(bytes.to_vec()).ok()
 }
 
 /// Contains either a frame or a result.
@@ -295,3 +356,55 @@ impl FrameOrResult {
         }))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use primitives::{bytes, Bytes};
+
+    fn reverted(output: Bytes) -> FrameResult {
+        FrameResult::Call(CallOutcome::new(
+            InterpreterResult::new(InstructionResult::Revert, output, Gas::new(0)),
+            0..0,
+        ))
+    }
+
+    #[test]
+    fn decode_revert_reason() {
+        // `Error("not owner")`.
+        let error = reverted(bytes!(
+            "08c379a0"
+            "0000000000000000000000000000000000000000000000000000000000000020"
+            "0000000000000000000000000000000000000000000000000000000000000009"
+            "6e6f74206f776e65720000000000000000000000000000000000000000000000"
+        ));
+        assert_eq!(error.decode_revert_reason().as_deref(), Some("not owner"));
+
+        // `Panic(0x11)`.
+        let panic = reverted(bytes!(
+            "4e487b71"
+            "0000000000000000000000000000000000000000000000000000000000000011"
+        ));
+        assert_eq!(
+            panic.decode_revert_reason().as_deref(),
+            Some("panic: arithmetic underflow or overflow (0x11)")
+        );
+
+        // A custom error `Unauthorized()`.
+        let custom = reverted(bytes!("82b42900"));
+        assert_eq!(custom.decode_revert_reason().as_deref(), Some("0x82b42900"));
+
+        // A truncated `Error(string)` is returned as hex.
+        let truncated = reverted(bytes!("08c379a00000"));
+        assert_eq!(
+            truncated.decode_revert_reason().as_deref(),
+            Some("0x08c379a00000")
+        );
+
+        let success = FrameResult::Call(CallOutcome::new(
+            InterpreterResult::new(InstructionResult::Stop, Bytes::new(), Gas::new(0)),
+            0..0,
+        ));
+        assert_eq!(success.decode_revert_reason(), None);
+    }
+}
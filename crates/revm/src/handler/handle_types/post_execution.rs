@@ -1,7 +1,7 @@
 // Includes.
 use crate::{handler::mainnet, Context, EvmWiring, FrameResult};
 use interpreter::Gas;
-use specification::hardfork::Spec;
+use specification::{eip4895::Withdrawal, hardfork::Spec};
 use std::sync::Arc;
 use wiring::result::{EVMResult, EVMResultGeneric, ResultAndState};
 
@@ -27,6 +27,10 @@ pub type EndHandle<'a, EvmWiringT> =
 /// context. It will always be called even on failed validation.
 pub type ClearHandle<'a, EvmWiringT> = Arc<dyn Fn(&mut Context<EvmWiringT>) + 'a>;
 
+/// Withdrawals handle, credits block withdrawals to their recipients.
+pub type ApplyWithdrawalsHandle<'a, EvmWiringT> =
+    Arc<dyn Fn(&mut Context<EvmWiringT>, &[Withdrawal]) -> EVMResultGeneric<(), EvmWiringT> + 'a>;
+
 /// Refund handle, calculates the final refund.
 pub type RefundHandle<'a, EvmWiringT> = Arc<dyn Fn(&mut Context<EvmWiringT>, &mut Gas, i64) + 'a>;
 /// Handles related to post execution after the stack loop is finished.
@@ -46,6 +50,8 @@ pub struct PostExecutionHandler<'a, EvmWiringT: EvmWiring> {
     /// Clear handle will be called always. In comparison to end that
     /// is called only on execution end, clear handle is called even if validation fails.
     pub clear: ClearHandle<'a, EvmWiringT>,
+    /// Credits EIP-4895 withdrawals. Not part of transaction execution, called once per block.
+    pub apply_withdrawals: ApplyWithdrawalsHandle<'a, EvmWiringT>,
 }
 
 impl<'a, EvmWiringT: EvmWiring + 'a> PostExecutionHandler<'a, EvmWiringT> {
@@ -58,6 +64,7 @@ impl<'a, EvmWiringT: EvmWiring + 'a> PostExecutionHandler<'a, EvmWiringT> {
             output: Arc::new(mainnet::output::<EvmWiringT>),
             end: Arc::new(mainnet::end::<EvmWiringT>),
             clear: Arc::new(mainnet::clear::<EvmWiringT>),
+            apply_withdrawals: Arc::new(mainnet::apply_withdrawals::<EvmWiringT>),
         }
     }
 }
@@ -107,4 +114,13 @@ impl<'a, EvmWiringT: EvmWiring> PostExecutionHandler<'a, EvmWiringT> {
     pub fn clear(&self, context: &mut Context<EvmWiringT>) {
         (self.clear)(context)
     }
+
+    /// Credits withdrawals to their recipients in the journal.
+    pub fn apply_withdrawals(
+        &self,
+        context: &mut Context<EvmWiringT>,
+        withdrawals: &[Withdrawal],
+    ) -> EVMResultGeneric<(), EvmWiringT> {
+        (self.apply_withdrawals)(context, withdrawals)
+    }
 }
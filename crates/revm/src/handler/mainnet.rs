@@ -12,7 +12,9 @@ pub use execution::{
     first_frame_creation, insert_call_outcome, insert_create_outcome, insert_eofcreate_outcome,
     last_frame_return,
 };
-pub use post_execution::{clear, end, output, refund, reimburse_caller, reward_beneficiary};
+pub use post_execution::{
+    apply_withdrawals, clear, end, output, refund, reimburse_caller, reward_beneficiary,
+};
 pub use pre_execution::{
     apply_eip7702_auth_list, deduct_caller, deduct_caller_inner, load_accounts, load_precompiles,
 };
@@ -7,12 +7,14 @@ use core::mem;
 use interpreter::{
     return_ok, return_revert, table::InstructionTables, CallInputs, CallOutcome, CallScheme,
     CallValue, CreateInputs, CreateOutcome, CreateScheme, EOFCreateInputs, EOFCreateKind, Gas,
-    InterpreterAction, InterpreterResult, NewFrameAction, SharedMemory, EMPTY_SHARED_MEMORY,
+    InstructionResult, InterpreterAction, InterpreterResult, NewFrameAction, SharedMemory,
+    EMPTY_SHARED_MEMORY,
 };
-use primitives::TxKind;
+use primitives::{Bytes, TxKind};
 use specification::hardfork::{Spec, SpecId};
 use std::boxed::Box;
 use wiring::{
+    default::{CfgEnv, ReturnDataLimitMode},
     result::{EVMError, EVMResultGeneric},
     Transaction,
 };
@@ -27,9 +29,23 @@ pub fn execute_frame<EvmWiringT: EvmWiring, SPEC: Spec>(
 ) -> EVMResultGeneric<InterpreterAction, EvmWiringT> {
     let interpreter = frame.interpreter_mut();
     let memory = mem::replace(shared_memory, EMPTY_SHARED_MEMORY);
-    let next_action = match instruction_tables {
-        InstructionTables::Plain(table) => interpreter.run(memory, table, context),
-        InstructionTables::Boxed(table) => interpreter.run(memory, table, context),
+    // The journal depth is one in the transaction's frame.
+    let gas_factor = context
+        .evm
+        .env
+        .cfg
+        .depth_gas_multiplier()
+        .map(|multiplier| multiplier.saturating_pow(context.evm.journaled_state.depth as u32 - 1))
+        .filter(|factor| factor.get() != 1);
+    let next_action = match (instruction_tables, gas_factor) {
+        (InstructionTables::Plain(table), None) => interpreter.run(memory, table, context),
+        (InstructionTables::Boxed(table), None) => interpreter.run(memory, table, context),
+        (InstructionTables::Plain(table), Some(factor)) => {
+            interpreter.run_scaled(memory, table, context, factor)
+        }
+        (InstructionTables::Boxed(table), Some(factor)) => {
+            interpreter.run_scaled(memory, table, context, factor)
+        }
     };
     // Take the shared memory back.
     *shared_memory = interpreter.take_memory();
@@ -144,13 +160,31 @@ pub fn insert_call_outcome<EvmWiringT: EvmWiring>(
 ) -> EVMResultGeneric<(), EvmWiringT> {
     context.evm.take_error().map_err(EVMError::Database)?;
 
-    frame
-        .frame_data_mut()
-        .interpreter
-        .insert_call_outcome(shared_memory, outcome);
+    let mut outcome = outcome;
+    let interpreter = &mut frame.frame_data_mut().interpreter;
+    if limit_return_data(&context.evm.env.cfg, &mut outcome.result.output) {
+        interpreter.instruction_result = InstructionResult::MemoryLimitOOG;
+        return Ok(());
+    }
+    interpreter.insert_call_outcome(shared_memory, outcome);
     Ok(())
 }
 
+/// Applies [`CfgEnv::max_return_data_size()`] to the output of a sub frame before it is copied into
+/// the return data buffer of its caller. Returns `true` if the caller has to halt.
+fn limit_return_data(cfg: &CfgEnv, output: &mut Bytes) -> bool {
+    match cfg.max_return_data_size() {
+        Some(max) if output.len() > max => match cfg.return_data_limit_mode() {
+            ReturnDataLimitMode::Truncate => {
+                output.truncate(max);
+                false
+            }
+            ReturnDataLimitMode::Halt => true,
+        },
+        _ => false,
+    }
+}
+
 /// Handle frame sub create.
 #[inline]
 pub fn create<EvmWiringT: EvmWiring, SPEC: Spec>(
@@ -188,10 +222,16 @@ pub fn insert_create_outcome<EvmWiringT: EvmWiring>(
 ) -> EVMResultGeneric<(), EvmWiringT> {
     context.evm.take_error().map_err(EVMError::Database)?;
 
-    frame
-        .frame_data_mut()
-        .interpreter
-        .insert_create_outcome(outcome);
+    // Only the output of a reverted creation is copied into the return data buffer.
+    let mut outcome = outcome;
+    let interpreter = &mut frame.frame_data_mut().interpreter;
+    if outcome.instruction_result().is_revert()
+        && limit_return_data(&context.evm.env.cfg, &mut outcome.result.output)
+    {
+        interpreter.instruction_result = InstructionResult::MemoryLimitOOG;
+        return Ok(());
+    }
+    interpreter.insert_create_outcome(outcome);
     Ok(())
 }
 
@@ -232,10 +272,16 @@ pub fn insert_eofcreate_outcome<EvmWiringT: EvmWiring>(
 ) -> EVMResultGeneric<(), EvmWiringT> {
     context.evm.take_error().map_err(EVMError::Database)?;
 
-    frame
-        .frame_data_mut()
-        .interpreter
-        .insert_eofcreate_outcome(outcome);
+    // Only the output of a reverted creation is copied into the return data buffer.
+    let mut outcome = outcome;
+    let interpreter = &mut frame.frame_data_mut().interpreter;
+    if outcome.instruction_result().is_revert()
+        && limit_return_data(&context.evm.env.cfg, &mut outcome.result.output)
+    {
+        interpreter.instruction_result = InstructionResult::MemoryLimitOOG;
+        return Ok(());
+    }
+    interpreter.insert_eofcreate_outcome(outcome);
     Ok(())
 }
 
@@ -244,7 +290,6 @@ mod tests {
     use super::*;
     use crate::handler::mainnet::refund;
     use interpreter::InstructionResult;
-    use primitives::Bytes;
     use specification::hardfork::CancunSpec;
     use wiring::{default::EnvWiring, DefaultEthereumWiring};
 
@@ -254,7 +299,7 @@ mod tests {
         env.tx.gas_limit = 100;
 
         let mut ctx = Context::default();
-        ctx.evm.inner.env = Box::new(env);
+        *ctx.evm.inner.env = env;
         let mut first_frame = FrameResult::Call(CallOutcome::new(
             InterpreterResult {
                 result: instruction_result,
@@ -299,4 +344,81 @@ mod tests {
         assert_eq!(gas.spent(), 10);
         assert_eq!(gas.refunded(), 0);
     }
+
+    #[test]
+    #[cfg(feature = "optional_max_return_data_size")]
+    fn test_max_return_data_size() {
+        use crate::test_utils::call_evm_builder;
+        use bytecode::{
+            opcode::{CALL, GAS, MSTORE, POP, PUSH0, PUSH1, PUSH2, PUSH20, RETURN, RETURNDATASIZE},
+            Bytecode,
+        };
+        use database::InMemoryDB;
+        use primitives::{address, U256};
+        use state::AccountInfo;
+        use wiring::{
+            default::ReturnDataLimitMode,
+            result::{ExecutionResult, HaltReason, OutOfGasError},
+        };
+
+        let contract = address!("00000000000000000000000000000000000000a0");
+        let callee = address!("00000000000000000000000000000000000000a1");
+
+        // Calls the callee and returns the size of its return data.
+        let mut code = vec![PUSH0, PUSH0, PUSH0, PUSH0, PUSH0, PUSH20];
+        code.extend_from_slice(callee.as_slice());
+        code.extend_from_slice(&[
+            GAS,
+            CALL,
+            POP,
+            RETURNDATASIZE,
+            PUSH0,
+            MSTORE,
+            PUSH1,
+            0x20,
+            PUSH0,
+            RETURN,
+        ]);
+
+        let mut db = InMemoryDB::default();
+        db.insert_account_info(
+            contract,
+            AccountInfo::from_bytecode(Bytecode::new_raw(code.into())),
+        );
+        // Returns 256 zero bytes.
+        db.insert_account_info(
+            callee,
+            AccountInfo::from_bytecode(Bytecode::new_raw(
+                vec![PUSH2, 0x01, 0x00, PUSH0, RETURN].into(),
+            )),
+        );
+
+        let transact = |limit: Option<usize>, mode: ReturnDataLimitMode| {
+            let mut evm = call_evm_builder(db.clone(), (), contract, 1_000_000)
+                .modify_cfg_env(|cfg| {
+                    cfg.max_return_data_size = limit;
+                    cfg.return_data_limit_mode = mode;
+                })
+                .build();
+            evm.transact().unwrap().result
+        };
+        let return_data_size = |result: ExecutionResult<HaltReason>| {
+            assert!(result.is_success(), "{result:?}");
+            U256::from_be_slice(result.output().unwrap())
+        };
+
+        let unlimited = transact(None, ReturnDataLimitMode::Truncate);
+        assert_eq!(return_data_size(unlimited), U256::from(0x100));
+
+        let truncated = transact(Some(0x20), ReturnDataLimitMode::Truncate);
+        assert_eq!(return_data_size(truncated), U256::from(0x20));
+
+        assert!(matches!(
+            transact(Some(0x20), ReturnDataLimitMode::Halt),
+            ExecutionResult::Halt {
+                reason: HaltReason::OutOfGas(OutOfGasError::MemoryLimit),
+                gas_used: 1_000_000,
+            }
+        ));
+    }
 }
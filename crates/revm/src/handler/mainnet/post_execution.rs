@@ -1,7 +1,10 @@
 use crate::{Context, EvmWiring, FrameResult};
 use interpreter::{Gas, SuccessOrHalt};
 use primitives::U256;
-use specification::hardfork::{Spec, SpecId};
+use specification::{
+    eip4895::Withdrawal,
+    hardfork::{Spec, SpecId},
+};
 use wiring::{
     result::{EVMError, EVMResult, EVMResultGeneric, ExecutionResult, ResultAndState},
     Block, Transaction,
@@ -19,9 +22,42 @@ pub fn end<EvmWiringT: EvmWiring>(
 /// Clear handle clears error and journal state.
 #[inline]
 pub fn clear<EvmWiringT: EvmWiring>(context: &mut Context<EvmWiringT>) {
-    // clear error and journaled state.
+    // clear error, journaled state and the values of the transaction.
     let _ = context.evm.take_error();
     context.evm.inner.journaled_state.clear();
+    context.evm.inner.beneficiary_reward = U256::ZERO;
+    context.evm.inner.intrinsic_gas = 0;
+    context.evm.inner.gas_refunded_uncapped = 0;
+}
+
+/// Credits EIP-4895 withdrawals to their recipients and marks them touched.
+///
+/// Zero amount withdrawals are skipped so no empty account gets created.
+#[inline]
+pub fn apply_withdrawals<EvmWiringT: EvmWiring>(
+    context: &mut Context<EvmWiringT>,
+    withdrawals: &[Withdrawal],
+) -> EVMResultGeneric<(), EvmWiringT> {
+    for withdrawal in withdrawals
+        .iter()
+        .filter(|withdrawal| withdrawal.amount != 0)
+    {
+        let account = context
+            .evm
+            .inner
+            .journaled_state
+            .load_account(withdrawal.address, &mut context.evm.inner.db)
+            .map_err(EVMError::Database)?;
+
+        account.data.mark_touch();
+        account.data.info.balance = account
+            .data
+            .info
+            .balance
+            .saturating_add(withdrawal.amount_wei());
+    }
+
+    Ok(())
 }
 
 /// Reward beneficiary with gas fee.
@@ -36,11 +72,21 @@ pub fn reward_beneficiary<EvmWiringT: EvmWiring, SPEC: Spec>(
     // transfer fee to coinbase/beneficiary.
     // EIP-1559 discard basefee for coinbase transfer. Basefee amount of gas is discarded.
     let coinbase_gas_price = if SPEC::enabled(SpecId::LONDON) {
-        effective_gas_price.saturating_sub(*context.evm.env.block.basefee())
+        effective_gas_price.saturating_sub(context.evm.env.effective_basefee())
     } else {
         effective_gas_price
     };
 
+    // Saturating as the gas price is not bounded by the caller balance if its check is disabled.
+    let reward = coinbase_gas_price.saturating_mul(U256::from(gas.spent() - gas.refunded() as u64));
+
+    // Deferred rewards are accumulated when the transaction is committed and paid once with
+    // `Evm::settle_rewards`.
+    context.evm.inner.beneficiary_reward = reward;
+    if context.evm.env.cfg.is_lazy_beneficiary_reward_enabled() {
+        return Ok(());
+    }
+
     let coinbase_account = context
         .evm
         .inner
@@ -49,26 +95,29 @@ pub fn reward_beneficiary<EvmWiringT: EvmWiring, SPEC: Spec>(
         .map_err(EVMError::Database)?;
 
     coinbase_account.data.mark_touch();
-    coinbase_account.data.info.balance = coinbase_account
-        .data
-        .info
-        .balance
-        .saturating_add(coinbase_gas_price * U256::from(gas.spent() - gas.refunded() as u64));
+    coinbase_account.data.info.balance = coinbase_account.data.info.balance.saturating_add(reward);
 
     Ok(())
 }
 
 pub fn refund<EvmWiringT: EvmWiring, SPEC: Spec>(
-    _context: &mut Context<EvmWiringT>,
+    context: &mut Context<EvmWiringT>,
     gas: &mut Gas,
     eip7702_refund: i64,
 ) {
     gas.record_refund(eip7702_refund);
+    context.evm.inner.gas_refunded_uncapped = gas.refunded().max(0) as u64;
 
     // Calculate gas refund for transaction.
     // If spec is set to london, it will decrease the maximum refund amount to 5th part of
     // gas spend. (Before london it was 2th part of gas spend)
-    gas.set_final_refund(SPEC::SPEC_ID.is_enabled_in(SpecId::LONDON));
+    // The quotient can be overridden with `CfgEnv::refund_quotient`.
+    let max_refund_quotient = context
+        .evm
+        .env
+        .cfg
+        .max_refund_quotient(SPEC::SPEC_ID.is_enabled_in(SpecId::LONDON));
+    gas.set_final_refund_with_quotient(max_refund_quotient);
 }
 
 #[inline]
@@ -87,10 +136,9 @@ pub fn reimburse_caller<EvmWiringT: EvmWiring>(
         .load_account(caller, &mut context.evm.inner.db)
         .map_err(EVMError::Database)?;
 
-    caller_account.data.info.balance =
-        caller_account.data.info.balance.saturating_add(
-            effective_gas_price * U256::from(gas.remaining() + gas.refunded() as u64),
-        );
+    caller_account.data.info.balance = caller_account.data.info.balance.saturating_add(
+        effective_gas_price.saturating_mul(U256::from(gas.remaining() + gas.refunded() as u64)),
+    );
 
     Ok(())
 }
@@ -112,7 +160,7 @@ pub fn output<EvmWiringT: EvmWiring>(
     // reset journal and return present state.
     let (state, logs) = context.evm.journaled_state.finalize();
 
-    let result = match SuccessOrHalt::<EvmWiringT::HaltReason>::from(instruction_result.result) {
+    let result = match EvmWiringT::success_or_halt(instruction_result.result) {
         SuccessOrHalt::Success(reason) => ExecutionResult::Success {
             reason,
             gas_used: final_gas_used,
@@ -128,14 +176,268 @@ pub fn output<EvmWiringT: EvmWiring>(
             reason,
             gas_used: final_gas_used,
         },
-        // Only two internal return flags.
-        flag @ (SuccessOrHalt::FatalExternalError | SuccessOrHalt::Internal(_)) => {
-            panic!(
+        // Database errors were returned above, so the host failed without reporting one.
+        SuccessOrHalt::FatalExternalError => {
+            return Err(EVMError::Custom(format!(
+                "Fatal external error without a database error, instruction result: {:?}",
+                instruction_result
+            )));
+        }
+        // Internal return flags never leave the interpreter.
+        flag @ SuccessOrHalt::Internal(_) => {
+            debug_assert!(
+                false,
                 "Encountered unexpected internal return flag: {:?} with instruction result: {:?}",
                 flag, instruction_result
-            )
+            );
+            return Err(EVMError::Custom(format!(
+                "Unexpected internal return flag: {:?}",
+                flag
+            )));
         }
     };
 
-    Ok(ResultAndState { result, state })
+    // Values recorded in the context by validation, `refund` and `reward_beneficiary`.
+    let inner = &mut context.evm.inner;
+    let priority_fee_paid = core::mem::take(&mut inner.beneficiary_reward);
+    Ok(ResultAndState {
+        result,
+        state,
+        intrinsic_gas: core::mem::take(&mut inner.intrinsic_gas),
+        gas_refunded_uncapped: core::mem::take(&mut inner.gas_refunded_uncapped),
+        deferred_reward: if inner.env.cfg.is_lazy_beneficiary_reward_enabled() {
+            priority_fee_paid
+        } else {
+            U256::ZERO
+        },
+        priority_fee_paid,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::call_evm_builder;
+    use bytecode::{
+        opcode::{PUSH0, PUSH1, SSTORE, STOP},
+        Bytecode,
+    };
+    use database::{BenchmarkDB, InMemoryDB};
+    use database_interface::{Database, EmptyDBTyped};
+    use interpreter::{CallOutcome, InstructionResult, InterpreterResult};
+    use primitives::{address, Address, Bytes};
+    use specification::{
+        eip4895::GWEI_TO_WEI,
+        hardfork::{BerlinSpec, LondonSpec},
+    };
+    use state::AccountInfo;
+    use wiring::{default::CfgEnv, DefaultEthereumWiring, EthereumWiring, TransactionType};
+
+    /// Returns the final refund of 1000 gas spent with 600 gas of refunds.
+    fn final_refund<SPEC: Spec>(modify_cfg: impl FnOnce(&mut CfgEnv)) -> i64 {
+        let mut ctx = Context::<DefaultEthereumWiring>::default();
+        modify_cfg(&mut ctx.evm.inner.env.cfg);
+
+        let mut gas = Gas::new(1000);
+        assert!(gas.record_cost(1000));
+        gas.record_refund(600);
+        refund::<DefaultEthereumWiring, SPEC>(&mut ctx, &mut gas, 0);
+        gas.refunded()
+    }
+
+    #[test]
+    fn test_refund_quotient_default() {
+        assert_eq!(final_refund::<LondonSpec>(|_| {}), 200);
+        assert_eq!(final_refund::<BerlinSpec>(|_| {}), 500);
+    }
+
+    #[test]
+    #[cfg(feature = "optional_refund_quotient")]
+    fn test_refund_quotient_override() {
+        use core::num::NonZeroU64;
+
+        let quotient =
+            |quotient| move |cfg: &mut CfgEnv| cfg.refund_quotient = NonZeroU64::new(quotient);
+        assert_eq!(final_refund::<LondonSpec>(quotient(10)), 100);
+        assert_eq!(final_refund::<BerlinSpec>(quotient(4)), 250);
+        // Refund is still capped by what was recorded.
+        assert_eq!(final_refund::<LondonSpec>(quotient(1)), 600);
+    }
+
+    #[test]
+    fn test_apply_withdrawals() {
+        let first = Address::with_last_byte(0xa1);
+        let second = Address::with_last_byte(0xa2);
+        let empty = Address::with_last_byte(0xa3);
+
+        let mut db = InMemoryDB::default();
+        db.insert_account_info(first, AccountInfo::from_balance(U256::from(1)));
+        let mut ctx = Context::<EthereumWiring<InMemoryDB, ()>>::new_with_db(db);
+
+        let withdrawals = [
+            Withdrawal {
+                index: 0,
+                validator_index: 7,
+                address: first,
+                amount: 2,
+            },
+            Withdrawal {
+                index: 1,
+                validator_index: 8,
+                address: second,
+                amount: 3,
+            },
+            Withdrawal {
+                index: 2,
+                validator_index: 9,
+                address: empty,
+                amount: 0,
+            },
+        ];
+        apply_withdrawals(&mut ctx, &withdrawals).unwrap();
+
+        let state = &ctx.evm.journaled_state.state;
+        assert_eq!(state[&first].info.balance, U256::from(1 + 2 * GWEI_TO_WEI));
+        assert_eq!(state[&second].info.balance, U256::from(3 * GWEI_TO_WEI));
+        assert!(state[&second].is_touched());
+        // Zero amount withdrawals do not touch the recipient.
+        assert!(!state.contains_key(&empty));
+    }
+
+    #[test]
+    fn test_output_fatal_external_error() {
+        let fatal = || {
+            FrameResult::Call(CallOutcome::new(
+                InterpreterResult::new(
+                    InstructionResult::FatalExternalError,
+                    Bytes::new(),
+                    Gas::new(0),
+                ),
+                0..0,
+            ))
+        };
+        let mut ctx = Context::<EthereumWiring<EmptyDBTyped<&'static str>, ()>>::new_with_db(
+            Default::default(),
+        );
+
+        // The database error recorded by the host is returned.
+        ctx.evm.error = Err("database error");
+        assert!(matches!(
+            output(&mut ctx, fatal()),
+            Err(EVMError::Database("database error"))
+        ));
+
+        // Without a recorded database error the failure is still recoverable.
+        assert!(matches!(
+            output(&mut ctx, fatal()),
+            Err(EVMError::Custom(_))
+        ));
+    }
+
+    #[test]
+    fn test_output_recorded_values() {
+        let mut ctx = Context::<DefaultEthereumWiring>::default();
+        ctx.evm.inner.intrinsic_gas = 21_000;
+
+        let mut gas = Gas::new(30_000);
+        assert!(gas.record_cost(25_000));
+        gas.record_refund(6_000);
+        refund::<DefaultEthereumWiring, LondonSpec>(&mut ctx, &mut gas, 0);
+        reward_beneficiary::<DefaultEthereumWiring, LondonSpec>(&mut ctx, &gas).unwrap();
+
+        let result = FrameResult::Call(CallOutcome::new(
+            InterpreterResult::new(InstructionResult::Stop, Bytes::new(), gas),
+            0..0,
+        ));
+        let output = output(&mut ctx, result).unwrap();
+        assert_eq!(output.intrinsic_gas(), 21_000);
+        // Capped to a fifth of the gas spent.
+        assert_eq!(output.gas_refunded_uncapped(), 6_000);
+        assert_eq!(output.result.gas_used(), 20_000);
+        // The default gas price is zero.
+        assert_eq!(output.priority_fee_paid(), U256::ZERO);
+        assert_eq!(output.deferred_reward, U256::ZERO);
+        // Taken by the output.
+        assert_eq!(ctx.evm.inner.intrinsic_gas, 0);
+        assert_eq!(ctx.evm.inner.gas_refunded_uncapped, 0);
+    }
+
+    #[test]
+    fn test_caller_is_coinbase() {
+        let caller = address!("0000000000000000000000000000000000000001");
+        let basefee = U256::from(10);
+
+        let mut evm = call_evm_builder(
+            BenchmarkDB::new_bytecode(Bytecode::new()),
+            (),
+            Address::ZERO,
+            100_000,
+        )
+        .with_spec_id(SpecId::CANCUN)
+        .modify_block_env(|block| {
+            block.basefee = basefee;
+            block.coinbase = caller;
+        })
+        .modify_tx_env(|tx| {
+            tx.tx_type = TransactionType::Eip1559;
+            tx.caller = caller;
+            tx.gas_price = U256::from(20);
+            tx.gas_priority_fee = Some(U256::from(3));
+        })
+        .build();
+
+        let balance = evm.context.evm.db.basic(caller).unwrap().unwrap().balance;
+        let ok = evm.transact().unwrap();
+        // The tip returns to the caller, only the basefee is burned.
+        let gas_used = U256::from(ok.result.gas_used());
+        assert_eq!(
+            ok.state.get(&caller).unwrap().info.balance,
+            balance - basefee * gas_used
+        );
+    }
+
+    #[test]
+    fn test_gas_refunded_uncapped() {
+        let contract = address!("00000000000000000000000000000000000000a0");
+
+        // Clear slots 0 to 9, each refunding 4800 gas.
+        let mut code = Vec::new();
+        for slot in 0..10 {
+            code.extend_from_slice(&[PUSH0, PUSH1, slot, SSTORE]);
+        }
+        code.push(STOP);
+        let mut db = InMemoryDB::default();
+        db.insert_account_info(
+            contract,
+            AccountInfo::from_bytecode(Bytecode::new_raw(code.into())),
+        );
+        for slot in 0..10 {
+            db.insert_account_storage(contract, U256::from(slot), U256::from(1))
+                .unwrap();
+        }
+
+        let mut evm = call_evm_builder(db, (), contract, 1_000_000)
+            .with_spec_id(SpecId::CANCUN)
+            .build();
+
+        let output = evm.transact().unwrap();
+        let ExecutionResult::Success {
+            gas_used,
+            gas_refunded,
+            ..
+        } = output.result
+        else {
+            panic!("transaction failed: {:?}", output.result);
+        };
+        assert_eq!(output.gas_refunded_uncapped(), 10 * 4800);
+        // Capped to a fifth of the gas spent.
+        assert_eq!(gas_refunded, (gas_used + gas_refunded) / 5);
+        assert!(output.gas_refunded_uncapped() > gas_refunded);
+
+        // Refunds of a failed execution are dropped.
+        evm.tx_mut().gas_limit = 30_000;
+        let output = evm.transact().unwrap();
+        assert!(!output.result.is_success());
+        assert_eq!(output.gas_refunded_uncapped(), 0);
+    }
 }
@@ -11,7 +11,7 @@ use specification::{
     hardfork::{Spec, SpecId},
 };
 use state::Account;
-use transaction::{eip7702::Authorization, Eip7702Tx};
+use transaction::eip7702::Authorization;
 use wiring::{
     default::EnvWiring,
     result::{EVMError, EVMResultGeneric},
@@ -135,10 +135,8 @@ pub fn apply_eip7702_auth_list<EvmWiringT: EvmWiring, SPEC: Spec>(
         return Ok(0);
     }
 
-    //let authorization_list = tx.eip7702().authorization_list();
-
     let mut refunded_accounts = 0;
-    for authorization in tx.eip7702().authorization_list_iter() {
+    for authorization in tx.authorization_list() {
         // 1. recover authority and authorized addresses.
         // authority = ecrecover(keccak(MAGIC || rlp([chain_id, address, nonce])), y_parity, r, s]
         let Some(authority) = authorization.authority() else {
@@ -194,3 +192,175 @@ pub fn apply_eip7702_auth_list<EvmWiringT: EvmWiring, SPEC: Spec>(
 
     Ok(refunded_gas)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{call_evm_builder, TEST_CALLER};
+    use database::InMemoryDB;
+    use primitives::{address, b256, Address};
+    use specification::{
+        eip7702::{Authorization as Eip7702Authorization, RecoveredAuthorization, Signature},
+        hardfork::PragueSpec,
+    };
+    use state::AccountInfo;
+    use wiring::{
+        default::TxEnv,
+        result::{InvalidTransaction, ResultAndState},
+        EthereumWiring,
+    };
+
+    type TestWiring = EthereumWiring<InMemoryDB, ()>;
+
+    const AUTHORITY: Address = Address::with_last_byte(0xaa);
+    const DELEGATE: Address = Address::with_last_byte(0xde);
+
+    /// Applies a single authorization signed by [`AUTHORITY`] and returns the context and refund.
+    fn apply_auth(chain_id: u64, nonce: u64) -> (Context<TestWiring>, u64) {
+        let mut db = InMemoryDB::default();
+        db.insert_account_info(
+            AUTHORITY,
+            AccountInfo {
+                balance: U256::from(1),
+                ..Default::default()
+            },
+        );
+
+        let mut context = Context::<TestWiring>::new_with_db(db);
+        let tx = &mut context.evm.inner.env.tx;
+        tx.tx_type = TransactionType::Eip7702;
+        tx.authorization_list = vec![RecoveredAuthorization::new_unchecked(
+            Eip7702Authorization {
+                chain_id: U256::from(chain_id),
+                address: DELEGATE,
+                nonce,
+            }
+            .into_signed(Signature::test_signature()),
+            Some(AUTHORITY),
+        )]
+        .into();
+
+        let refund = apply_eip7702_auth_list::<TestWiring, PragueSpec>(&mut context).unwrap();
+        (context, refund)
+    }
+
+    fn authority_info(context: &Context<TestWiring>) -> &AccountInfo {
+        &context.evm.journaled_state.state[&AUTHORITY].info
+    }
+
+    #[test]
+    fn test_eip7702_valid_delegation() {
+        let (context, refund) = apply_auth(1, 0);
+
+        let info = authority_info(&context);
+        assert_eq!(info.code, Some(Bytecode::new_eip7702(DELEGATE)));
+        assert_eq!(info.nonce, 1);
+        // Authority already existed so the empty account cost is refunded.
+        assert_eq!(
+            refund,
+            eip7702::PER_EMPTY_ACCOUNT_COST - eip7702::PER_AUTH_BASE_COST
+        );
+    }
+
+    #[test]
+    fn test_eip7702_wrong_chain_id() {
+        let (context, refund) = apply_auth(2, 0);
+
+        // Invalid authorization is skipped without failing the transaction.
+        assert!(!context.evm.journaled_state.state.contains_key(&AUTHORITY));
+        assert_eq!(refund, 0);
+    }
+
+    #[test]
+    fn test_eip7702_nonce_mismatch() {
+        let (context, refund) = apply_auth(1, 1);
+
+        let info = authority_info(&context);
+        assert_eq!(info.code, Some(Bytecode::default()));
+        assert_eq!(info.nonce, 0);
+        assert_eq!(refund, 0);
+    }
+
+    #[test]
+    fn test_blob_gas_fee() {
+        use specification::eip4844::{GAS_PER_BLOB, MAX_BLOB_NUMBER_PER_BLOCK};
+
+        let caller = TEST_CALLER;
+        let balance = U256::from(10u64.pow(18));
+        let mut db = InMemoryDB::default();
+        db.insert_account_info(caller, AccountInfo::from_balance(balance));
+
+        let blob_tx = |blobs: usize| {
+            move |tx: &mut TxEnv| {
+                tx.tx_type = TransactionType::Eip4844;
+                tx.gas_price = U256::from(1);
+                tx.gas_priority_fee = Some(U256::from(1));
+                // Versioned hashes of KZG commitments.
+                tx.blob_hashes =
+                    vec![
+                        b256!("0100000000000000000000000000000000000000000000000000000000000000");
+                        blobs
+                    ];
+                tx.max_fee_per_blob_gas = Some(U256::from(1_000));
+            }
+        };
+
+        let contract = address!("00000000000000000000000000000000000000a0");
+        let mut evm = call_evm_builder(db, (), contract, 100_000)
+            .with_spec_id(SpecId::CANCUN)
+            .modify_block_env(|block| block.set_blob_excess_gas_and_price(10_000_000))
+            .modify_tx_env(blob_tx(2))
+            .build();
+        let blob_gasprice = evm.block().blob_gasprice().unwrap();
+        assert!(blob_gasprice > 1);
+
+        // The blob gas is paid on top of the execution gas.
+        let ResultAndState { result, state, .. } = evm.transact().unwrap();
+        assert!(result.is_success());
+        let blob_fee = U256::from(2 * GAS_PER_BLOB as u128 * blob_gasprice);
+        assert_eq!(
+            state[&caller].info.balance,
+            balance - U256::from(result.gas_used()) - blob_fee
+        );
+
+        blob_tx(MAX_BLOB_NUMBER_PER_BLOCK as usize + 1)(evm.tx_mut());
+        assert!(matches!(
+            evm.transact(),
+            Err(EVMError::Transaction(
+                InvalidTransaction::TooManyBlobs { .. }
+            ))
+        ));
+    }
+
+    #[test]
+    fn test_warm_coinbase() {
+        use bytecode::opcode::{COINBASE, EXTCODESIZE, POP, STOP};
+        use interpreter::gas;
+
+        let contract = address!("00000000000000000000000000000000000000a0");
+        let mut db = InMemoryDB::default();
+        db.insert_account_info(
+            contract,
+            AccountInfo::from_bytecode(Bytecode::new_raw(
+                vec![COINBASE, EXTCODESIZE, POP, STOP].into(),
+            )),
+        );
+
+        let gas_used = |spec_id| {
+            let mut evm = call_evm_builder(db.clone(), (), contract, 100_000)
+                .with_spec_id(spec_id)
+                .modify_block_env(|block| {
+                    block.coinbase = address!("00000000000000000000000000000000000000cb")
+                })
+                .build();
+            let result = evm.transact().unwrap().result;
+            assert!(result.is_success());
+            result.gas_used()
+        };
+        // EIP-3651: the coinbase starts warm from Shanghai.
+        assert_eq!(
+            gas_used(SpecId::MERGE) - gas_used(SpecId::SHANGHAI),
+            gas::COLD_ACCOUNT_ACCESS_COST - gas::WARM_STORAGE_READ_COST
+        );
+    }
+}
@@ -4,7 +4,6 @@ use crate::{Context, EvmWiring};
 use interpreter::gas;
 use primitives::{B256, U256};
 use specification::{
-    constants::MAX_INITCODE_SIZE,
     eip4844,
     hardfork::{Spec, SpecId},
 };
@@ -15,7 +14,7 @@ use transaction::{
     Transaction,
 };
 use wiring::{
-    default::{CfgEnv, EnvWiring},
+    default::{CfgEnv, EnvWiring, NonceCheckMode},
     result::{EVMError, EVMResultGeneric, InvalidHeader, InvalidTransaction},
     Block, TransactionType,
 };
@@ -243,13 +242,17 @@ pub fn validate_tx_env<EvmWiringT: EvmWiring, SPEC: Spec>(
     }
 
     // EIP-3860: Limit and meter initcode
-    if SPEC::enabled(SpecId::SHANGHAI) && tx.kind().is_create() {
-        let max_initcode_size = cfg
-            .limit_contract_code_size
-            .map(|limit| limit.saturating_mul(2))
-            .unwrap_or(MAX_INITCODE_SIZE);
-        if tx.common_fields().input().len() > max_initcode_size {
-            return Err(InvalidTransaction::CreateInitCodeSizeLimit);
+    if SPEC::enabled(SpecId::SHANGHAI)
+        && tx.kind().is_create()
+        && tx.common_fields().input().len() > cfg.max_initcode_size()
+    {
+        return Err(InvalidTransaction::CreateInitCodeSizeLimit);
+    }
+
+    if let Some(max) = cfg.max_calldata_size() {
+        let have = tx.common_fields().input().len();
+        if have > max {
+            return Err(InvalidTransaction::CallDataTooLarge { max, have });
         }
     }
 
@@ -283,7 +286,7 @@ where
         let tx = tx.common_fields().nonce();
         let state = account.info.nonce;
         match tx.cmp(&state) {
-            Ordering::Greater => {
+            Ordering::Greater if cfg.nonce_check_mode != NonceCheckMode::AllowHigher => {
                 return Err(InvalidTransaction::NonceTooHigh { tx, state });
             }
             Ordering::Less => {
@@ -372,12 +375,17 @@ where
     let input = common_fields.input();
     let access_list = env.tx.access_list();
 
-    let initial_gas_spend = gas::validate_initial_tx_gas(
+    let access_list_storage_key = env.cfg.sload_cold_gas().map_or(
+        gas::ACCESS_LIST_STORAGE_KEY,
+        gas::access_list_storage_key_cost,
+    );
+    let initial_gas_spend = gas::custom_initial_tx_gas(
         SPEC::SPEC_ID,
         input,
         is_create,
         access_list,
         authorization_list_num,
+        access_list_storage_key,
     );
 
     // Additional check to see if limit is big enough to cover initial gas.
@@ -388,3 +396,178 @@ where
     }
     Ok(initial_gas_spend)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::call_evm_builder;
+    use database::InMemoryDB;
+    use primitives::address;
+
+    #[test]
+    fn test_nonce_check_mode() {
+        let transact = |mode, nonce| {
+            let contract = address!("00000000000000000000000000000000000000a0");
+            let mut evm = call_evm_builder(InMemoryDB::default(), (), contract, 100_000)
+                .with_spec_id(SpecId::CANCUN)
+                .modify_cfg_env(|cfg| cfg.nonce_check_mode = mode)
+                .modify_tx_env(|tx| tx.nonce = nonce)
+                .build();
+            evm.transact_commit()
+        };
+
+        // The caller's nonce is 0.
+        assert!(matches!(
+            transact(NonceCheckMode::Strict, 5),
+            Err(EVMError::Transaction(InvalidTransaction::NonceTooHigh {
+                tx: 5,
+                state: 0
+            }))
+        ));
+        assert!(transact(NonceCheckMode::AllowHigher, 5)
+            .unwrap()
+            .is_success());
+        assert!(transact(NonceCheckMode::Disabled, 5).unwrap().is_success());
+    }
+
+    #[test]
+    #[cfg(feature = "optional_no_base_fee")]
+    fn test_disable_base_fee() {
+        use crate::test_utils::TEST_CALLER;
+        use bytecode::{
+            opcode::{BASEFEE, GASPRICE, PUSH0, PUSH1, SSTORE, STOP},
+            Bytecode,
+        };
+        use state::AccountInfo;
+        use wiring::result::ResultAndState;
+
+        let caller = TEST_CALLER;
+        let contract = address!("00000000000000000000000000000000000000a0");
+        let coinbase = address!("00000000000000000000000000000000000000c0");
+
+        // Store the gas price at slot 0 and the base fee at slot 1.
+        let mut db = InMemoryDB::default();
+        db.insert_account_info(caller, AccountInfo::from_balance(U256::from(100_000_000)));
+        db.insert_account_info(
+            contract,
+            AccountInfo::from_bytecode(Bytecode::new_raw(
+                vec![GASPRICE, PUSH0, SSTORE, BASEFEE, PUSH1, 1, SSTORE, STOP].into(),
+            )),
+        );
+
+        let mut evm = call_evm_builder(db, (), contract, 100_000)
+            .modify_block_env(|block| {
+                block.basefee = U256::from(100);
+                block.coinbase = coinbase;
+            })
+            .modify_tx_env(|tx| {
+                tx.tx_type = TransactionType::Eip1559;
+                tx.gas_price = U256::from(110);
+                tx.gas_priority_fee = Some(U256::from(1));
+            })
+            .build();
+
+        // With the base fee the gas price is the base fee plus the priority fee.
+        let ResultAndState { result, state, .. } = evm.transact().unwrap();
+        assert!(result.is_success());
+        let storage = &state[&contract].storage;
+        assert_eq!(storage[&U256::ZERO].present_value, U256::from(101));
+        assert_eq!(storage[&U256::from(1)].present_value, U256::from(100));
+        assert_eq!(state[&coinbase].info.balance, U256::from(result.gas_used()));
+
+        // Max fee below the base fee.
+        evm.context.evm.env.tx.gas_price = U256::from(10);
+        assert!(matches!(
+            evm.transact(),
+            Err(EVMError::Transaction(
+                InvalidTransaction::GasPriceLessThanBasefee
+            ))
+        ));
+
+        // Without a base fee the gas price is the priority fee, all paid to the coinbase.
+        evm.context.evm.env.cfg.disable_base_fee = true;
+        let ResultAndState { result, state, .. } = evm.transact().unwrap();
+        assert!(result.is_success());
+        let storage = &state[&contract].storage;
+        assert_eq!(storage[&U256::ZERO].present_value, U256::from(1));
+        assert_eq!(storage[&U256::from(1)].present_value, U256::ZERO);
+        assert_eq!(state[&coinbase].info.balance, U256::from(result.gas_used()));
+    }
+
+    #[test]
+    #[cfg(feature = "optional_balance_check")]
+    fn test_disable_balance_check() {
+        use wiring::result::ResultAndState;
+
+        let contract = address!("00000000000000000000000000000000000000a0");
+
+        let mut evm = call_evm_builder(InMemoryDB::default(), (), contract, 100_000)
+            .modify_tx_env(|tx| {
+                tx.gas_price = U256::from(10);
+                tx.value = U256::from(1_000);
+            })
+            .build();
+
+        // The caller has no balance.
+        assert!(matches!(
+            evm.transact(),
+            Err(EVMError::Transaction(
+                InvalidTransaction::LackOfFundForMaxFee { .. }
+            ))
+        ));
+
+        evm.context.evm.env.cfg.disable_balance_check = true;
+        let ResultAndState { result, state, .. } = evm.transact().unwrap();
+        assert!(result.is_success());
+        assert_eq!(state[&contract].info.balance, U256::from(1_000));
+    }
+
+    #[test]
+    #[cfg(feature = "optional_max_calldata_size")]
+    fn test_max_calldata_size() {
+        use primitives::{Address, Bytes};
+
+        let transact = |size: usize| {
+            let mut evm = call_evm_builder(InMemoryDB::default(), (), Address::ZERO, 100_000)
+                .modify_cfg_env(|cfg| cfg.max_calldata_size = Some(100))
+                .modify_tx_env(|tx| {
+                    tx.data = Bytes::from(vec![0; size]);
+                })
+                .build();
+            evm.transact().map(|output| output.result)
+        };
+
+        assert!(transact(100).unwrap().is_success());
+        assert!(matches!(
+            transact(101),
+            Err(EVMError::Transaction(
+                InvalidTransaction::CallDataTooLarge {
+                    max: 100,
+                    have: 101
+                }
+            ))
+        ));
+    }
+
+    #[test]
+    #[cfg(feature = "optional_sload_gas")]
+    fn test_sload_cold_gas_access_list() {
+        use specification::eip2930::{AccessList, AccessListItem};
+
+        let contract = address!("00000000000000000000000000000000000000a0");
+        let mut evm = call_evm_builder(InMemoryDB::default(), (), contract, 100_000)
+            .modify_cfg_env(|cfg| cfg.sload_cold_gas = Some(3_100))
+            .modify_tx_env(|tx| {
+                tx.tx_type = TransactionType::Eip2930;
+                tx.access_list = AccessList(vec![AccessListItem {
+                    address: contract,
+                    storage_keys: vec![B256::ZERO],
+                }]);
+            })
+            .build();
+
+        // A storage key costs 200 gas less than a cold `SLOAD`.
+        let output = evm.transact().unwrap();
+        assert_eq!(output.intrinsic_gas(), 21_000 + 2_400 + 2_900);
+    }
+}
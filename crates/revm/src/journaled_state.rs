@@ -7,7 +7,7 @@ use primitives::{
     hash_map::Entry, Address, HashMap, HashSet, Log, B256, KECCAK_EMPTY, PRECOMPILE3, U256,
 };
 use specification::hardfork::{SpecId, SpecId::*};
-use state::{Account, EvmState, EvmStorageSlot, TransientStorage};
+use state::{Account, AccountInfo, EvmState, EvmStorageSlot, TransientStorage};
 
 use core::mem;
 use std::vec::Vec;
@@ -245,7 +245,7 @@ impl JournaledState {
     ///
     /// There are few steps done:
     /// 1. Make created account warm loaded (AccessList) and this should
-    ///     be done before subroutine checkpoint is created.
+    ///    be done before subroutine checkpoint is created.
     /// 2. Check if there is collision of newly created account with existing one.
     /// 3. Mark created account as created.
     /// 4. Add fund to created account
@@ -470,6 +470,93 @@ impl JournaledState {
         self.journal.truncate(checkpoint.journal_i);
     }
 
+    /// Returns the changes made to the state since `checkpoint`, with their values at the
+    /// checkpoint and now.
+    ///
+    /// The diff is computed by walking back the journal entries made since the checkpoint, so
+    /// changes of reverted sub calls are not included and a value set back to what it was at the
+    /// checkpoint shows no diff. Changes that are not journaled, like the gas deducted from the
+    /// caller, are not included.
+    pub fn state_diff_since(&self, checkpoint: JournalCheckpoint) -> StateDiff {
+        fn info_at_checkpoint<'a>(
+            accounts: &'a mut HashMap<Address, AccountInfo>,
+            state: &EvmState,
+            address: Address,
+        ) -> &'a mut AccountInfo {
+            accounts
+                .entry(address)
+                .or_insert_with(|| state[&address].info.clone())
+        }
+
+        let mut accounts = HashMap::default();
+        let mut storage: HashMap<(Address, U256), U256> = HashMap::default();
+        let entries = self.journal.get(checkpoint.journal_i..).unwrap_or_default();
+        for entry in entries.iter().flatten().rev() {
+            match *entry {
+                JournalEntry::AccountDestroyed {
+                    address,
+                    target,
+                    had_balance,
+                    ..
+                } => {
+                    let info = info_at_checkpoint(&mut accounts, &self.state, address);
+                    info.balance = info.balance.saturating_add(had_balance);
+                    if address != target {
+                        let info = info_at_checkpoint(&mut accounts, &self.state, target);
+                        info.balance = info.balance.saturating_sub(had_balance);
+                    }
+                }
+                JournalEntry::BalanceTransfer { from, to, balance } => {
+                    let info = info_at_checkpoint(&mut accounts, &self.state, from);
+                    info.balance = info.balance.saturating_add(balance);
+                    let info = info_at_checkpoint(&mut accounts, &self.state, to);
+                    info.balance = info.balance.saturating_sub(balance);
+                }
+                JournalEntry::NonceChange { address } => {
+                    let info = info_at_checkpoint(&mut accounts, &self.state, address);
+                    info.nonce = info.nonce.saturating_sub(1);
+                }
+                JournalEntry::AccountCreated { address } => {
+                    info_at_checkpoint(&mut accounts, &self.state, address).nonce = 0;
+                }
+                JournalEntry::CodeChange { address } => {
+                    let info = info_at_checkpoint(&mut accounts, &self.state, address);
+                    info.code_hash = KECCAK_EMPTY;
+                    info.code = None;
+                }
+                JournalEntry::StorageChanged {
+                    address,
+                    key,
+                    had_value,
+                } => {
+                    // The earliest change holds the value at the checkpoint.
+                    storage.insert((address, key), had_value);
+                }
+                JournalEntry::AccountWarmed { .. }
+                | JournalEntry::AccountTouched { .. }
+                | JournalEntry::StorageWarmed { .. }
+                | JournalEntry::TransientStorageChange { .. } => {}
+            }
+        }
+
+        StateDiff {
+            accounts: accounts
+                .into_iter()
+                .filter_map(|(address, old)| {
+                    let new = &self.state[&address].info;
+                    (old != *new).then(|| (address, (old, new.clone())))
+                })
+                .collect(),
+            storage: storage
+                .into_iter()
+                .filter_map(|((address, key), old)| {
+                    let new = self.state[&address].storage[&key].present_value;
+                    (old != new).then_some(((address, key), (old, new)))
+                })
+                .collect(),
+        }
+    }
+
     /// Performances selfdestruct action.
     /// Transfers balance from address to target. Check if target exist/is_cold
     ///
@@ -574,6 +661,24 @@ impl JournaledState {
         Ok(account)
     }
 
+    /// Returns `true` if accessing `address` would be warm, without loading it.
+    #[inline]
+    pub fn is_address_warm(&self, address: Address) -> bool {
+        match self.state.get(&address) {
+            Some(account) => !account.is_cold(),
+            None => self.warm_preloaded_addresses.contains(&address),
+        }
+    }
+
+    /// Returns `true` if accessing the storage `key` of `address` would be warm, without loading it.
+    #[inline]
+    pub fn is_storage_warm(&self, address: Address, key: U256) -> bool {
+        self.state
+            .get(&address)
+            .and_then(|account| account.storage.get(&key))
+            .is_some_and(|slot| !slot.is_cold)
+    }
+
     /// load account into memory. return if it is cold or warm accessed
     #[inline]
     pub fn load_account<DB: Database>(
@@ -891,6 +996,15 @@ pub enum JournalEntry {
     CodeChange { address: Address },
 }
 
+/// Changes to the state since a [JournalCheckpoint], see [JournaledState::state_diff_since].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct StateDiff {
+    /// Accounts with a changed balance, nonce or code, with their info at the checkpoint and now.
+    pub accounts: HashMap<Address, (AccountInfo, AccountInfo)>,
+    /// Changed storage slots, with their value at the checkpoint and now.
+    pub storage: HashMap<(Address, U256), (U256, U256)>,
+}
+
 /// SubRoutine checkpoint that will help us to go back from this
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -898,3 +1012,137 @@ pub struct JournalCheckpoint {
     log_i: usize,
     journal_i: usize,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::call_evm_builder;
+    use bytecode::opcode::{PUSH0, PUSH1, PUSH20, SSTORE, STOP};
+    use database::InMemoryDB;
+    use primitives::address;
+    use wiring::{result::ResultAndState, TransactionType};
+
+    #[test]
+    fn test_state_diff_since() {
+        let a = Address::with_last_byte(0xa);
+        let b = Address::with_last_byte(0xb);
+        let mut db = InMemoryDB::default();
+        db.insert_account_info(a, AccountInfo::from_balance(U256::from(100)));
+
+        let mut journal = JournaledState::new(SpecId::CANCUN, HashSet::default());
+        journal.load_account(a, &mut db).unwrap();
+        journal
+            .sstore(a, U256::from(1), U256::from(1), &mut db)
+            .unwrap();
+
+        let checkpoint = journal.checkpoint();
+        journal
+            .sstore(a, U256::from(1), U256::from(10), &mut db)
+            .unwrap();
+        journal
+            .sstore(a, U256::from(2), U256::from(20), &mut db)
+            .unwrap();
+        // Set then unset.
+        journal
+            .sstore(a, U256::from(3), U256::from(30), &mut db)
+            .unwrap();
+        journal
+            .sstore(a, U256::from(3), U256::ZERO, &mut db)
+            .unwrap();
+        assert_eq!(journal.transfer(&a, &b, U256::from(5), &mut db), Ok(None));
+
+        // Reverted changes are not in the diff.
+        let reverted = journal.checkpoint();
+        journal
+            .sstore(a, U256::from(4), U256::from(40), &mut db)
+            .unwrap();
+        journal.checkpoint_revert(reverted);
+
+        let diff = journal.state_diff_since(checkpoint);
+        assert_eq!(
+            diff.storage,
+            HashMap::from_iter([
+                ((a, U256::from(1)), (U256::from(1), U256::from(10))),
+                ((a, U256::from(2)), (U256::ZERO, U256::from(20))),
+            ])
+        );
+        let balances: HashMap<_, _> = diff
+            .accounts
+            .iter()
+            .map(|(address, (old, new))| (*address, (old.balance, new.balance)))
+            .collect();
+        assert_eq!(
+            balances,
+            HashMap::from_iter([
+                (a, (U256::from(100), U256::from(95))),
+                (b, (U256::ZERO, U256::from(5))),
+            ])
+        );
+
+        journal.checkpoint_revert(checkpoint);
+        assert_eq!(journal.state_diff_since(checkpoint), StateDiff::default());
+    }
+
+    #[test]
+    fn test_host_warm_set() {
+        use interpreter::{Host, Interpreter};
+        use specification::eip2930::{AccessList, AccessListItem};
+
+        // Pops an address and pushes whether it is warm.
+        fn is_address_warm<H: Host + ?Sized>(interp: &mut Interpreter, host: &mut H) {
+            let address = Address::from_word(interp.stack.pop().unwrap().into());
+            let warm = host.is_address_warm(address);
+            interp.stack.push(U256::from(warm)).unwrap();
+        }
+        // Pops a slot and pushes whether it is warm in the executing contract.
+        fn is_storage_warm<H: Host + ?Sized>(interp: &mut Interpreter, host: &mut H) {
+            let slot = interp.stack.pop().unwrap();
+            let warm = host.is_storage_warm(interp.contract.target_address, slot);
+            interp.stack.push(U256::from(warm)).unwrap();
+        }
+
+        let contract = address!("00000000000000000000000000000000000000a0");
+        let listed = address!("00000000000000000000000000000000000000b0");
+        let unlisted = address!("00000000000000000000000000000000000000b1");
+
+        let mut code = vec![PUSH20];
+        code.extend_from_slice(listed.as_slice());
+        code.extend_from_slice(&[0x0c, PUSH0, SSTORE, PUSH20]);
+        code.extend_from_slice(unlisted.as_slice());
+        code.extend_from_slice(&[0x0c, PUSH1, 0x01, SSTORE]);
+        code.extend_from_slice(&[PUSH1, 0x07, 0x0d, PUSH1, 0x02, SSTORE]);
+        code.extend_from_slice(&[PUSH1, 0x08, 0x0d, PUSH1, 0x03, SSTORE, STOP]);
+        let mut db = InMemoryDB::default();
+        db.insert_account_info(
+            contract,
+            AccountInfo::from_bytecode(Bytecode::new_raw(code.into())),
+        );
+
+        let mut evm = call_evm_builder(db, (), contract, 1_000_000)
+            .modify_tx_env(|tx| {
+                tx.tx_type = TransactionType::Eip2930;
+                tx.access_list = AccessList(vec![
+                    AccessListItem {
+                        address: listed,
+                        storage_keys: vec![],
+                    },
+                    AccessListItem {
+                        address: contract,
+                        storage_keys: vec![B256::with_last_byte(0x07)],
+                    },
+                ]);
+            })
+            .override_instruction(0x0c, is_address_warm)
+            .override_instruction(0x0d, is_storage_warm)
+            .build();
+
+        let ResultAndState { result, state, .. } = evm.transact().unwrap();
+        assert!(result.is_success(), "{result:?}");
+        let storage = &state[&contract].storage;
+        let warm = |slot: u64| storage[&U256::from(slot)].present_value == U256::from(1);
+        assert!(warm(0));
+        assert!(!warm(1));
+        assert!(warm(2));
+        assert!(!warm(3));
+    }
+}
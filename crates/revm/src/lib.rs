@@ -33,13 +33,13 @@ mod journaled_state;
 
 pub use builder::EvmBuilder;
 pub use context::{
-    Context, ContextPrecompile, ContextPrecompiles, ContextStatefulPrecompile,
-    ContextStatefulPrecompileArc, ContextStatefulPrecompileBox, ContextStatefulPrecompileMut,
-    ContextWithEvmWiring, EvmContext, InnerEvmContext,
+    AnalysisCache, BlockHashCache, Context, ContextPrecompile, ContextPrecompiles,
+    ContextStatefulPrecompile, ContextStatefulPrecompileArc, ContextStatefulPrecompileBox,
+    ContextStatefulPrecompileMut, ContextWithEvmWiring, EvmContext, InnerEvmContext, ResultCache,
 };
 pub use database_interface::{Database, DatabaseCommit, DatabaseRef};
-pub use evm::{Evm, CALL_STACK_LIMIT};
+pub use evm::{Evm, PreExecutionSnapshot, CALL_STACK_LIMIT};
 pub use evm_wiring::EvmWiring;
 pub use frame::{CallFrame, CreateFrame, Frame, FrameData, FrameOrResult, FrameResult};
 pub use handler::{register::EvmHandler, Handler};
-pub use journaled_state::{JournalCheckpoint, JournalEntry, JournaledState};
+pub use journaled_state::{JournalCheckpoint, JournalEntry, JournaledState, StateDiff};
@@ -1,2 +1,28 @@
 #[doc(hidden)]
 pub use crate::context::evm_context::test_utils::*;
+
+use crate::{builder::SetGenericStage, Database, Evm, EvmBuilder};
+use core::fmt::Debug;
+use primitives::{address, Address, TxKind};
+use wiring::EthereumWiring;
+
+/// Caller of the transaction set by [`call_evm_builder`].
+pub const TEST_CALLER: Address = address!("1000000000000000000000000000000000000000");
+
+/// Returns a builder of a mainnet [`Evm`] with the given database and external context, whose
+/// transaction calls `target` from [`TEST_CALLER`] with `gas_limit`.
+pub fn call_evm_builder<'a, DB: Database, EXT: Debug>(
+    db: DB,
+    external_context: EXT,
+    target: Address,
+    gas_limit: u64,
+) -> EvmBuilder<'a, SetGenericStage, EthereumWiring<DB, EXT>> {
+    Evm::<EthereumWiring<DB, EXT>>::builder()
+        .with_db(db)
+        .with_external_context(external_context)
+        .modify_tx_env(|tx| {
+            tx.caller = TEST_CALLER;
+            tx.transact_to = TxKind::Call(target);
+            tx.gas_limit = gas_limit;
+        })
+}
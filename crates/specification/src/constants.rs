@@ -1,6 +1,11 @@
 /// EVM interpreter stack limit.
 pub const STACK_LIMIT: usize = 1024;
 
+/// EVM call stack limit.
+///
+/// Maximum depth of nested calls and creates.
+pub const CALL_STACK_LIMIT: u64 = 1024;
+
 /// EIP-170: Contract code size limit
 ///
 /// By default the limit is `0x6000` (~25kb)
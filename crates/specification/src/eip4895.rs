@@ -0,0 +1,27 @@
+//! EIP-4895: Beacon chain push withdrawals as operations
+
+use primitives::{Address, U256};
+
+/// Number of Wei in a Gwei, the unit of withdrawal amounts.
+pub const GWEI_TO_WEI: u64 = 1_000_000_000;
+
+/// Withdrawal of validator balance from the beacon chain to the execution layer.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Withdrawal {
+    /// Monotonically increasing identifier issued by the consensus layer.
+    pub index: u64,
+    /// Index of the validator the withdrawal is made for.
+    pub validator_index: u64,
+    /// Recipient of the withdrawn ether.
+    pub address: Address,
+    /// Withdrawn amount in Gwei.
+    pub amount: u64,
+}
+
+impl Withdrawal {
+    /// Returns the withdrawn amount in Wei.
+    pub fn amount_wei(&self) -> U256 {
+        U256::from(self.amount) * U256::from(GWEI_TO_WEI)
+    }
+}
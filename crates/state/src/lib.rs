@@ -104,6 +104,11 @@ impl Account {
         }
     }
 
+    /// Is account cold, not accessed since it was marked cold.
+    pub fn is_cold(&self) -> bool {
+        self.status.contains(AccountStatus::Cold)
+    }
+
     /// Is account loaded as not existing from database
     /// This is needed for pre spurious dragon hardforks where
     /// existing and empty were two separate states.
@@ -8,8 +8,9 @@ use crate::block::blob::calc_blob_gasprice;
 use crate::{Block, EvmWiring, Transaction};
 use core::fmt::Debug;
 use core::hash::Hash;
-use primitives::{TxKind, U256};
-use specification::constants::MAX_CODE_SIZE;
+use core::num::NonZeroU64;
+use primitives::{keccak256, Address, TxKind, B256, U256};
+use specification::constants::{CALL_STACK_LIMIT, MAX_CODE_SIZE, MAX_INITCODE_SIZE};
 use std::boxed::Box;
 
 /// Subtype
@@ -35,9 +36,18 @@ impl<BlockT: Block, TxT: Transaction> Env<BlockT, TxT> {
         Box::new(Self { cfg, block, tx })
     }
 
+    /// Returns the base fee of the block, zero if the base fee is disabled with
+    /// [`CfgEnv::is_base_fee_check_disabled`].
+    pub fn effective_basefee(&self) -> U256 {
+        if self.cfg.is_base_fee_check_disabled() {
+            U256::ZERO
+        } else {
+            *self.block.basefee()
+        }
+    }
+
     pub fn effective_gas_price(&self) -> U256 {
-        let basefee = *self.block.basefee();
-        self.tx.effective_gas_price(basefee)
+        self.tx.effective_gas_price(self.effective_basefee())
     }
 
     /// Calculates the [EIP-4844] `data_fee` of the transaction.
@@ -80,6 +90,90 @@ impl<BlockT: Block + Default, TxT: Transaction + Default> Env<BlockT, TxT> {
     }
 }
 
+/// Keccak256 implementation, see [`CfgEnv::keccak256_hasher`].
+///
+/// Two hashers are equal if they hash with the same function.
+#[derive(Clone, Copy)]
+pub struct Keccak256Hasher {
+    hash: fn(&[u8]) -> B256,
+}
+
+impl Keccak256Hasher {
+    /// The keccak256 implementation of `primitives`.
+    pub const KECCAK256: Self = Self::new(keccak256_hash);
+
+    /// Creates a hasher that hashes with `hash`.
+    pub const fn new(hash: fn(&[u8]) -> B256) -> Self {
+        Self { hash }
+    }
+
+    /// Hashes `data`.
+    #[inline]
+    pub fn hash(&self, data: &[u8]) -> B256 {
+        (self.hash)(data)
+    }
+
+    /// Returns the `CREATE` address of `caller` at `nonce`: `hash(rlp([caller, nonce]))[12..]`.
+    pub fn create_address(&self, caller: Address, nonce: u64) -> Address {
+        // RLP encoding of `[caller, nonce]`, at most 31 bytes.
+        let nonce_bytes = nonce.to_be_bytes();
+        let nonce_bytes = &nonce_bytes[nonce.leading_zeros() as usize / 8..];
+        let mut encoded = [0; 31];
+        encoded[1] = 0x80 + 20;
+        encoded[2..22].copy_from_slice(caller.as_slice());
+        let len = match nonce_bytes {
+            [byte] if *byte < 0x80 => {
+                encoded[22] = *byte;
+                23
+            }
+            _ => {
+                encoded[22] = 0x80 + nonce_bytes.len() as u8;
+                encoded[23..23 + nonce_bytes.len()].copy_from_slice(nonce_bytes);
+                23 + nonce_bytes.len()
+            }
+        };
+        encoded[0] = 0xc0 + len as u8 - 1;
+        Address::from_word(self.hash(&encoded[..len]))
+    }
+
+    /// Returns the `CREATE2` address of `caller`:
+    /// `hash(0xff ++ caller ++ salt ++ init_code_hash)[12..]`.
+    pub fn create2_address(&self, caller: Address, salt: B256, init_code_hash: B256) -> Address {
+        let mut encoded = [0; 85];
+        encoded[0] = 0xff;
+        encoded[1..21].copy_from_slice(caller.as_slice());
+        encoded[21..53].copy_from_slice(salt.as_slice());
+        encoded[53..].copy_from_slice(init_code_hash.as_slice());
+        Address::from_word(self.hash(&encoded))
+    }
+}
+
+impl Default for Keccak256Hasher {
+    fn default() -> Self {
+        Self::KECCAK256
+    }
+}
+
+impl Debug for Keccak256Hasher {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("Keccak256Hasher")
+            .field(&(self.hash as *const ()))
+            .finish()
+    }
+}
+
+impl PartialEq for Keccak256Hasher {
+    fn eq(&self, other: &Self) -> bool {
+        core::ptr::fn_addr_eq(self.hash, other.hash)
+    }
+}
+
+impl Eq for Keccak256Hasher {}
+
+fn keccak256_hash(data: &[u8]) -> B256 {
+    keccak256(data)
+}
+
 /// EVM configuration.
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -100,8 +194,48 @@ pub struct CfgEnv {
     /// If some it will effects EIP-170: Contract code size limit. Useful to increase this because of tests.
     /// By default it is 0x6000 (~25kb).
     pub limit_contract_code_size: Option<usize>,
-    /// Skips the nonce validation against the account's nonce.
+    /// How the transaction's nonce is validated against the account's nonce.
+    ///
+    /// By default it is [`NonceCheckMode::Strict`].
+    pub nonce_check_mode: NonceCheckMode,
+    /// Skips the nonce validation against the account's nonce, same as
+    /// [`NonceCheckMode::Disabled`].
+    #[deprecated = "Use `nonce_check_mode` with `NonceCheckMode::Disabled` instead"]
     pub disable_nonce_check: bool,
+    /// Maximum call/create depth. A frame at a deeper level is not executed and returns
+    /// `CallTooDeep`. Useful to lower for fuzzing or to raise for research chains.
+    ///
+    /// By default it is [`CALL_STACK_LIMIT`] (1024).
+    pub max_depth: u64,
+    /// Halts execution with `ReadOnlyViolation` on the first state change: `SSTORE`, `TSTORE`,
+    /// `LOG*`, `SELFDESTRUCT`, contract creation or any call that transfers value, including the
+    /// transaction's own value transfer. Unlike static calls, this applies to the top level frame.
+    ///
+    /// By default it is `false`.
+    pub read_only: bool,
+    /// Disables caching the block hashes fetched for `BLOCKHASH`, so every lookup reads the
+    /// database. Useful when the database serves hashes that change between lookups.
+    ///
+    /// By default it is `false`.
+    pub disable_block_hash_cache: bool,
+    /// If some it replaces keccak256 for `KECCAK256` and the `CREATE`, `CREATE2` and `EOFCREATE`
+    /// address derivation, e.g. with a circuit friendly or hardware accelerated implementation.
+    ///
+    /// It is not serialized, as functions can not be serialized.
+    ///
+    /// By default it is `None`.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub keccak256_hasher: Option<Keccak256Hasher>,
+    /// Number of analyzed bytecodes kept by the EVM context, keyed by code hash, so repeated
+    /// calls to the same code skip jump destination analysis. Zero disables the cache.
+    ///
+    /// By default it is 0.
+    pub analysis_cache_size: usize,
+    /// Number of successful transaction results kept by `Evm::transact_cached`. Zero disables
+    /// the cache.
+    ///
+    /// By default it is 1024.
+    pub result_cache_size: usize,
     /// A hard memory limit in bytes beyond which [crate::result::OutOfGasError::Memory] cannot be resized.
     ///
     /// In cases where the gas limit may be extraordinarily high, it is recommended to set this to
@@ -127,25 +261,133 @@ pub struct CfgEnv {
     /// By default, it is set to `false`.
     #[cfg(feature = "optional_gas_refund")]
     pub disable_gas_refund: bool,
-    /// Disables base fee checks for EIP-1559 transactions.
+    /// Disables the base fee: it is not checked for EIP-1559 transactions, not part of the
+    /// effective gas price nor burned from the coinbase reward, and `BASEFEE` returns zero.
     /// This is useful for testing method calls with zero gas price.
     /// By default, it is set to `false`.
     #[cfg(feature = "optional_no_base_fee")]
     pub disable_base_fee: bool,
+    /// Accumulates the beneficiary reward of each committed transaction in the context instead
+    /// of paying it, so block builders can pay the coinbase once with `Evm::settle_rewards`.
+    /// The reward of a transaction that is not committed is only returned in its result.
+    /// By default, it is set to `false`.
+    #[cfg(feature = "optional_lazy_beneficiary_reward")]
+    pub lazy_beneficiary_reward: bool,
+    /// If some it overrides the EIP-3860 initcode size limit, taking precedence over the double
+    /// of [`Self::limit_contract_code_size`]. Useful for L2s and test chains.
+    /// By default, it is set to `None`: 0xC000 (48kb).
+    #[cfg(feature = "optional_initcode_size_limit")]
+    pub limit_contract_initcode_size: Option<usize>,
+    /// Disables both EIP-170 (contract code size) and EIP-3860 (initcode size) limits,
+    /// taking precedence over [`Self::limit_contract_code_size`]. Useful to deploy very large
+    /// contracts in tests.
+    /// By default, it is set to `false`.
+    #[cfg(feature = "optional_size_limits")]
+    pub disable_size_limits: bool,
+    /// If some it overrides the base gas cost of `KECCAK256`. Useful for hashing cost research.
+    /// By default, it is set to `None`: 30.
+    #[cfg(feature = "optional_keccak256_gas")]
+    pub keccak256_gas: Option<u64>,
+    /// If some it overrides the gas cost of `KECCAK256` per word of input.
+    /// By default, it is set to `None`: 6.
+    #[cfg(feature = "optional_keccak256_gas")]
+    pub keccak256_word_gas: Option<u64>,
+    /// If some it overrides the EIP-2929 gas cost of `SLOAD` on a cold storage slot.
+    ///
+    /// It is also the cold surcharge of `SSTORE`, deducted from its reset cost and refunds, and
+    /// the EIP-2930 cost of an access list storage key stays 200 gas below it.
+    /// By default, it is set to `None`: 2100.
+    #[cfg(feature = "optional_sload_gas")]
+    pub sload_cold_gas: Option<u64>,
+    /// If some it overrides the EIP-2929 gas cost of `SLOAD` on a warm storage slot, including
+    /// slots warmed by the transaction's access list. It is also the cost of `SSTORE` writes that
+    /// do not change the original value, and deducted from their refunds.
+    /// By default, it is set to `None`: 100.
+    #[cfg(feature = "optional_sload_gas")]
+    pub sload_warm_gas: Option<u64>,
+    /// If some it overrides the EIP-3529 maximum refund quotient: the final refund is capped at
+    /// `gas_spent / quotient`. Useful for research chains that tune refunds.
+    /// By default, it is set to `None`: 5 since London and 2 before.
+    #[cfg(feature = "optional_refund_quotient")]
+    pub refund_quotient: Option<NonZeroU64>,
+    /// If some, transactions with more input bytes are rejected with
+    /// [`InvalidTransaction::CallDataTooLarge`](crate::result::InvalidTransaction::CallDataTooLarge).
+    /// Bounds the memory of calldata heavy transactions in sandboxes.
+    /// By default, it is set to `None`: the size is only limited by the gas limit.
+    #[cfg(feature = "optional_max_calldata_size")]
+    pub max_calldata_size: Option<usize>,
+    /// If some, bounds the output of a sub call or create copied into the return data buffer of
+    /// its caller, which is handled according to [`Self::return_data_limit_mode`]. Protects
+    /// against contracts inflating their caller's memory with huge return data.
+    /// By default, it is set to `None`.
+    #[cfg(feature = "optional_max_return_data_size")]
+    pub max_return_data_size: Option<usize>,
+    /// How output larger than [`Self::max_return_data_size`] is handled.
+    /// By default, it is set to [`ReturnDataLimitMode::Truncate`].
+    #[cfg(feature = "optional_max_return_data_size")]
+    pub return_data_limit_mode: ReturnDataLimitMode,
+    /// Multiplies the gas cost of instructions by this value per call depth, the transaction's
+    /// frame being at depth zero. Not part of any consensus, meant for stress testing.
+    /// By default, it is set to `None`.
+    #[cfg(feature = "optional_depth_gas_multiplier")]
+    pub depth_gas_multiplier: Option<NonZeroU64>,
 }
 
 impl CfgEnv {
     /// Returns max code size from [`Self::limit_contract_code_size`] if set
     /// or default [`MAX_CODE_SIZE`] value.
+    ///
+    /// Returns [`usize::MAX`] if [`Self::is_size_limits_disabled`].
     pub fn max_code_size(&self) -> usize {
+        if self.is_size_limits_disabled() {
+            return usize::MAX;
+        }
         self.limit_contract_code_size.unwrap_or(MAX_CODE_SIZE)
     }
 
+    /// Returns max initcode size from [`Self::limit_contract_initcode_size()`] if set, double of
+    /// [`Self::limit_contract_code_size`] if set or default [`MAX_INITCODE_SIZE`] value.
+    ///
+    /// Returns [`usize::MAX`] if [`Self::is_size_limits_disabled`].
+    pub fn max_initcode_size(&self) -> usize {
+        if self.is_size_limits_disabled() {
+            return usize::MAX;
+        }
+        self.limit_contract_initcode_size()
+            .or_else(|| {
+                self.limit_contract_code_size
+                    .map(|limit| limit.saturating_mul(2))
+            })
+            .unwrap_or(MAX_INITCODE_SIZE)
+    }
+
+    /// Returns the maximum refund quotient from [`Self::refund_quotient()`] if set
+    /// or the EIP-3529 value for the given fork.
+    pub fn max_refund_quotient(&self, is_london: bool) -> NonZeroU64 {
+        self.refund_quotient()
+            .unwrap_or_else(|| NonZeroU64::new(if is_london { 5 } else { 2 }).unwrap())
+    }
+
+    /// Returns [`Self::keccak256_hasher`] if set, keccak256 otherwise.
+    pub fn hasher(&self) -> Keccak256Hasher {
+        self.keccak256_hasher.unwrap_or_default()
+    }
+
     pub fn with_chain_id(mut self, chain_id: u64) -> Self {
         self.chain_id = chain_id;
         self
     }
 
+    /// Sets the KZG settings used by the point evaluation precompile.
+    ///
+    /// Use [`EnvKzgSettings::Custom`](crate::kzg::EnvKzgSettings::Custom) to run against a trusted
+    /// setup other than the mainnet one.
+    #[cfg(any(feature = "c-kzg", feature = "kzg-rs"))]
+    pub fn with_kzg_settings(mut self, kzg_settings: crate::kzg::EnvKzgSettings) -> Self {
+        self.kzg_settings = kzg_settings;
+        self
+    }
+
     #[cfg(feature = "optional_eip3607")]
     pub fn is_eip3607_disabled(&self) -> bool {
         self.disable_eip3607
@@ -196,18 +438,147 @@ impl CfgEnv {
         false
     }
 
+    #[cfg(feature = "optional_lazy_beneficiary_reward")]
+    pub fn is_lazy_beneficiary_reward_enabled(&self) -> bool {
+        self.lazy_beneficiary_reward
+    }
+
+    #[cfg(not(feature = "optional_lazy_beneficiary_reward"))]
+    pub fn is_lazy_beneficiary_reward_enabled(&self) -> bool {
+        false
+    }
+
+    #[cfg(feature = "optional_initcode_size_limit")]
+    pub fn limit_contract_initcode_size(&self) -> Option<usize> {
+        self.limit_contract_initcode_size
+    }
+
+    #[cfg(not(feature = "optional_initcode_size_limit"))]
+    pub fn limit_contract_initcode_size(&self) -> Option<usize> {
+        None
+    }
+
+    #[cfg(feature = "optional_size_limits")]
+    pub fn is_size_limits_disabled(&self) -> bool {
+        self.disable_size_limits
+    }
+
+    #[cfg(not(feature = "optional_size_limits"))]
+    pub fn is_size_limits_disabled(&self) -> bool {
+        false
+    }
+
+    #[cfg(feature = "optional_keccak256_gas")]
+    pub fn keccak256_gas(&self) -> Option<u64> {
+        self.keccak256_gas
+    }
+
+    #[cfg(not(feature = "optional_keccak256_gas"))]
+    pub fn keccak256_gas(&self) -> Option<u64> {
+        None
+    }
+
+    #[cfg(feature = "optional_keccak256_gas")]
+    pub fn keccak256_word_gas(&self) -> Option<u64> {
+        self.keccak256_word_gas
+    }
+
+    #[cfg(not(feature = "optional_keccak256_gas"))]
+    pub fn keccak256_word_gas(&self) -> Option<u64> {
+        None
+    }
+
+    #[cfg(feature = "optional_sload_gas")]
+    pub fn sload_cold_gas(&self) -> Option<u64> {
+        self.sload_cold_gas
+    }
+
+    #[cfg(not(feature = "optional_sload_gas"))]
+    pub fn sload_cold_gas(&self) -> Option<u64> {
+        None
+    }
+
+    #[cfg(feature = "optional_sload_gas")]
+    pub fn sload_warm_gas(&self) -> Option<u64> {
+        self.sload_warm_gas
+    }
+
+    #[cfg(not(feature = "optional_sload_gas"))]
+    pub fn sload_warm_gas(&self) -> Option<u64> {
+        None
+    }
+
+    #[cfg(feature = "optional_refund_quotient")]
+    pub fn refund_quotient(&self) -> Option<NonZeroU64> {
+        self.refund_quotient
+    }
+
+    #[cfg(not(feature = "optional_refund_quotient"))]
+    pub fn refund_quotient(&self) -> Option<NonZeroU64> {
+        None
+    }
+
+    #[cfg(feature = "optional_max_calldata_size")]
+    pub fn max_calldata_size(&self) -> Option<usize> {
+        self.max_calldata_size
+    }
+
+    #[cfg(not(feature = "optional_max_calldata_size"))]
+    pub fn max_calldata_size(&self) -> Option<usize> {
+        None
+    }
+
+    #[cfg(feature = "optional_max_return_data_size")]
+    pub fn max_return_data_size(&self) -> Option<usize> {
+        self.max_return_data_size
+    }
+
+    #[cfg(not(feature = "optional_max_return_data_size"))]
+    pub fn max_return_data_size(&self) -> Option<usize> {
+        None
+    }
+
+    #[cfg(feature = "optional_max_return_data_size")]
+    pub fn return_data_limit_mode(&self) -> ReturnDataLimitMode {
+        self.return_data_limit_mode
+    }
+
+    #[cfg(not(feature = "optional_max_return_data_size"))]
+    pub fn return_data_limit_mode(&self) -> ReturnDataLimitMode {
+        ReturnDataLimitMode::default()
+    }
+
+    #[cfg(feature = "optional_depth_gas_multiplier")]
+    pub fn depth_gas_multiplier(&self) -> Option<NonZeroU64> {
+        self.depth_gas_multiplier
+    }
+
+    #[cfg(not(feature = "optional_depth_gas_multiplier"))]
+    pub fn depth_gas_multiplier(&self) -> Option<NonZeroU64> {
+        None
+    }
+
+    #[allow(deprecated)]
     pub const fn is_nonce_check_disabled(&self) -> bool {
-        self.disable_nonce_check
+        self.disable_nonce_check || matches!(self.nonce_check_mode, NonceCheckMode::Disabled)
     }
 }
 
 impl Default for CfgEnv {
+    #[allow(deprecated)]
     fn default() -> Self {
         Self {
             chain_id: 1,
             perf_analyse_created_bytecodes: AnalysisKind::default(),
             limit_contract_code_size: None,
+            nonce_check_mode: NonceCheckMode::default(),
             disable_nonce_check: false,
+            max_depth: CALL_STACK_LIMIT,
+            read_only: false,
+            disable_block_hash_cache: false,
+            keccak256_hasher: None,
+            analysis_cache_size: 0,
+            result_cache_size: 1024,
             #[cfg(any(feature = "c-kzg", feature = "kzg-rs"))]
             kzg_settings: crate::kzg::EnvKzgSettings::Default,
             #[cfg(feature = "memory_limit")]
@@ -222,6 +593,30 @@ impl Default for CfgEnv {
             disable_gas_refund: false,
             #[cfg(feature = "optional_no_base_fee")]
             disable_base_fee: false,
+            #[cfg(feature = "optional_lazy_beneficiary_reward")]
+            lazy_beneficiary_reward: false,
+            #[cfg(feature = "optional_initcode_size_limit")]
+            limit_contract_initcode_size: None,
+            #[cfg(feature = "optional_size_limits")]
+            disable_size_limits: false,
+            #[cfg(feature = "optional_keccak256_gas")]
+            keccak256_gas: None,
+            #[cfg(feature = "optional_keccak256_gas")]
+            keccak256_word_gas: None,
+            #[cfg(feature = "optional_sload_gas")]
+            sload_cold_gas: None,
+            #[cfg(feature = "optional_sload_gas")]
+            sload_warm_gas: None,
+            #[cfg(feature = "optional_refund_quotient")]
+            refund_quotient: None,
+            #[cfg(feature = "optional_max_calldata_size")]
+            max_calldata_size: None,
+            #[cfg(feature = "optional_max_return_data_size")]
+            max_return_data_size: None,
+            #[cfg(feature = "optional_max_return_data_size")]
+            return_data_limit_mode: ReturnDataLimitMode::default(),
+            #[cfg(feature = "optional_depth_gas_multiplier")]
+            depth_gas_multiplier: None,
         }
     }
 }
@@ -278,8 +673,96 @@ pub enum AnalysisKind {
     Analyse,
 }
 
+/// How the transaction's nonce is validated against the account's nonce.
+#[derive(Clone, Copy, Default, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum NonceCheckMode {
+    /// The nonce must be equal to the account's nonce.
+    #[default]
+    Strict,
+    /// The nonce must not be lower than the account's nonce. Useful to simulate a transaction
+    /// assuming the ones before it are included.
+    AllowHigher,
+    /// The nonce is not validated.
+    Disabled,
+}
+
+/// How [`CfgEnv::max_return_data_size()`] is enforced.
+#[derive(Clone, Copy, Default, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ReturnDataLimitMode {
+    /// The output is truncated to the limit, the caller continues.
+    #[default]
+    Truncate,
+    /// The caller halts with `MemoryLimitOOG`, spending all its gas.
+    Halt,
+}
+
 #[cfg(test)]
 mod tests {
+    use super::*;
+    use primitives::address;
+
+    #[test]
+    #[allow(deprecated)]
+    fn deprecated_disable_nonce_check() {
+        let cfg = CfgEnv {
+            disable_nonce_check: true,
+            ..Default::default()
+        };
+        assert_eq!(cfg.nonce_check_mode, NonceCheckMode::Strict);
+        assert!(cfg.is_nonce_check_disabled());
+        assert!(!CfgEnv::default().is_nonce_check_disabled());
+    }
+
+    #[test]
+    fn create_address_with_hasher() {
+        let caller = address!("1000000000000000000000000000000000000000");
+        let hasher = CfgEnv::default().hasher();
+        for nonce in [0, 1, 0x7f, 0x80, 0xff, 0x1234, u64::MAX] {
+            assert_eq!(hasher.create_address(caller, nonce), caller.create(nonce));
+        }
+        let salt = B256::repeat_byte(0x11);
+        let init_code_hash = keccak256([0x00]);
+        assert_eq!(
+            hasher.create2_address(caller, salt, init_code_hash),
+            caller.create2(salt, init_code_hash)
+        );
+    }
+
+    #[test]
+    fn keccak256_hasher_eq() {
+        fn zero(_: &[u8]) -> B256 {
+            B256::ZERO
+        }
+        let hasher = Keccak256Hasher::new(zero);
+        assert_eq!(hasher, Keccak256Hasher::new(zero));
+        assert_ne!(hasher, Keccak256Hasher::KECCAK256);
+        assert_eq!(Keccak256Hasher::default(), Keccak256Hasher::KECCAK256);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn keccak256_hasher_serde() {
+        let cfg = CfgEnv::default();
+        let json = serde_json::to_string(&cfg).unwrap();
+        assert!(!json.contains("keccak256_hasher"));
+        assert_eq!(serde_json::from_str::<CfgEnv>(&json).unwrap(), cfg);
+
+        let cfg = CfgEnv {
+            keccak256_hasher: Some(Keccak256Hasher::new(|_| B256::ZERO)),
+            ..Default::default()
+        };
+        let json = serde_json::to_string(&cfg).unwrap();
+        assert!(!json.contains("keccak256_hasher"));
+        assert_eq!(
+            serde_json::from_str::<CfgEnv>(&json)
+                .unwrap()
+                .keccak256_hasher,
+            None
+        );
+    }
+
     // use super::*;
     // use crate::default::block::BlockEnv;
     // use specification::hardfork::{FrontierSpec, LatestSpec};
@@ -252,3 +252,84 @@ impl Transaction for TxEnv {
         self
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use specification::eip7702::{Authorization as SignedAuth, RecoveredAuthorization, Signature};
+
+    #[test]
+    fn eip7702_authorization_list() {
+        let authority = Address::with_last_byte(0xaa);
+        let delegate = Address::with_last_byte(0xde);
+        let tx = TxEnv {
+            tx_type: TransactionType::Eip7702,
+            authorization_list: vec![RecoveredAuthorization::new_unchecked(
+                SignedAuth {
+                    chain_id: U256::from(1),
+                    address: delegate,
+                    nonce: 7,
+                }
+                .into_signed(Signature::test_signature()),
+                Some(authority),
+            )]
+            .into(),
+            ..Default::default()
+        };
+
+        let auths = tx
+            .authorization_list()
+            .map(|auth| {
+                (
+                    auth.authority(),
+                    auth.address(),
+                    auth.nonce(),
+                    auth.chain_id(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(auths, vec![(Some(authority), delegate, 7, U256::from(1))]);
+    }
+
+    #[test]
+    fn effective_gas_price() {
+        let tx = |tx_type| TxEnv {
+            tx_type,
+            gas_price: U256::from(100),
+            gas_priority_fee: Some(U256::from(10)),
+            max_fee_per_blob_gas: Some(U256::from(1_000)),
+            ..Default::default()
+        };
+
+        // Legacy transactions pay their gas price whatever the base fee.
+        for tx_type in [TransactionType::Legacy, TransactionType::Eip2930] {
+            for base_fee in [0, 50, 100] {
+                assert_eq!(
+                    tx(tx_type).effective_gas_price(U256::from(base_fee)),
+                    U256::from(100)
+                );
+            }
+        }
+
+        // Others pay the base fee and the priority fee, up to their max fee. The blob gas price
+        // is not part of it.
+        for tx_type in [
+            TransactionType::Eip1559,
+            TransactionType::Eip4844,
+            TransactionType::Eip7702,
+        ] {
+            for (base_fee, price) in [(0, 10), (50, 60), (90, 100), (95, 100)] {
+                assert_eq!(
+                    tx(tx_type).effective_gas_price(U256::from(base_fee)),
+                    U256::from(price)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn legacy_authorization_list_is_empty() {
+        let tx = TxEnv::default();
+        assert_eq!(tx.authorization_list().count(), 0);
+    }
+}
@@ -1,7 +1,7 @@
 use crate::{evm_wiring::HaltReasonTrait, EvmWiring};
 use core::fmt::{self, Debug};
 use database_interface::Database;
-use primitives::{Address, Bytes, Log, U256};
+use primitives::{Address, Bloom, Bytes, Log, U256};
 use specification::eip7702::InvalidAuthorization;
 use state::EvmState;
 use std::{boxed::Box, string::String, vec::Vec};
@@ -27,6 +27,81 @@ pub struct ResultAndState<HaltReasonT: HaltReasonTrait> {
     pub result: ExecutionResult<HaltReasonT>,
     /// State that got updated
     pub state: EvmState,
+    /// Intrinsic gas of the transaction, included in the gas used of the result.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub intrinsic_gas: u64,
+    /// Gas refund of the transaction before the EIP-3529 cap, including the EIP-7702 refund of
+    /// authorizations to existing accounts. If the execution failed, only the EIP-7702 refund is
+    /// left as it does not depend on the execution.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub gas_refunded_uncapped: u64,
+    /// Beneficiary reward deferred by `CfgEnv::is_lazy_beneficiary_reward_enabled`, zero if it was paid.
+    ///
+    /// Added to the pending rewards when the transaction is committed.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub deferred_reward: U256,
+    /// Priority fee (tip) credited to the block beneficiary, whether paid or deferred.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub priority_fee_paid: U256,
+}
+
+impl<HaltReasonT: HaltReasonTrait> ResultAndState<HaltReasonT> {
+    /// Returns the intrinsic gas of the transaction.
+    ///
+    /// Gas spent on execution is `result.gas_used() - intrinsic_gas()`.
+    pub const fn intrinsic_gas(&self) -> u64 {
+        self.intrinsic_gas
+    }
+
+    /// Returns the gas refund of the transaction before it was capped.
+    ///
+    /// The refund lost to the cap is `gas_refunded_uncapped() - gas_refunded`.
+    pub const fn gas_refunded_uncapped(&self) -> u64 {
+        self.gas_refunded_uncapped
+    }
+
+    /// Returns the priority fee (tip) paid to the block beneficiary.
+    ///
+    /// This is the reward credited by the beneficiary reward handler: the effective gas price
+    /// less the base fee after London, times the gas used. Zero for transactions that pay no
+    /// reward, e.g. Optimism deposits.
+    pub const fn priority_fee_paid(&self) -> U256 {
+        self.priority_fee_paid
+    }
+
+    /// Returns the receipt of the transaction.
+    ///
+    /// `cumulative_gas_used` is the gas used by the block up to and including this transaction.
+    pub fn to_receipt(&self, cumulative_gas_used: u64) -> Receipt {
+        let logs = self.result.logs().to_vec();
+        let mut logs_bloom = Bloom::ZERO;
+        for log in &logs {
+            logs_bloom.accrue_log(log);
+        }
+        Receipt {
+            status: self.result.is_success(),
+            cumulative_gas_used,
+            logs_bloom,
+            logs,
+        }
+    }
+}
+
+/// Receipt of a transaction, with the fields of the consensus receipt in their order.
+///
+/// Returned by [`ResultAndState::to_receipt`]. The transaction type, which prefixes the encoding
+/// of typed receipts, is not included.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Receipt {
+    /// EIP-658 status, `true` if the transaction succeeded.
+    pub status: bool,
+    /// Gas used by the block up to and including the transaction.
+    pub cumulative_gas_used: u64,
+    /// Bloom filter of the addresses and topics of the logs.
+    pub logs_bloom: Bloom,
+    /// Logs emitted by the transaction, empty if it failed.
+    pub logs: Vec<Log>,
 }
 
 /// Result of a transaction execution.
@@ -272,6 +347,11 @@ pub enum InvalidTransaction {
     },
     /// EIP-3860: Limit and meter initcode
     CreateInitCodeSizeLimit,
+    /// Transaction input is larger than [`CfgEnv::max_calldata_size()`](crate::default::CfgEnv::max_calldata_size).
+    CallDataTooLarge {
+        max: usize,
+        have: usize,
+    },
     /// Transaction chain id does not match the config chain id.
     InvalidChainId,
     /// Access list is not supported for blocks before the Berlin hardfork.
@@ -360,6 +440,9 @@ impl fmt::Display for InvalidTransaction {
             Self::CreateInitCodeSizeLimit => {
                 write!(f, "create initcode size limit")
             }
+            Self::CallDataTooLarge { max, have } => {
+                write!(f, "call data too large, have {have}, max {max}")
+            }
             Self::InvalidChainId => write!(f, "invalid chain ID"),
             Self::AccessListNotSupported => write!(f, "access list not supported"),
             Self::MaxFeePerBlobGasNotSupported => {
@@ -461,6 +544,8 @@ pub enum HaltReason {
     EOFFunctionStackOverflow,
     /// Check for target address validity is only done inside subcall.
     InvalidEXTCALLTarget,
+    /// State change attempted while [`CfgEnv::read_only`](crate::default::CfgEnv::read_only) is set.
+    ReadOnlyViolation,
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
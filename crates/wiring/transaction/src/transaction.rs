@@ -1,6 +1,6 @@
 use crate::{
-    eip1559::Eip1559CommonTxFields, AccessListTrait, CommonTxFields, Eip1559Tx, Eip2930Tx,
-    Eip4844Tx, Eip7702Tx, LegacyTx, TransactionType,
+    eip1559::Eip1559CommonTxFields, eip7702::Authorization, AccessListTrait, CommonTxFields,
+    Eip1559Tx, Eip2930Tx, Eip4844Tx, Eip7702Tx, LegacyTx, TransactionType,
 };
 use core::cmp::min;
 use core::fmt::Debug;
@@ -84,6 +84,9 @@ pub trait Transaction {
 
     /// Effective gas price is gas price field for Legacy and Eip2930 transaction
     /// While for transactions after Eip1559 it is minimum of max_fee and base+max_priority_fee.
+    ///
+    /// This is the price of execution gas, returned by `GASPRICE` and used for the beneficiary
+    /// reward. The blob gas of EIP-4844 transactions is paid separately at the blob gas price.
     fn effective_gas_price(&self, base_fee: U256) -> U256 {
         let tx_type = self.tx_type().into();
         let (max_fee, max_priority_fee) = match tx_type {
@@ -104,7 +107,10 @@ pub trait Transaction {
             TransactionType::Custom => unimplemented!("Custom tx not supported"),
         };
 
-        min(U256::from(max_fee), base_fee + U256::from(max_priority_fee))
+        min(
+            U256::from(max_fee),
+            base_fee.saturating_add(U256::from(max_priority_fee)),
+        )
     }
 
     /// Transaction kind.
@@ -132,4 +138,16 @@ pub trait Transaction {
             TransactionType::Custom => unimplemented!("Custom tx not supported"),
         }
     }
+
+    /// Returns EIP-7702 authorizations. Each of them exposes its authority (the account whose
+    /// code gets delegated) and the address the code is delegated to.
+    ///
+    /// Empty for all transaction types other than EIP-7702.
+    fn authorization_list(&self) -> impl Iterator<Item = impl Authorization> {
+        let is_eip7702 = self.tx_type().into() == TransactionType::Eip7702;
+        is_eip7702
+            .then(|| self.eip7702().authorization_list_iter())
+            .into_iter()
+            .flatten()
+    }
 }
@@ -0,0 +1,120 @@
+//! Wiring of a chain without base fee, built by overriding handles of the mainnet handler.
+#![cfg_attr(not(test), warn(unused_crate_dependencies))]
+
+use anyhow::{anyhow, bail};
+use database::InMemoryDB;
+use revm::{
+    database_interface::Database,
+    interpreter::Gas,
+    primitives::{address, TxKind, U256},
+    specification::hardfork::SpecId,
+    state::AccountInfo,
+    wiring::{
+        default::{block::BlockEnv, EnvWiring, TxEnv},
+        result::{EVMError, EVMResultGeneric, HaltReason},
+        Block, EvmWiring as PrimitiveEvmWiring,
+    },
+    Context, Evm, EvmHandler, EvmWiring,
+};
+use std::{marker::PhantomData, sync::Arc};
+
+/// Wiring of a chain that ignores the base fee of its blocks.
+///
+/// Transactions are not required to pay the base fee and the whole gas fee goes to the
+/// beneficiary instead of being burnt.
+#[derive(Debug)]
+struct NoBaseFeeWiring<DB: Database>(PhantomData<DB>);
+
+impl<DB: Database> PrimitiveEvmWiring for NoBaseFeeWiring<DB> {
+    type ExternalContext = ();
+    type ChainContext = ();
+    type Database = DB;
+    type Block = BlockEnv;
+    type Transaction = TxEnv;
+    type Hardfork = SpecId;
+    type HaltReason = HaltReason;
+}
+
+impl<DB: Database> EvmWiring for NoBaseFeeWiring<DB> {
+    fn handler<'evm>(hardfork: Self::Hardfork) -> EvmHandler<'evm, Self> {
+        let mut handler = EvmHandler::mainnet_with_spec(hardfork);
+
+        // Validate the environment as if the block had no base fee.
+        let validate_env = handler.validation.env.clone();
+        handler.validation.env = Arc::new(move |env: &EnvWiring<Self>| {
+            let mut env = env.clone();
+            env.block.basefee = U256::ZERO;
+            validate_env(&env)
+        });
+        handler.post_execution.reward_beneficiary = Arc::new(reward_beneficiary::<Self>);
+
+        handler
+    }
+}
+
+/// Pays the whole fee of the spent gas to the beneficiary.
+fn reward_beneficiary<EvmWiringT: EvmWiring>(
+    context: &mut Context<EvmWiringT>,
+    gas: &Gas,
+) -> EVMResultGeneric<(), EvmWiringT> {
+    let beneficiary = *context.evm.env.block.coinbase();
+    let reward = context
+        .evm
+        .env
+        .effective_gas_price()
+        .saturating_mul(U256::from(gas.spent() - gas.refunded() as u64));
+
+    let beneficiary = context
+        .evm
+        .load_account(beneficiary)
+        .map_err(EVMError::Database)?;
+    beneficiary.data.mark_touch();
+    beneficiary.data.info.balance = beneficiary.data.info.balance.saturating_add(reward);
+    Ok(())
+}
+
+fn main() -> anyhow::Result<()> {
+    let caller = address!("1000000000000000000000000000000000000000");
+    let recipient = address!("2000000000000000000000000000000000000000");
+    let beneficiary = address!("3000000000000000000000000000000000000000");
+
+    let mut db = InMemoryDB::default();
+    db.insert_account_info(caller, AccountInfo::from_balance(U256::from(1_000_000)));
+
+    let mut evm = Evm::<NoBaseFeeWiring<InMemoryDB>>::builder()
+        .with_db(db)
+        .with_default_ext_ctx()
+        .modify_block_env(|block| {
+            block.coinbase = beneficiary;
+            block.basefee = U256::from(1_000);
+        })
+        .modify_tx_env(|tx| {
+            tx.caller = caller;
+            tx.transact_to = TxKind::Call(recipient);
+            tx.value = U256::from(100);
+            tx.gas_limit = 21_000;
+            // Below the base fee of the block, which the chain ignores.
+            tx.gas_price = U256::from(1);
+        })
+        .build();
+
+    let result = evm.transact()?;
+    if !result.result.is_success() {
+        bail!("Transfer failed: {result:#?}");
+    }
+
+    let balance = |address| {
+        result
+            .state
+            .get(&address)
+            .map(|account| account.info.balance)
+            .ok_or_else(|| anyhow!("Account {address} not found"))
+    };
+    println!("caller balance: {}", balance(caller)?);
+    println!("recipient balance: {}", balance(recipient)?);
+    println!("beneficiary balance: {}", balance(beneficiary)?);
+    assert_eq!(balance(caller)?, U256::from(1_000_000 - 100 - 21_000));
+    assert_eq!(balance(recipient)?, U256::from(100));
+    assert_eq!(balance(beneficiary)?, U256::from(21_000));
+    Ok(())
+}